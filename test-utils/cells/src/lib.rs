@@ -13,6 +13,10 @@ use std::ops::Range;
 use proptest::bits::{BitSetLike, VarBitSet};
 
 use tiledb_common::datatype::physical::{BitsEq, BitsOrd};
+use tiledb_common::query::condition::eval::{
+    CellValue, Error as EvalError, Row,
+};
+use tiledb_common::query::condition::QueryConditionExpr;
 
 pub use self::field::FieldData;
 
@@ -255,6 +259,26 @@ impl Cells {
         count
     }
 
+    /// Returns one record of the cells.
+    pub fn row(&self, index: usize) -> CellsRow<'_> {
+        CellsRow { cells: self, index }
+    }
+
+    /// Returns the set of records which satisfy a query condition.
+    /// Use `Self::filter` to select just those records.
+    pub fn evaluate(
+        &self,
+        qc: &QueryConditionExpr,
+    ) -> Result<VarBitSet, EvalError> {
+        let mut set = VarBitSet::new_bitset(self.len());
+        for index in 0..self.len() {
+            if qc.evaluate(&self.row(index))? {
+                set.set(index);
+            }
+        }
+        Ok(set)
+    }
+
     /// Returns a subset of the records using the bitmap to determine which are included
     pub fn filter(&self, set: &VarBitSet) -> Cells {
         Self::new(
@@ -347,6 +371,37 @@ impl BitsEq for Cells {
     }
 }
 
+/// One record of a `Cells`.
+///
+/// Fields with a single value per cell are accessed as that value,
+/// and `VecUInt8` fields are accessed as strings.
+pub struct CellsRow<'a> {
+    cells: &'a Cells,
+    index: usize,
+}
+
+impl Row for CellsRow<'_> {
+    fn value(&self, field: &str) -> Result<CellValue<'_>, EvalError> {
+        let Some(data) = self.cells.fields.get(field) else {
+            return Err(EvalError::NoSuchField(field.to_owned()));
+        };
+        if let FieldData::VecUInt8(ref values) = data {
+            return Ok(CellValue::String(&values[self.index]));
+        }
+        typed_field_data_go!(
+            data,
+            _DT,
+            ref values,
+            Ok(CellValue::from(values[self.index])),
+            {
+                // cells with multiple values cannot be compared
+                let _ = values;
+                Err(EvalError::UnsupportedField(field.to_owned()))
+            }
+        )
+    }
+}
+
 pub struct StructuredCells {
     dimensions: Vec<usize>,
     cells: Cells,
@@ -992,4 +1047,50 @@ mod tests {
             do_cells_projection(cells, keys)
         }
     }
+
+    #[test]
+    fn cells_evaluate() {
+        use tiledb_common::query::condition::QueryConditionExpr as QC;
+
+        let cells = Cells::new(HashMap::from([
+            ("a".to_owned(), FieldData::Int32(vec![1, 2, 3, 4])),
+            (
+                "s".to_owned(),
+                FieldData::from(vec![
+                    "foo".to_owned(),
+                    "bar".to_owned(),
+                    "baz".to_owned(),
+                    "foo".to_owned(),
+                ]),
+            ),
+            (
+                "v".to_owned(),
+                FieldData::VecInt32(vec![vec![1], vec![2], vec![3], vec![4]]),
+            ),
+        ]));
+
+        let qc = QC::field("a").gt(1) & QC::field("s").ne("baz");
+        let matches = cells.evaluate(&qc).unwrap();
+        assert_eq!(
+            vec![false, true, false, true],
+            (0..cells.len())
+                .map(|i| matches.test(i))
+                .collect::<Vec<_>>()
+        );
+
+        let expect = Cells::new(HashMap::from([
+            ("a".to_owned(), FieldData::Int32(vec![2, 4])),
+            (
+                "s".to_owned(),
+                FieldData::from(vec!["bar".to_owned(), "foo".to_owned()]),
+            ),
+            ("v".to_owned(), FieldData::VecInt32(vec![vec![2], vec![4]])),
+        ]));
+        assert_eq!(expect, cells.filter(&matches));
+
+        assert_eq!(
+            EvalError::UnsupportedField("v".to_owned()),
+            cells.evaluate(&QC::field("v").eq(1)).unwrap_err()
+        );
+    }
 }
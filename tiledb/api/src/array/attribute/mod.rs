@@ -38,6 +38,10 @@ impl Drop for RawAttribute {
     }
 }
 
+// SAFETY: only `Builder` modifies an attribute, and it owns the handle.
+unsafe impl Send for RawAttribute {}
+unsafe impl Sync for RawAttribute {}
+
 pub struct Attribute {
     context: Context,
     raw: RawAttribute,
@@ -0,0 +1,250 @@
+use std::ops::Deref;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::context::CApiInterface;
+use crate::{Array, Result as TileDBResult};
+
+pub(crate) enum RawConsolidationPlan {
+    Owned(*mut ffi::tiledb_consolidation_plan_t),
+}
+
+impl Deref for RawConsolidationPlan {
+    type Target = *mut ffi::tiledb_consolidation_plan_t;
+    fn deref(&self) -> &Self::Target {
+        let RawConsolidationPlan::Owned(ref ffi) = *self;
+        ffi
+    }
+}
+
+impl Drop for RawConsolidationPlan {
+    fn drop(&mut self) {
+        let RawConsolidationPlan::Owned(ref mut ffi) = *self;
+        unsafe { ffi::tiledb_consolidation_plan_free(ffi) }
+    }
+}
+
+// SAFETY: a consolidation plan is not modified after libtiledb creates it,
+// and is only read while `ConsolidationPlan::new` copies out its nodes.
+unsafe impl Send for RawConsolidationPlan {}
+unsafe impl Sync for RawConsolidationPlan {}
+
+/// A group of fragments which would be consolidated together.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ConsolidationPlanNode {
+    pub fragment_uris: Vec<String>,
+}
+
+impl ConsolidationPlanNode {
+    /// Returns the names of the fragments of this node, which is the form
+    /// expected by [Array::consolidate_fragments].
+    pub fn fragment_names(&self) -> Vec<&str> {
+        self.fragment_uris
+            .iter()
+            .map(|uri| {
+                let uri = uri.trim_end_matches('/');
+                uri.rsplit('/').next().unwrap_or(uri)
+            })
+            .collect()
+    }
+}
+
+/// Describes how libtiledb would consolidate the fragments of an array
+/// so that the resulting fragments are no larger than a target size.
+///
+/// Each node of the plan can be consolidated independently:
+///
+/// ```no_run
+/// # use tiledb_api::{Array, Context, Result as TileDBResult};
+/// # use tiledb_api::array::{ConsolidationPlan, Mode};
+/// # fn consolidate(ctx: &Context, uri: &str) -> TileDBResult<()> {
+/// let plan = {
+///     let array = Array::open(ctx, uri, Mode::Read)?;
+///     ConsolidationPlan::new(&array, 1 << 30)?
+/// };
+/// for node in plan.iter().filter(|n| n.fragment_uris.len() > 1) {
+///     Array::consolidate_fragments(ctx, uri, &node.fragment_names(), None)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ConsolidationPlan {
+    pub nodes: Vec<ConsolidationPlanNode>,
+}
+
+impl ConsolidationPlan {
+    /// Computes the consolidation plan for `array` with a target
+    /// fragment size of `fragment_size` bytes.
+    pub fn new(array: &Array, fragment_size: u64) -> TileDBResult<Self> {
+        let c_array = **array.capi();
+        let mut c_plan: *mut ffi::tiledb_consolidation_plan_t = out_ptr!();
+        array.capi_call(|ctx| unsafe {
+            ffi::tiledb_consolidation_plan_create_with_mbr(
+                ctx,
+                c_array,
+                fragment_size,
+                &mut c_plan,
+            )
+        })?;
+
+        let raw = RawConsolidationPlan::Owned(c_plan);
+        let c_plan = *raw;
+
+        let mut c_num_nodes: u64 = 0;
+        array.capi_call(|ctx| unsafe {
+            ffi::tiledb_consolidation_plan_get_num_nodes(
+                ctx,
+                c_plan,
+                &mut c_num_nodes,
+            )
+        })?;
+
+        let nodes = (0..c_num_nodes)
+            .map(|node| {
+                let mut c_num_fragments: u64 = 0;
+                array.capi_call(|ctx| unsafe {
+                    ffi::tiledb_consolidation_plan_get_num_fragments(
+                        ctx,
+                        c_plan,
+                        node,
+                        &mut c_num_fragments,
+                    )
+                })?;
+
+                let fragment_uris = (0..c_num_fragments)
+                    .map(|fragment| {
+                        let mut c_uri: *const std::ffi::c_char = out_ptr!();
+                        array.capi_call(|ctx| unsafe {
+                            ffi::tiledb_consolidation_plan_get_fragment_uri(
+                                ctx, c_plan, node, fragment, &mut c_uri,
+                            )
+                        })?;
+
+                        // N.B. The URI is owned by the plan,
+                        // so we do *not* free it.
+                        let uri = unsafe { std::ffi::CStr::from_ptr(c_uri) };
+                        Ok(String::from(uri.to_string_lossy()))
+                    })
+                    .collect::<TileDBResult<Vec<_>>>()?;
+
+                Ok(ConsolidationPlanNode { fragment_uris })
+            })
+            .collect::<TileDBResult<Vec<_>>>()?;
+
+        Ok(ConsolidationPlan { nodes })
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, ConsolidationPlanNode> {
+        self.nodes.iter()
+    }
+}
+
+impl IntoIterator for ConsolidationPlan {
+    type Item = ConsolidationPlanNode;
+    type IntoIter = std::vec::IntoIter<ConsolidationPlanNode>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ConsolidationPlan {
+    type Item = &'a ConsolidationPlanNode;
+    type IntoIter = std::slice::Iter<'a, ConsolidationPlanNode>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use uri::{self, TestArrayUri};
+
+    use super::*;
+    use crate::array::fragment_info::tests::create_sparse_array;
+    use crate::array::fragment_info::Builder as FragmentInfoBuilder;
+    use crate::array::Mode;
+    use crate::error::Error;
+    use crate::Context;
+
+    #[test]
+    fn consolidation_plan() -> TileDBResult<()> {
+        let ctx = Context::new()?;
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let array_uri = create_sparse_array(&ctx, &test_uri)?;
+
+        let fragment_names = || -> TileDBResult<HashSet<String>> {
+            FragmentInfoBuilder::new(&ctx, &array_uri)?
+                .build()?
+                .iter()?
+                .map(|f| f.name())
+                .collect()
+        };
+        let before = fragment_names()?;
+        assert_eq!(2, before.len());
+
+        let plan = {
+            let array = Array::open(&ctx, &array_uri, Mode::Read)?;
+            ConsolidationPlan::new(&array, 1024 * 1024)?
+        };
+
+        // the two small, overlapping fragments are merged
+        assert_eq!(1, plan.num_nodes());
+        for node in plan.iter() {
+            for name in node.fragment_names() {
+                assert!(before.contains(name), "{} not in {:?}", name, before);
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&plan).unwrap();
+            let plan_out =
+                serde_json::from_str::<ConsolidationPlan>(&json).unwrap();
+            assert_eq!(plan, plan_out);
+        }
+
+        for node in &plan {
+            Array::consolidate_fragments(
+                &ctx,
+                array_uri.as_str(),
+                &node.fragment_names(),
+                None,
+            )?;
+        }
+        Array::vacuum(&ctx, &array_uri, None)?;
+
+        let after = fragment_names()?;
+        assert_eq!(1, after.len());
+        assert!(before.is_disjoint(&after));
+
+        test_uri.close().map_err(|e| Error::Other(e.to_string()))
+    }
+
+    #[test]
+    fn fragment_names() {
+        let node = ConsolidationPlanNode {
+            fragment_uris: vec![
+                "file:///a/__fragments/__1_1_abc_22".to_owned(),
+                "file:///a/__fragments/__2_2_def_22/".to_owned(),
+            ],
+        };
+        assert_eq!(vec!["__1_1_abc_22", "__2_2_def_22"], node.fragment_names());
+    }
+}
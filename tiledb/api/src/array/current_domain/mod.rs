@@ -0,0 +1,593 @@
+use std::ops::Deref;
+
+#[cfg(any(test, feature = "pod"))]
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+use anyhow::anyhow;
+use tiledb_common::array::CellValNum;
+use tiledb_common::range::{
+    Range, SingleValueRange, TypedRange, VarValueRange,
+};
+use tiledb_common::{single_value_range_go, var_value_range_go};
+
+use crate::array::Domain;
+use crate::context::{CApiInterface, Context, ContextBound};
+use crate::error::Error;
+use crate::key::LookupKey;
+use crate::{Datatype, Result as TileDBResult};
+
+pub(crate) enum RawNDRectangle {
+    Owned(*mut ffi::tiledb_ndrectangle_t),
+}
+
+impl Deref for RawNDRectangle {
+    type Target = *mut ffi::tiledb_ndrectangle_t;
+    fn deref(&self) -> &Self::Target {
+        let RawNDRectangle::Owned(ref ffi) = *self;
+        ffi
+    }
+}
+
+impl Drop for RawNDRectangle {
+    fn drop(&mut self) {
+        let RawNDRectangle::Owned(ref mut ffi) = *self;
+        unsafe {
+            ffi::tiledb_ndrectangle_free(ffi);
+        }
+    }
+}
+
+// SAFETY: only `NDRectangleBuilder` sets the ranges of a rectangle,
+// and it owns the handle.
+unsafe impl Send for RawNDRectangle {}
+unsafe impl Sync for RawNDRectangle {}
+
+/// A hyperrectangle spanning one range of each dimension of a domain.
+pub struct NDRectangle {
+    context: Context,
+    raw: RawNDRectangle,
+}
+
+impl ContextBound for NDRectangle {
+    fn context(&self) -> Context {
+        self.context.clone()
+    }
+}
+
+impl NDRectangle {
+    pub(crate) fn capi(&self) -> *mut ffi::tiledb_ndrectangle_t {
+        *self.raw
+    }
+
+    /// Returns the number of dimensions spanned by this rectangle.
+    pub fn num_dimensions(&self) -> TileDBResult<usize> {
+        let c_ndr = self.capi();
+        let mut c_ndim: u32 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_ndrectangle_get_dim_num(ctx, c_ndr, &mut c_ndim)
+        })?;
+        Ok(c_ndim as usize)
+    }
+
+    /// Returns the datatype of the range of a dimension.
+    pub fn datatype<K: Into<LookupKey>>(
+        &self,
+        key: K,
+    ) -> TileDBResult<Datatype> {
+        let c_ndr = self.capi();
+        let mut c_datatype: ffi::tiledb_datatype_t = out_ptr!();
+        match key.into() {
+            LookupKey::Index(idx) => {
+                let c_idx = dimension_index(idx)?;
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_ndrectangle_get_dtype(
+                        ctx,
+                        c_ndr,
+                        c_idx,
+                        &mut c_datatype,
+                    )
+                })?;
+            }
+            LookupKey::Name(name) => {
+                let c_name = cstring!(name);
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_ndrectangle_get_dtype_from_name(
+                        ctx,
+                        c_ndr,
+                        c_name.as_ptr(),
+                        &mut c_datatype,
+                    )
+                })?;
+            }
+        }
+        Ok(Datatype::try_from(c_datatype)?)
+    }
+
+    /// Returns the range of a dimension.
+    pub fn range<K: Into<LookupKey>>(&self, key: K) -> TileDBResult<Range> {
+        let key = key.into();
+        let datatype = self.datatype(key.clone())?;
+
+        let c_ndr = self.capi();
+        let mut c_range = ffi::tiledb_range_t {
+            min: std::ptr::null(),
+            min_size: 0,
+            max: std::ptr::null(),
+            max_size: 0,
+        };
+        match key {
+            LookupKey::Index(idx) => {
+                let c_idx = dimension_index(idx)?;
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_ndrectangle_get_range(
+                        ctx,
+                        c_ndr,
+                        c_idx,
+                        &mut c_range,
+                    )
+                })?;
+            }
+            LookupKey::Name(name) => {
+                let c_name = cstring!(name);
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_ndrectangle_get_range_from_name(
+                        ctx,
+                        c_ndr,
+                        c_name.as_ptr(),
+                        &mut c_range,
+                    )
+                })?;
+            }
+        }
+
+        // N.B. the range data is owned by the rectangle, so copy it out
+        let to_slice = |ptr: *const std::ffi::c_void, size: u64| {
+            if size == 0 {
+                &[] as &[u8]
+            } else {
+                unsafe {
+                    std::slice::from_raw_parts(ptr as *const u8, size as usize)
+                }
+            }
+        };
+        let min = to_slice(c_range.min, c_range.min_size);
+        let max = to_slice(c_range.max, c_range.max_size);
+
+        let typed = TypedRange::from_slices(
+            datatype,
+            dimension_cell_val_num(datatype),
+            min,
+            max,
+        )?;
+        Ok(typed.range)
+    }
+
+    /// Returns the ranges of each dimension, in order.
+    pub fn ranges(&self) -> TileDBResult<Vec<Range>> {
+        (0..self.num_dimensions()?)
+            .map(|d| self.range(d))
+            .collect::<TileDBResult<Vec<Range>>>()
+    }
+}
+
+#[cfg(any(test, feature = "pod"))]
+impl Debug for NDRectangle {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match tiledb_pod::array::NDRectangleData::try_from(self) {
+            Ok(ndr) => Debug::fmt(&ndr, f),
+            Err(e) => {
+                let RawNDRectangle::Owned(ptr) = self.raw;
+                write!(
+                    f,
+                    "<NDRectangle @ {:?}: serialization error: {}>",
+                    ptr, e
+                )
+            }
+        }
+    }
+}
+
+fn dimension_index(idx: usize) -> TileDBResult<u32> {
+    idx.try_into().map_err(|e: <usize as TryInto<u32>>::Error| {
+        Error::InvalidArgument(anyhow!(e))
+    })
+}
+
+/// Returns the cell val num of a dimension with type `datatype`.
+fn dimension_cell_val_num(datatype: Datatype) -> CellValNum {
+    if datatype.is_string_type() {
+        CellValNum::Var
+    } else {
+        CellValNum::single()
+    }
+}
+
+pub struct NDRectangleBuilder {
+    ndr: NDRectangle,
+}
+
+impl ContextBound for NDRectangleBuilder {
+    fn context(&self) -> Context {
+        self.ndr.context()
+    }
+}
+
+impl NDRectangleBuilder {
+    /// Begins building a rectangle over the dimensions of `domain`.
+    /// A range must be added for each dimension.
+    pub fn new(context: &Context, domain: &Domain) -> TileDBResult<Self> {
+        let c_domain = domain.capi();
+        let mut c_ndr: *mut ffi::tiledb_ndrectangle_t = out_ptr!();
+        context.capi_call(|ctx| unsafe {
+            ffi::tiledb_ndrectangle_alloc(ctx, c_domain, &mut c_ndr)
+        })?;
+
+        Ok(NDRectangleBuilder {
+            ndr: NDRectangle {
+                context: context.clone(),
+                raw: RawNDRectangle::Owned(c_ndr),
+            },
+        })
+    }
+
+    /// Sets the range of a dimension.
+    pub fn add_range<K, R>(self, key: K, range: R) -> TileDBResult<Self>
+    where
+        K: Into<LookupKey>,
+        R: Into<Range>,
+    {
+        let key = key.into();
+        let range = range.into();
+
+        let datatype = self.ndr.datatype(key.clone())?;
+        range
+            .check_dimension_compatibility(
+                datatype,
+                dimension_cell_val_num(datatype),
+            )
+            .map_err(|e| {
+                Error::InvalidArgument(
+                    anyhow!("Invalid range variant for dimension").context(e),
+                )
+            })?;
+
+        match range {
+            Range::Single(range) => {
+                single_value_range_go!(range, DT, start, end, {
+                    let size = std::mem::size_of::<DT>() as u64;
+                    self.set_range(
+                        key,
+                        ffi::tiledb_range_t {
+                            min: &start as *const DT as *const std::ffi::c_void,
+                            min_size: size,
+                            max: &end as *const DT as *const std::ffi::c_void,
+                            max_size: size,
+                        },
+                    )
+                })
+            }
+            Range::Multi(_) => unreachable!(
+                "This is rejected by range.check_dimension_compatibility"
+            ),
+            Range::Var(range) => {
+                var_value_range_go!(range, _DT, start, end, {
+                    self.set_range(
+                        key,
+                        ffi::tiledb_range_t {
+                            min: start.as_ptr() as *const std::ffi::c_void,
+                            min_size: std::mem::size_of_val(&*start) as u64,
+                            max: end.as_ptr() as *const std::ffi::c_void,
+                            max_size: std::mem::size_of_val(&*end) as u64,
+                        },
+                    )
+                })
+            }
+        }
+    }
+
+    fn set_range(
+        self,
+        key: LookupKey,
+        mut c_range: ffi::tiledb_range_t,
+    ) -> TileDBResult<Self> {
+        let c_ndr = self.ndr.capi();
+        match key {
+            LookupKey::Index(idx) => {
+                let c_idx = dimension_index(idx)?;
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_ndrectangle_set_range(
+                        ctx,
+                        c_ndr,
+                        c_idx,
+                        &mut c_range,
+                    )
+                })?;
+            }
+            LookupKey::Name(name) => {
+                let c_name = cstring!(name);
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_ndrectangle_set_range_for_name(
+                        ctx,
+                        c_ndr,
+                        c_name.as_ptr(),
+                        &mut c_range,
+                    )
+                })?;
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn build(self) -> NDRectangle {
+        self.ndr
+    }
+}
+
+/// The type of a [CurrentDomain].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CurrentDomainType {
+    NDRectangle,
+}
+
+impl TryFrom<ffi::tiledb_current_domain_type_t> for CurrentDomainType {
+    type Error = ffi::tiledb_current_domain_type_t;
+
+    fn try_from(
+        value: ffi::tiledb_current_domain_type_t,
+    ) -> Result<Self, Self::Error> {
+        match value {
+            ffi::tiledb_current_domain_type_t_TILEDB_NDRECTANGLE => {
+                Ok(Self::NDRectangle)
+            }
+            _ => Err(value),
+        }
+    }
+}
+
+pub(crate) enum RawCurrentDomain {
+    Owned(*mut ffi::tiledb_current_domain_t),
+}
+
+impl Deref for RawCurrentDomain {
+    type Target = *mut ffi::tiledb_current_domain_t;
+    fn deref(&self) -> &Self::Target {
+        let RawCurrentDomain::Owned(ref ffi) = *self;
+        ffi
+    }
+}
+
+impl Drop for RawCurrentDomain {
+    fn drop(&mut self) {
+        let RawCurrentDomain::Owned(ref mut ffi) = *self;
+        unsafe {
+            ffi::tiledb_current_domain_free(ffi);
+        }
+    }
+}
+
+// SAFETY: only `CurrentDomainBuilder` sets the rectangle of a current
+// domain, and it owns the handle.
+unsafe impl Send for RawCurrentDomain {}
+unsafe impl Sync for RawCurrentDomain {}
+
+/// The current domain of an array is the part of its domain
+/// which may be written to and read from.
+///
+/// An array with a very large domain can be created with a small current
+/// domain, which can then be expanded over time using
+/// [SchemaEvolutionBuilder::expand_current_domain].
+///
+/// [SchemaEvolutionBuilder::expand_current_domain]: crate::array::schema::SchemaEvolutionBuilder::expand_current_domain
+pub struct CurrentDomain {
+    context: Context,
+    raw: RawCurrentDomain,
+}
+
+impl ContextBound for CurrentDomain {
+    fn context(&self) -> Context {
+        self.context.clone()
+    }
+}
+
+impl CurrentDomain {
+    pub(crate) fn capi(&self) -> *mut ffi::tiledb_current_domain_t {
+        *self.raw
+    }
+
+    pub(crate) fn new(context: &Context, raw: RawCurrentDomain) -> Self {
+        CurrentDomain {
+            context: context.clone(),
+            raw,
+        }
+    }
+
+    /// Returns whether this current domain is empty.
+    /// The current domain of an array created without one is empty.
+    pub fn is_empty(&self) -> TileDBResult<bool> {
+        let c_current_domain = self.capi();
+        let mut c_is_empty: u32 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_current_domain_get_is_empty(
+                ctx,
+                c_current_domain,
+                &mut c_is_empty,
+            )
+        })?;
+        Ok(c_is_empty != 0)
+    }
+
+    pub fn current_domain_type(&self) -> TileDBResult<CurrentDomainType> {
+        let c_current_domain = self.capi();
+        let mut c_type: ffi::tiledb_current_domain_type_t = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_current_domain_get_type(
+                ctx,
+                c_current_domain,
+                &mut c_type,
+            )
+        })?;
+        CurrentDomainType::try_from(c_type).map_err(|c_type| {
+            Error::Internal(format!("Invalid current domain type: {}", c_type))
+        })
+    }
+
+    /// Returns the [NDRectangle] of this current domain.
+    pub fn ndrectangle(&self) -> TileDBResult<NDRectangle> {
+        let c_current_domain = self.capi();
+        let mut c_ndr: *mut ffi::tiledb_ndrectangle_t = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_current_domain_get_ndrectangle(
+                ctx,
+                c_current_domain,
+                &mut c_ndr,
+            )
+        })?;
+        Ok(NDRectangle {
+            context: self.context.clone(),
+            raw: RawNDRectangle::Owned(c_ndr),
+        })
+    }
+}
+
+#[cfg(any(test, feature = "pod"))]
+impl Debug for CurrentDomain {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let data = match self.is_empty() {
+            Ok(true) => Ok(None),
+            Ok(false) => {
+                tiledb_pod::array::CurrentDomainData::try_from(self).map(Some)
+            }
+            Err(e) => Err(e),
+        };
+        match data {
+            Ok(cd) => Debug::fmt(&cd, f),
+            Err(e) => {
+                let RawCurrentDomain::Owned(ptr) = self.raw;
+                write!(
+                    f,
+                    "<CurrentDomain @ {:?}: serialization error: {}>",
+                    ptr, e
+                )
+            }
+        }
+    }
+}
+
+pub struct CurrentDomainBuilder {
+    current_domain: CurrentDomain,
+}
+
+impl ContextBound for CurrentDomainBuilder {
+    fn context(&self) -> Context {
+        self.current_domain.context()
+    }
+}
+
+impl CurrentDomainBuilder {
+    pub fn new(context: &Context) -> TileDBResult<Self> {
+        let mut c_current_domain: *mut ffi::tiledb_current_domain_t =
+            out_ptr!();
+        context.capi_call(|ctx| unsafe {
+            ffi::tiledb_current_domain_create(ctx, &mut c_current_domain)
+        })?;
+
+        Ok(CurrentDomainBuilder {
+            current_domain: CurrentDomain::new(
+                context,
+                RawCurrentDomain::Owned(c_current_domain),
+            ),
+        })
+    }
+
+    /// Sets the current domain to span `ndrectangle`.
+    pub fn ndrectangle(self, ndrectangle: NDRectangle) -> TileDBResult<Self> {
+        let c_current_domain = self.current_domain.capi();
+        let c_ndr = ndrectangle.capi();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_current_domain_set_ndrectangle(
+                ctx,
+                c_current_domain,
+                c_ndr,
+            )
+        })?;
+        Ok(self)
+    }
+
+    pub fn build(self) -> CurrentDomain {
+        self.current_domain
+    }
+}
+
+#[cfg(any(test, feature = "pod"))]
+pub mod pod;
+
+#[cfg(test)]
+mod tests {
+    use tiledb_common::array::ArrayType;
+    use tiledb_pod::array::{CurrentDomainData, NDRectangleData};
+
+    use super::*;
+    use crate::array::Array;
+    use crate::tests::examples::{quickstart, TestArray};
+    use crate::Factory;
+
+    #[test]
+    fn current_domain_schema() -> TileDBResult<()> {
+        let ctx = Context::new()?;
+        let schema_data = quickstart::Builder::new(ArrayType::Sparse).build();
+
+        let domain = schema_data.domain.create(&ctx)?;
+        let ndr = NDRectangleBuilder::new(&ctx, &domain)?
+            .add_range("rows", &[1i32, 2])?
+            .add_range(1, &[2i32, 3])?
+            .build();
+        assert_eq!(2, ndr.num_dimensions()?);
+        assert_eq!(Datatype::Int32, ndr.datatype("cols")?);
+        assert_eq!(Range::from(&[1i32, 2]), ndr.range(0)?);
+        assert_eq!(Range::from(&[2i32, 3]), ndr.range("cols")?);
+
+        // the ranges must match the dimension types
+        assert!(matches!(
+            NDRectangleBuilder::new(&ctx, &domain)?
+                .add_range("rows", &[1u64, 2]),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        let current_domain =
+            CurrentDomainBuilder::new(&ctx)?.ndrectangle(ndr)?.build();
+        assert!(!current_domain.is_empty()?);
+        assert_eq!(
+            CurrentDomainType::NDRectangle,
+            current_domain.current_domain_type()?
+        );
+
+        // without a current domain
+        {
+            let schema = schema_data.create(&ctx)?;
+            assert!(schema.current_domain()?.is_empty()?);
+        }
+
+        let array = TestArray::new("current_domain", {
+            let mut s = schema_data.clone();
+            s.current_domain = Some(CurrentDomainData::from(NDRectangleData {
+                ranges: vec![Range::from(&[1i32, 2]), Range::from(&[2i32, 3])],
+            }));
+            s.into()
+        })?;
+
+        let schema = Array::open(&ctx, &array.uri, crate::array::Mode::Read)?
+            .schema()?;
+        let current_domain = schema.current_domain()?;
+        assert!(!current_domain.is_empty()?);
+        assert_eq!(
+            vec![Range::from(&[1i32, 2]), Range::from(&[2i32, 3])],
+            current_domain.ndrectangle()?.ranges()?
+        );
+        assert_eq!(
+            array.schema.current_domain,
+            tiledb_pod::array::SchemaData::try_from(&schema)?.current_domain
+        );
+
+        Ok(())
+    }
+}
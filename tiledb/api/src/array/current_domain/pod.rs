@@ -0,0 +1,93 @@
+use anyhow::anyhow;
+use tiledb_pod::array::current_domain::{CurrentDomainData, NDRectangleData};
+
+use super::{
+    CurrentDomain, CurrentDomainBuilder, CurrentDomainType, NDRectangle,
+    NDRectangleBuilder,
+};
+use crate::array::Domain;
+use crate::error::Error as TileDBError;
+use crate::{Context, Result as TileDBResult};
+
+impl TryFrom<&NDRectangle> for NDRectangleData {
+    type Error = TileDBError;
+
+    fn try_from(ndr: &NDRectangle) -> Result<Self, Self::Error> {
+        Ok(NDRectangleData {
+            ranges: ndr.ranges()?,
+        })
+    }
+}
+
+impl TryFrom<NDRectangle> for NDRectangleData {
+    type Error = TileDBError;
+
+    fn try_from(ndr: NDRectangle) -> Result<Self, Self::Error> {
+        Self::try_from(&ndr)
+    }
+}
+
+impl TryFrom<&CurrentDomain> for CurrentDomainData {
+    type Error = TileDBError;
+
+    fn try_from(current_domain: &CurrentDomain) -> Result<Self, Self::Error> {
+        if current_domain.is_empty()? {
+            return Err(TileDBError::InvalidArgument(anyhow!(
+                "Current domain is empty"
+            )));
+        }
+        match current_domain.current_domain_type()? {
+            CurrentDomainType::NDRectangle => {
+                Ok(CurrentDomainData::NDRectangle(NDRectangleData::try_from(
+                    &current_domain.ndrectangle()?,
+                )?))
+            }
+        }
+    }
+}
+
+impl TryFrom<CurrentDomain> for CurrentDomainData {
+    type Error = TileDBError;
+
+    fn try_from(current_domain: CurrentDomain) -> Result<Self, Self::Error> {
+        Self::try_from(&current_domain)
+    }
+}
+
+impl NDRectangle {
+    /// Creates the rectangle described by `data` over the dimensions
+    /// of `domain`.
+    pub fn from_data(
+        context: &Context,
+        domain: &Domain,
+        data: &NDRectangleData,
+    ) -> TileDBResult<Self> {
+        Ok(data
+            .ranges
+            .iter()
+            .enumerate()
+            .try_fold(
+                NDRectangleBuilder::new(context, domain)?,
+                |b, (d, r)| b.add_range(d, r.clone()),
+            )?
+            .build())
+    }
+}
+
+impl CurrentDomain {
+    /// Creates the current domain described by `data` for an array
+    /// whose domain is `domain`.
+    pub fn from_data(
+        context: &Context,
+        domain: &Domain,
+        data: &CurrentDomainData,
+    ) -> TileDBResult<Self> {
+        let b = CurrentDomainBuilder::new(context)?;
+        let b = match data {
+            CurrentDomainData::NDRectangle(ndr) => {
+                b.ndrectangle(NDRectangle::from_data(context, domain, ndr)?)?
+            }
+        };
+        Ok(b.build())
+    }
+}
@@ -32,6 +32,10 @@ impl Drop for RawDimension {
     }
 }
 
+// SAFETY: only `Builder` modifies a dimension, and it owns the handle.
+unsafe impl Send for RawDimension {}
+unsafe impl Sync for RawDimension {}
+
 pub struct Dimension {
     pub(crate) context: Context,
     pub(crate) raw: RawDimension,
@@ -31,6 +31,10 @@ impl Drop for RawDomain {
     }
 }
 
+// SAFETY: a domain is read-only once built.
+unsafe impl Send for RawDomain {}
+unsafe impl Sync for RawDomain {}
+
 pub struct Domain {
     context: Context,
     raw: RawDomain,
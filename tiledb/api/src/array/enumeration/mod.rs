@@ -30,6 +30,11 @@ impl Drop for RawEnumeration {
     }
 }
 
+// SAFETY: an enumeration is read-only once built. Extending an enumeration
+// allocates a new handle rather than modifying this one.
+unsafe impl Send for RawEnumeration {}
+unsafe impl Sync for RawEnumeration {}
+
 pub struct Enumeration {
     context: Context,
     raw: RawEnumeration,
@@ -38,6 +38,11 @@ impl Drop for RawFragmentInfo {
     }
 }
 
+// SAFETY: fragment info is loaded once when it is built and is only read
+// afterwards, which libtiledb permits from multiple threads.
+unsafe impl Send for RawFragmentInfo {}
+unsafe impl Sync for RawFragmentInfo {}
+
 struct FragmentInfoInternal {
     context: Context,
     raw: RawFragmentInfo,
@@ -628,6 +633,22 @@ impl FragmentInfoList {
         })
     }
 
+    /// Returns the fragment named `name`, if it is in the list.
+    pub fn find_fragment(
+        &self,
+        name: &str,
+    ) -> TileDBResult<Option<FragmentInfo>> {
+        for index in 0..self.num_fragments()? {
+            if self.info.fragment_name(index)? == name {
+                return Ok(Some(FragmentInfo {
+                    info: &self.info,
+                    index,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
     pub fn iter(&self) -> TileDBResult<FragmentInfoListIterator> {
         FragmentInfoListIterator::try_from(self)
     }
@@ -1,7 +1,7 @@
 use std::convert::TryFrom;
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use std::num::NonZeroU32;
-use std::ops::Deref;
+use std::ops::{Bound, Deref, RangeBounds};
 use std::str::FromStr;
 
 use anyhow::anyhow;
@@ -15,7 +15,7 @@ use crate::datatype::PhysicalType;
 use crate::error::{DatatypeError, Error};
 use crate::key::LookupKey;
 use crate::metadata;
-use crate::metadata::Metadata;
+use crate::metadata::{Metadata, MetadataIter};
 use crate::range::{
     Range, SingleValueRange, TypedNonEmptyDomain, TypedRange, VarValueRange,
 };
@@ -23,6 +23,8 @@ use crate::Result as TileDBResult;
 use crate::{physical_type_go, Datatype};
 
 pub mod attribute;
+pub mod consolidation;
+pub mod current_domain;
 pub mod dimension;
 pub mod domain;
 pub mod enumeration;
@@ -32,6 +34,11 @@ pub mod schema;
 use crate::config::Config;
 
 pub use attribute::{Attribute, Builder as AttributeBuilder};
+pub use consolidation::{ConsolidationPlan, ConsolidationPlanNode};
+pub use current_domain::{
+    CurrentDomain, CurrentDomainBuilder, CurrentDomainType, NDRectangle,
+    NDRectangleBuilder,
+};
 pub use dimension::{
     Builder as DimensionBuilder, Dimension, DimensionConstraints,
 };
@@ -44,9 +51,10 @@ pub use fragment_info::{
     Builder as FragmentInfoBuilder, FragmentInfo, FragmentInfoList,
 };
 pub use schema::{
-    ArrayType, Builder as SchemaBuilder, CellValNum, Field, Schema,
+    ArrayType, Builder as SchemaBuilder, CellValNum, DimensionLabel, Field,
+    Schema,
 };
-pub use tiledb_common::array::{CellOrder, Mode, TileOrder};
+pub use tiledb_common::array::{CellOrder, DataOrder, Mode, TileOrder};
 
 /// Method of encryption.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -152,6 +160,11 @@ impl Drop for RawArray {
     }
 }
 
+// SAFETY: libtiledb arrays are thread-safe once opened. Multiple queries may
+// be created and run against the same array from different threads.
+unsafe impl Send for RawArray {}
+unsafe impl Sync for RawArray {}
+
 pub struct Array {
     context: Context,
     uri: String,
@@ -245,6 +258,35 @@ impl Array {
         self.uri.as_ref()
     }
 
+    /// Returns the start of the timestamp range which this array was opened at.
+    pub fn open_timestamp_start(&self) -> TileDBResult<u64> {
+        let c_array = *self.raw;
+        let mut c_timestamp: u64 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_get_open_timestamp_start(
+                ctx,
+                c_array,
+                &mut c_timestamp,
+            )
+        })?;
+        Ok(c_timestamp)
+    }
+
+    /// Returns the end of the timestamp range which this array was opened at.
+    /// If no end was requested, this is the time at which the array was opened.
+    pub fn open_timestamp_end(&self) -> TileDBResult<u64> {
+        let c_array = *self.raw;
+        let mut c_timestamp: u64 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_get_open_timestamp_end(
+                ctx,
+                c_array,
+                &mut c_timestamp,
+            )
+        })?;
+        Ok(c_timestamp)
+    }
+
     pub fn schema(&self) -> TileDBResult<Schema> {
         let c_array = *self.raw;
         let mut c_schema: *mut ffi::tiledb_array_schema_t = out_ptr!();
@@ -282,6 +324,17 @@ impl Array {
         Ok(())
     }
 
+    /// Writes each of the items of `metadata`.
+    pub fn put_metadata_all<I>(&mut self, metadata: I) -> TileDBResult<()>
+    where
+        I: IntoIterator<Item = Metadata>,
+    {
+        for m in metadata {
+            self.put_metadata(m)?;
+        }
+        Ok(())
+    }
+
     pub fn delete_metadata<S>(&mut self, name: S) -> TileDBResult<()>
     where
         S: AsRef<str>,
@@ -347,11 +400,12 @@ impl Array {
             }
         }?;
         let datatype = Datatype::try_from(c_datatype)?;
-        Ok(metadata::metadata_from_ffi(
-            name,
-            datatype,
-            (vec_size, vec_ptr),
-        ))
+        metadata::metadata_from_ffi(name, datatype, (vec_size, vec_ptr))
+    }
+
+    /// Returns an iterator over all of the metadata of this array.
+    pub fn metadata_iter(&self) -> TileDBResult<MetadataIter<'_>> {
+        MetadataIter::for_array(self)
     }
 
     pub fn has_metadata_key<S>(&self, name: S) -> TileDBResult<Option<Datatype>>
@@ -501,6 +555,15 @@ impl Array {
         Ok(())
     }
 
+    /// Computes a plan for consolidating the fragments of this array
+    /// into fragments of at most `fragment_size` bytes.
+    pub fn consolidation_plan(
+        &self,
+        fragment_size: u64,
+    ) -> TileDBResult<ConsolidationPlan> {
+        ConsolidationPlan::new(self, fragment_size)
+    }
+
     /// Removes the array located at [array_uri].
     ///
     /// All of the array contents are deleted, including the values
@@ -517,6 +580,90 @@ impl Array {
         Ok(())
     }
 
+    /// Deletes the fragments of the array located at `array_uri` whose
+    /// timestamp ranges are contained within `timestamps`.
+    ///
+    /// ```no_run
+    /// # use tiledb_api::{Array, Context, Result as TileDBResult};
+    /// # fn delete_window(ctx: &Context, uri: &str) -> TileDBResult<()> {
+    /// // deletes all fragments written at timestamps 100 through 199
+    /// Array::delete_fragments(ctx, uri, 100..200)
+    /// # }
+    /// ```
+    pub fn delete_fragments<S, R>(
+        context: &Context,
+        array_uri: S,
+        timestamps: R,
+    ) -> TileDBResult<()>
+    where
+        S: AsRef<str>,
+        R: RangeBounds<u64>,
+    {
+        let start = match timestamps.start_bound() {
+            Bound::Included(t) => Some(*t),
+            Bound::Excluded(t) => t.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let end = match timestamps.end_bound() {
+            Bound::Included(t) => Some(*t),
+            Bound::Excluded(t) => t.checked_sub(1),
+            Bound::Unbounded => Some(u64::MAX),
+        };
+        let (c_start, c_end) = match (start, end) {
+            (Some(start), Some(end)) if start <= end => (start, end),
+            _ => {
+                return Err(Error::InvalidArgument(anyhow!(
+                    "Empty timestamp range for fragment deletion"
+                )))
+            }
+        };
+
+        let c_array_uri = cstring!(array_uri.as_ref());
+        context.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_delete_fragments_v2(
+                ctx,
+                c_array_uri.as_ptr(),
+                c_start,
+                c_end,
+            )
+        })?;
+        Ok(())
+    }
+
+    /// Deletes the fragments with the given URIs from the array located at
+    /// `array_uri`.
+    pub fn delete_fragments_list<S, F>(
+        context: &Context,
+        array_uri: S,
+        fragment_uris: &[F],
+    ) -> TileDBResult<()>
+    where
+        S: AsRef<str>,
+        F: AsRef<str>,
+    {
+        let c_array_uri = cstring!(array_uri.as_ref());
+
+        // This array has to outlive the API call below.
+        let fragment_uris_cstr = fragment_uris
+            .iter()
+            .map(|fragment_uri| Ok(cstring!(fragment_uri.as_ref())))
+            .collect::<TileDBResult<Vec<_>>>()?;
+        let mut fragment_uris_ptr = fragment_uris_cstr
+            .iter()
+            .map(|fragment_uri| fragment_uri.as_ptr())
+            .collect::<Vec<_>>();
+
+        context.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_delete_fragments_list(
+                ctx,
+                c_array_uri.as_ptr(),
+                fragment_uris_ptr.as_mut_ptr(),
+                fragment_uris_ptr.len(),
+            )
+        })?;
+        Ok(())
+    }
+
     /// Evolves the schema of an array.
     pub fn evolve<S>(
         context: &Context,
@@ -953,7 +1100,8 @@ pub mod tests {
     use super::*;
     use crate::config::CommonOption;
     use crate::query::{
-        Query, QueryBuilder, QueryLayout, QueryType, WriteBuilder,
+        Query, QueryBuilder, QueryLayout, QueryType, ReadBuilder, ReadQuery,
+        ReadQueryBuilder, WriteBuilder,
     };
     use crate::Factory;
 
@@ -1132,6 +1280,133 @@ pub mod tests {
         test_uri.close().map_err(|e| Error::Other(e.to_string()))
     }
 
+    #[test]
+    fn test_array_metadata_string_blob_bool() -> TileDBResult<()> {
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        let tdb = Context::new()?;
+        let uri = create_quickstart_dense(&test_uri, &tdb)?;
+
+        {
+            let mut array = Array::open(&tdb, &uri, QueryType::Write)?;
+            array.put_metadata(Metadata::new(
+                "provenance".to_owned(),
+                Datatype::StringUtf8,
+                "written by ünit test",
+            )?)?;
+            array.put_metadata(Metadata::new(
+                "ascii".to_owned(),
+                Datatype::StringAscii,
+                "abc".to_owned(),
+            )?)?;
+            array.put_metadata(Metadata::new(
+                "blob".to_owned(),
+                Datatype::Blob,
+                vec![0u8, 255, 7],
+            )?)?;
+            array.put_metadata(Metadata::new(
+                "flags".to_owned(),
+                Datatype::Boolean,
+                vec![true, false, true],
+            )?)?;
+        }
+
+        {
+            let array = Array::open(&tdb, &uri, QueryType::Read)?;
+
+            let provenance = array.metadata("provenance")?;
+            assert_eq!(provenance.datatype, Datatype::StringUtf8);
+            assert_eq!(
+                provenance.value,
+                Value::StringValue("written by ünit test".to_owned())
+            );
+
+            let ascii = array.metadata("ascii")?;
+            assert_eq!(ascii.datatype, Datatype::StringAscii);
+            assert_eq!(ascii.value, Value::StringValue("abc".to_owned()));
+
+            let blob = array.metadata("blob")?;
+            assert_eq!(blob.datatype, Datatype::Blob);
+            assert_eq!(blob.value, Value::BlobValue(vec![0, 255, 7]));
+
+            let flags = array.metadata("flags")?;
+            assert_eq!(flags.datatype, Datatype::Boolean);
+            assert_eq!(flags.value, Value::BoolValue(vec![true, false, true]));
+        }
+
+        test_uri.close().map_err(|e| Error::Other(e.to_string()))
+    }
+
+    #[test]
+    fn test_array_metadata_iter() -> TileDBResult<()> {
+        let tdb = Context::new()?;
+
+        let src_test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let src_uri = create_quickstart_dense(&src_test_uri, &tdb)?;
+
+        let dst_test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let dst_uri = create_quickstart_dense(&dst_test_uri, &tdb)?;
+
+        let metadata = vec![
+            Metadata::new("b".to_owned(), Datatype::Int32, vec![5])?,
+            Metadata::new("a".to_owned(), Datatype::StringUtf8, "value")?,
+            Metadata::new("c".to_owned(), Datatype::Float64, vec![1.5, 2.5])?,
+        ];
+        {
+            let mut array = Array::open(&tdb, &src_uri, QueryType::Write)?;
+            array.put_metadata_all(metadata.clone())?;
+        }
+
+        let src_metadata = {
+            let array = Array::open(&tdb, &src_uri, QueryType::Read)?;
+            let iter = array.metadata_iter()?;
+            assert_eq!(3, iter.len());
+
+            let src_metadata = iter.collect::<TileDBResult<Vec<_>>>()?;
+            let keys = src_metadata
+                .iter()
+                .map(|m| m.key.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(vec!["a", "b", "c"], keys);
+            for m in metadata.iter() {
+                assert!(src_metadata.contains(m));
+            }
+            src_metadata
+        };
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&src_metadata).unwrap();
+            let src_metadata_out =
+                serde_json::from_str::<Vec<Metadata>>(&json).unwrap();
+            assert_eq!(src_metadata, src_metadata_out);
+        }
+
+        {
+            let mut array = Array::open(&tdb, &dst_uri, QueryType::Write)?;
+            array.put_metadata_all(src_metadata)?;
+        }
+
+        {
+            let src = Array::open(&tdb, &src_uri, QueryType::Read)?;
+            let dst = Array::open(&tdb, &dst_uri, QueryType::Read)?;
+
+            let src_map = src.metadata_iter()?.into_map()?;
+            assert_eq!(Some(&Value::Int32Value(vec![5])), src_map.get("b"));
+            assert_eq!(src_map, dst.metadata_iter()?.into_map()?);
+        }
+
+        src_test_uri
+            .close()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        dst_test_uri
+            .close()
+            .map_err(|e| Error::Other(e.to_string()))
+    }
+
     #[test]
     fn test_mode_metadata() -> TileDBResult<()> {
         let test_uri = uri::get_uri_generator()
@@ -1320,6 +1595,184 @@ pub mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_delete_fragments() -> TileDBResult<()> {
+        let ctx: Context = Context::new().unwrap();
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let array_uri = create_simple_dense(&test_uri, &ctx)?;
+
+        // fragments are written at timestamps 1, 2, 3 and 4
+        write_dense_vector_4_fragments(&ctx, &array_uri, 0)?;
+
+        let timestamps = || -> TileDBResult<Vec<u64>> {
+            FragmentInfoBuilder::new(&ctx, &array_uri)?
+                .build()?
+                .iter()?
+                .map(|f| Ok(f.timestamp_range()?[0]))
+                .collect::<TileDBResult<Vec<_>>>()
+        };
+        assert_eq!(vec![1, 2, 3, 4], timestamps()?);
+
+        // empty ranges are rejected
+        #[allow(clippy::reversed_empty_ranges)]
+        {
+            assert!(matches!(
+                Array::delete_fragments(&ctx, &array_uri, 3..3),
+                Err(Error::InvalidArgument(_))
+            ));
+            assert!(matches!(
+                Array::delete_fragments(&ctx, &array_uri, 3..=2),
+                Err(Error::InvalidArgument(_))
+            ));
+        }
+        assert_eq!(vec![1, 2, 3, 4], timestamps()?);
+
+        // only fragments within the range are deleted
+        Array::delete_fragments(&ctx, &array_uri, 2..4)?;
+        assert_eq!(vec![1, 4], timestamps()?);
+
+        Array::delete_fragments(&ctx, &array_uri, 4..)?;
+        assert_eq!(vec![1], timestamps()?);
+
+        // write some more and delete by URI
+        write_dense_vector_4_fragments(&ctx, &array_uri, 10)?;
+        assert_eq!(vec![1, 11, 12, 13, 14], timestamps()?);
+
+        let fragment_uris = FragmentInfoBuilder::new(&ctx, &array_uri)?
+            .build()?
+            .iter()?
+            .filter_map(|f| match f.timestamp_range() {
+                Ok([t, _]) if t % 2 == 1 => Some(f.uri()),
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            })
+            .collect::<TileDBResult<Vec<_>>>()?;
+        assert_eq!(3, fragment_uris.len());
+
+        Array::delete_fragments_list(&ctx, &array_uri, &fragment_uris)?;
+        assert_eq!(vec![12, 14], timestamps()?);
+
+        test_uri.close().map_err(|e| Error::Other(e.to_string()))
+    }
+
+    #[test]
+    /// Ensure that arrays and their queries can be used from other threads,
+    /// fails to compile if not
+    fn is_send_sync() {
+        fn is_send<T: Send>() {}
+        fn is_send_sync<T: Send + Sync>() {}
+
+        is_send_sync::<Array>();
+        is_send_sync::<Schema>();
+        is_send_sync::<FragmentInfoList>();
+        is_send_sync::<CurrentDomain>();
+        is_send_sync::<NDRectangle>();
+        is_send_sync::<schema::DimensionLabel>();
+        is_send_sync::<ConsolidationPlan>();
+        is_send_sync::<consolidation::RawConsolidationPlan>();
+        is_send::<ArrayOpener>();
+        is_send::<crate::query::QueryBase>();
+        is_send::<crate::query::WriteQuery<'static>>();
+    }
+
+    #[test]
+    fn array_threads() -> TileDBResult<()> {
+        const NUM_THREADS: i32 = 8;
+        const CELLS_PER_THREAD: i32 = 50;
+
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        let ctx = Context::new()?;
+        let uri = create_simple_dense(&test_uri, &ctx)?;
+
+        let thread_range = |t: i32| -> [i32; 2] {
+            let lb = t * CELLS_PER_THREAD + 1;
+            [lb, lb + CELLS_PER_THREAD - 1]
+        };
+
+        // each thread writes a disjoint range of the array
+        std::thread::scope(|s| {
+            (0..NUM_THREADS)
+                .map(|t| {
+                    let (ctx, uri) = (&ctx, &uri);
+                    s.spawn(move || -> TileDBResult<()> {
+                        let range = thread_range(t);
+                        let data = (range[0]..=range[1])
+                            .map(|c| -c)
+                            .collect::<Vec<_>>();
+
+                        let q = WriteBuilder::new(Array::open(
+                            ctx,
+                            uri,
+                            Mode::Write,
+                        )?)?
+                        .layout(QueryLayout::RowMajor)?
+                        .start_subarray()?
+                        .add_range(0, &range)?
+                        .finish_subarray()?
+                        .data_typed("a", &data)?
+                        .build();
+                        q.submit().and_then(|_| q.finalize())?;
+                        Ok(())
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .try_for_each(|h| h.join().unwrap())
+        })?;
+
+        let read_range = |array: Array, range: [i32; 2]| -> TileDBResult<()> {
+            let mut q = ReadBuilder::new(array)?
+                .layout(QueryLayout::RowMajor)?
+                .register_constructor::<_, Vec<i32>>("a", Default::default())?
+                .start_subarray()?
+                .add_range(0, &range)?
+                .finish_subarray()?
+                .build();
+            let (a, _) = q.execute()?;
+            assert_eq!(
+                (range[0]..=range[1]).map(|c| -c).collect::<Vec<_>>(),
+                a
+            );
+            Ok(())
+        };
+
+        // arrays opened on one thread can be shared with, or moved to, others
+        let shared = Array::open(&ctx, &uri, Mode::Read)?;
+        let moved = (0..NUM_THREADS)
+            .map(|_| Array::open(&ctx, &uri, Mode::Read))
+            .collect::<TileDBResult<Vec<_>>>()?;
+
+        std::thread::scope(|s| {
+            moved
+                .into_iter()
+                .enumerate()
+                .map(|(t, array)| {
+                    let shared = &shared;
+                    s.spawn(move || -> TileDBResult<()> {
+                        let domain = shared.dimension_nonempty_domain(0)?;
+                        assert_eq!(
+                            Some(Range::from(&[
+                                1,
+                                NUM_THREADS * CELLS_PER_THREAD
+                            ])),
+                            domain.map(|d| d.range)
+                        );
+                        assert_eq!(0, shared.num_metadata()?);
+
+                        read_range(array, thread_range(t as i32))
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .try_for_each(|h| h.join().unwrap())
+        })?;
+
+        test_uri.close().map_err(|e| Error::Other(e.to_string()))
+    }
+
     #[test]
     fn delete() -> TileDBResult<()> {
         let test_uri = uri::get_uri_generator()
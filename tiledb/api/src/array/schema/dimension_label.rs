@@ -0,0 +1,319 @@
+use std::ops::Deref;
+
+#[cfg(any(test, feature = "pod"))]
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+use crate::array::CellValNum;
+use crate::context::{CApiInterface, Context, ContextBound};
+use crate::{Datatype, Result as TileDBResult};
+
+pub use tiledb_common::array::DataOrder;
+
+pub(crate) enum RawDimensionLabel {
+    Owned(*mut ffi::tiledb_dimension_label_t),
+}
+
+impl Deref for RawDimensionLabel {
+    type Target = *mut ffi::tiledb_dimension_label_t;
+    fn deref(&self) -> &Self::Target {
+        let RawDimensionLabel::Owned(ref ffi) = *self;
+        ffi
+    }
+}
+
+impl Drop for RawDimensionLabel {
+    fn drop(&mut self) {
+        let RawDimensionLabel::Owned(ref mut ffi) = *self;
+        unsafe { ffi::tiledb_dimension_label_free(ffi) }
+    }
+}
+
+// SAFETY: a dimension label is read-only. It is obtained from a schema
+// and has no functions which modify it.
+unsafe impl Send for RawDimensionLabel {}
+unsafe impl Sync for RawDimensionLabel {}
+
+/// A dimension label maps the values of a dimension onto values of
+/// another datatype, such as a float "wavelength" label for an integer
+/// index dimension.
+///
+/// Label data is stored in its own array alongside the array which it labels.
+/// It is read and written using the name of the label in place of a field name,
+/// and subarrays may select cells by label value using
+/// [add_label_range](crate::query::subarray::SubarrayBuilder::add_label_range).
+pub struct DimensionLabel {
+    context: Context,
+    raw: RawDimensionLabel,
+}
+
+impl ContextBound for DimensionLabel {
+    fn context(&self) -> Context {
+        self.context.clone()
+    }
+}
+
+impl DimensionLabel {
+    pub(crate) fn capi(&self) -> *mut ffi::tiledb_dimension_label_t {
+        *self.raw
+    }
+
+    pub(crate) fn new(context: &Context, raw: RawDimensionLabel) -> Self {
+        DimensionLabel {
+            context: context.clone(),
+            raw,
+        }
+    }
+
+    pub fn name(&self) -> TileDBResult<String> {
+        let c_label = self.capi();
+        let mut c_name = std::ptr::null::<std::ffi::c_char>();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_dimension_label_get_name(ctx, c_label, &mut c_name)
+        })?;
+        let c_name = unsafe { std::ffi::CStr::from_ptr(c_name) };
+        Ok(String::from(c_name.to_string_lossy()))
+    }
+
+    /// Returns the index of the dimension which this label applies to.
+    pub fn dimension_index(&self) -> TileDBResult<usize> {
+        let c_label = self.capi();
+        let mut c_index: u32 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_dimension_label_get_dimension_index(
+                ctx,
+                c_label,
+                &mut c_index,
+            )
+        })?;
+        Ok(c_index as usize)
+    }
+
+    /// Returns the name of the attribute of the label array
+    /// which holds the label values.
+    pub fn label_attribute_name(&self) -> TileDBResult<String> {
+        let c_label = self.capi();
+        let mut c_name = std::ptr::null::<std::ffi::c_char>();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_dimension_label_get_label_attr_name(
+                ctx,
+                c_label,
+                &mut c_name,
+            )
+        })?;
+        let c_name = unsafe { std::ffi::CStr::from_ptr(c_name) };
+        Ok(String::from(c_name.to_string_lossy()))
+    }
+
+    pub fn label_order(&self) -> TileDBResult<DataOrder> {
+        let c_label = self.capi();
+        let mut c_order: ffi::tiledb_data_order_t = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_dimension_label_get_label_order(
+                ctx,
+                c_label,
+                &mut c_order,
+            )
+        })?;
+        Ok(DataOrder::try_from(c_order)?)
+    }
+
+    pub fn label_type(&self) -> TileDBResult<Datatype> {
+        let c_label = self.capi();
+        let mut c_datatype: ffi::tiledb_datatype_t = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_dimension_label_get_label_type(
+                ctx,
+                c_label,
+                &mut c_datatype,
+            )
+        })?;
+        Ok(Datatype::try_from(c_datatype)?)
+    }
+
+    pub fn label_cell_val_num(&self) -> TileDBResult<CellValNum> {
+        let c_label = self.capi();
+        let mut c_num: u32 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_dimension_label_get_label_cell_val_num(
+                ctx, c_label, &mut c_num,
+            )
+        })?;
+        Ok(CellValNum::try_from(c_num)?)
+    }
+
+    /// Returns the URI of the array which stores the label data.
+    pub fn uri(&self) -> TileDBResult<String> {
+        let c_label = self.capi();
+        let mut c_uri = std::ptr::null::<std::ffi::c_char>();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_dimension_label_get_uri(ctx, c_label, &mut c_uri)
+        })?;
+        let c_uri = unsafe { std::ffi::CStr::from_ptr(c_uri) };
+        Ok(String::from(c_uri.to_string_lossy()))
+    }
+}
+
+impl PartialEq<DimensionLabel> for DimensionLabel {
+    fn eq(&self, other: &DimensionLabel) -> bool {
+        eq_helper!(self.name(), other.name());
+        eq_helper!(self.dimension_index(), other.dimension_index());
+        eq_helper!(self.label_order(), other.label_order());
+        eq_helper!(self.label_type(), other.label_type());
+        eq_helper!(self.label_cell_val_num(), other.label_cell_val_num());
+
+        true
+    }
+}
+
+#[cfg(any(test, feature = "pod"))]
+impl Debug for DimensionLabel {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match tiledb_pod::array::dimension_label::DimensionLabelData::try_from(
+            self,
+        ) {
+            Ok(d) => Debug::fmt(&d, f),
+            Err(e) => {
+                let RawDimensionLabel::Owned(ptr) = self.raw;
+                write!(
+                    f,
+                    "<DimensionLabel @ {:?}: serialization error: {}>",
+                    ptr, e
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tiledb_pod::array::dimension_label::DimensionLabelData;
+    use tiledb_pod::array::schema::SchemaData;
+    use uri::{self, TestArrayUri};
+
+    use super::*;
+    use crate::array::{
+        Array, ArrayType, AttributeBuilder, DimensionBuilder, DomainBuilder,
+        Mode, Schema, SchemaBuilder,
+    };
+    use crate::error::Error;
+    use crate::query::{
+        Query, QueryBuilder, QueryLayout, ReadBuilder, ReadQuery,
+        ReadQueryBuilder, WriteBuilder,
+    };
+    use crate::Factory;
+
+    /// Returns a dense schema with an `Int32` "index" dimension
+    /// which has a `Float64` "wavelength" label.
+    fn spectrum_schema(ctx: &Context) -> TileDBResult<Schema> {
+        let domain = {
+            let index = DimensionBuilder::new(
+                ctx,
+                "index",
+                Datatype::Int32,
+                ([0, 9], 10),
+            )?
+            .build();
+            DomainBuilder::new(ctx)?.add_dimension(index)?.build()
+        };
+        SchemaBuilder::new(ctx, ArrayType::Dense, domain)?
+            .add_attribute(
+                AttributeBuilder::new(ctx, "intensity", Datatype::Float32)?
+                    .build(),
+            )?
+            .add_dimension_label(
+                0,
+                "wavelength",
+                DataOrder::Increasing,
+                Datatype::Float64,
+            )?
+            .build()
+    }
+
+    #[test]
+    fn dimension_label_schema() -> TileDBResult<()> {
+        let ctx = Context::new()?;
+        let schema = spectrum_schema(&ctx)?;
+
+        assert_eq!(1, schema.num_dimension_labels()?);
+        assert!(schema.has_dimension_label("wavelength")?);
+        assert!(!schema.has_dimension_label("index")?);
+        assert!(!schema.has_dimension_label("intensity")?);
+
+        let label = schema.dimension_label("wavelength")?;
+        assert_eq!("wavelength", label.name()?);
+        assert_eq!(0, label.dimension_index()?);
+        assert_eq!(DataOrder::Increasing, label.label_order()?);
+        assert_eq!(Datatype::Float64, label.label_type()?);
+        assert_eq!(CellValNum::single(), label.label_cell_val_num()?);
+        assert_eq!(label, schema.dimension_label(0)?);
+
+        // labels are not fields of the schema, but can be looked up as one
+        assert_eq!(2, schema.num_fields()?);
+        let field = schema.field("wavelength")?;
+        assert!(field.is_dimension_label());
+        assert_eq!(Datatype::Float64, field.datatype()?);
+
+        let spec = SchemaData::try_from(&schema)?;
+        assert_eq!(
+            vec![DimensionLabelData {
+                name: "wavelength".to_owned(),
+                dimension_index: 0,
+                label_order: DataOrder::Increasing,
+                label_type: Datatype::Float64,
+            }],
+            spec.dimension_labels
+        );
+        assert_eq!(spec, SchemaData::try_from(spec.create(&ctx)?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn dimension_label_query() -> TileDBResult<()> {
+        let ctx = Context::new()?;
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let uri = test_uri
+            .with_path("spectrum")
+            .map_err(|e| Error::Other(e.to_string()))?;
+        Array::create(&ctx, &uri, spectrum_schema(&ctx)?)?;
+
+        let wavelength =
+            (0..10).map(|i| 400.0 + 25.0 * i as f64).collect::<Vec<_>>();
+        let intensity = (0..10).map(|i| i as f32 / 10.0).collect::<Vec<_>>();
+        {
+            let array = Array::open(&ctx, &uri, Mode::Write)?;
+            let q = WriteBuilder::new(array)?
+                .layout(QueryLayout::RowMajor)?
+                .start_subarray()?
+                .add_range("index", &[0i32, 9])?
+                .finish_subarray()?
+                .data_typed("intensity", &intensity)?
+                .data_typed("wavelength", &wavelength)?
+                .build();
+            q.submit().and_then(|_| q.finalize())?;
+        }
+
+        // select by label value, and read the label alongside the attribute
+        let array = Array::open(&ctx, &uri, Mode::Read)?;
+        let mut q = ReadBuilder::new(array)?
+            .layout(QueryLayout::RowMajor)?
+            .start_subarray()?
+            .add_label_range("wavelength", &[440.0f64, 510.0])?
+            .finish_subarray()?
+            .register_constructor::<_, Vec<f32>>(
+                "intensity",
+                Default::default(),
+            )?
+            .register_constructor::<_, Vec<f64>>(
+                "wavelength",
+                Default::default(),
+            )?
+            .build();
+        let (wavelength_out, (intensity_out, ())) = q.execute()?;
+        assert_eq!(wavelength[2..=4].to_vec(), wavelength_out);
+        assert_eq!(intensity[2..=4].to_vec(), intensity_out);
+
+        test_uri.close().map_err(|e| Error::Other(e.to_string()))
+    }
+}
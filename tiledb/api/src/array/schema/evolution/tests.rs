@@ -259,3 +259,77 @@ fn drop_enumeration() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Test expanding the current domain
+#[test]
+fn expand_current_domain() -> anyhow::Result<()> {
+    use tiledb_common::range::Range;
+    use tiledb_pod::array::{CurrentDomainData, NDRectangleData, SchemaData};
+
+    use crate::array::{CurrentDomain, Mode};
+    use crate::query::{Query, QueryBuilder, WriteBuilder};
+
+    let current_domain = |rows: [i32; 2], cols: [i32; 2]| {
+        CurrentDomainData::from(NDRectangleData {
+            ranges: vec![Range::from(&rows), Range::from(&cols)],
+        })
+    };
+
+    let array = TestArray::new("expand_current_domain", {
+        let mut b = quickstart::Builder::new(ArrayType::Sparse);
+        b.schema.current_domain = Some(current_domain([1, 2], [1, 2]));
+        b.build().into()
+    })?;
+
+    let write = |row: i32, col: i32| -> anyhow::Result<()> {
+        let (rows, cols, a) = (vec![row], vec![col], vec![row * col]);
+        let array = Array::open(&array.context, &array.uri, Mode::Write)?;
+        let q = WriteBuilder::new(array)?
+            .data("rows", &rows)?
+            .data("cols", &cols)?
+            .data("a", &a)?
+            .build();
+        q.submit()?;
+        q.finalize()?;
+        Ok(())
+    };
+
+    // cells outside of the current domain cannot be written
+    write(1, 1)?;
+    assert!(write(4, 4).is_err());
+
+    let expanded = current_domain([1, 4], [1, 4]);
+    let evolution = {
+        let domain = array.for_read()?.schema()?.domain()?;
+        Builder::new(&array.context)?
+            .expand_current_domain(CurrentDomain::from_data(
+                &array.context,
+                &domain,
+                &expanded,
+            )?)?
+            .build()
+    };
+    Array::evolve(&array.context, &array.uri, evolution)?;
+
+    let new_schema = array.for_read()?.schema()?;
+    assert_eq!(
+        Some(expanded),
+        SchemaData::try_from(&new_schema)?.current_domain
+    );
+    write(4, 4)?;
+
+    // the current domain cannot shrink
+    let evolution = {
+        let domain = new_schema.domain()?;
+        Builder::new(&array.context)?
+            .expand_current_domain(CurrentDomain::from_data(
+                &array.context,
+                &domain,
+                &current_domain([1, 3], [1, 3]),
+            )?)?
+            .build()
+    };
+    assert!(Array::evolve(&array.context, &array.uri, evolution).is_err());
+
+    Ok(())
+}
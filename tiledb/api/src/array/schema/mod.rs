@@ -1,3 +1,4 @@
+pub mod dimension_label;
 pub mod evolution;
 
 use std::borrow::Borrow;
@@ -11,6 +12,7 @@ use anyhow::anyhow;
 use itertools::Itertools;
 
 use crate::array::attribute::RawAttribute;
+use crate::array::current_domain::{CurrentDomain, RawCurrentDomain};
 use crate::array::dimension::Dimension;
 use crate::array::domain::RawDomain;
 use crate::array::enumeration::{Enumeration, RawEnumeration};
@@ -23,6 +25,9 @@ use crate::query::read::output::FieldScratchAllocator;
 use crate::Datatype;
 use crate::Result as TileDBResult;
 
+use self::dimension_label::RawDimensionLabel;
+
+pub use self::dimension_label::{DataOrder, DimensionLabel};
 pub use self::evolution::{Builder as SchemaEvolutionBuilder, SchemaEvolution};
 pub use tiledb_common::array::{ArrayType, CellValNum};
 
@@ -50,11 +55,20 @@ impl Drop for RawSchema {
     }
 }
 
+// SAFETY: a schema is immutable once `Builder::build` returns it, and the
+// builder owns the handle exclusively until then.
+unsafe impl Send for RawSchema {}
+unsafe impl Sync for RawSchema {}
+
 /// Holds a field of the schema, which may be either a dimension or an attribute.
+///
+/// A dimension label is not a field of the schema itself, but its data
+/// is queried by name in the same way as a field.
 #[derive(PartialEq)]
 pub enum Field {
     Dimension(Dimension),
     Attribute(Attribute),
+    DimensionLabel(DimensionLabel),
 }
 
 impl Field {
@@ -66,10 +80,15 @@ impl Field {
         matches!(self, Self::Dimension(_))
     }
 
+    pub fn is_dimension_label(&self) -> bool {
+        matches!(self, Self::DimensionLabel(_))
+    }
+
     pub fn name(&self) -> TileDBResult<String> {
         match self {
             Field::Dimension(ref d) => d.name(),
             Field::Attribute(ref a) => a.name(),
+            Field::DimensionLabel(ref l) => l.name(),
         }
     }
 
@@ -77,6 +96,7 @@ impl Field {
         match self {
             Field::Dimension(ref d) => d.datatype(),
             Field::Attribute(ref a) => a.datatype(),
+            Field::DimensionLabel(ref l) => l.label_type(),
         }
     }
 
@@ -84,6 +104,7 @@ impl Field {
         Ok(match self {
             Field::Dimension(_) => false,
             Field::Attribute(ref a) => a.is_nullable()?,
+            Field::DimensionLabel(_) => false,
         })
     }
 
@@ -91,6 +112,7 @@ impl Field {
         match self {
             Field::Dimension(ref d) => d.cell_val_num(),
             Field::Attribute(ref a) => a.cell_val_num(),
+            Field::DimensionLabel(ref l) => l.label_cell_val_num(),
         }
     }
 
@@ -132,6 +154,12 @@ impl From<Attribute> for Field {
     }
 }
 
+impl From<DimensionLabel> for Field {
+    fn from(label: DimensionLabel) -> Field {
+        Field::DimensionLabel(label)
+    }
+}
+
 type FnFilterListGet = unsafe extern "C" fn(
     *mut ffi::tiledb_ctx_t,
     *mut ffi::tiledb_array_schema_t,
@@ -171,6 +199,26 @@ impl Schema {
         Ok(Domain::new(&self.context, RawDomain::Owned(c_domain)))
     }
 
+    /// Returns the current domain of this schema.
+    /// The current domain is empty if none was set.
+    pub fn current_domain(&self) -> TileDBResult<CurrentDomain> {
+        let c_schema = *self.raw;
+        let mut c_current_domain: *mut ffi::tiledb_current_domain_t =
+            out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_get_current_domain(
+                ctx,
+                c_schema,
+                &mut c_current_domain,
+            )
+        })?;
+
+        Ok(CurrentDomain::new(
+            &self.context,
+            RawCurrentDomain::Owned(c_current_domain),
+        ))
+    }
+
     /// Retrieve the schema of an array from storage
     pub fn load<S>(context: &Context, uri: S) -> TileDBResult<Self>
     where
@@ -337,6 +385,9 @@ impl Schema {
     /// If the key is an index, then values `[0.. ndimensions]` will look
     /// up a dimension, and values outside that range will be adjusted by `ndimensions`
     /// to look up an attribute.
+    ///
+    /// If the key is a name which is neither a dimension nor an attribute,
+    /// then it may also look up a dimension label.
     pub fn field<K: Into<LookupKey>>(&self, key: K) -> TileDBResult<Field> {
         let domain = self.domain()?;
         match key.into() {
@@ -351,6 +402,8 @@ impl Schema {
             LookupKey::Name(name) => {
                 if domain.has_dimension(&name)? {
                     Ok(Field::Dimension(domain.dimension(name)?))
+                } else if self.has_dimension_label(&name)? {
+                    Ok(Field::DimensionLabel(self.dimension_label(name)?))
                 } else {
                     Ok(Field::Attribute(self.attribute(name)?))
                 }
@@ -362,6 +415,83 @@ impl Schema {
         Fields::new(self)
     }
 
+    pub fn num_dimension_labels(&self) -> TileDBResult<usize> {
+        let c_schema = self.capi();
+        let mut c_nlabels: u64 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_get_dimension_label_num(
+                ctx,
+                c_schema,
+                &mut c_nlabels,
+            )
+        })?;
+        Ok(c_nlabels as usize)
+    }
+
+    /// Returns whether this schema has a dimension label named `name`.
+    pub fn has_dimension_label<S>(&self, name: S) -> TileDBResult<bool>
+    where
+        S: AsRef<str>,
+    {
+        let c_schema = self.capi();
+        let c_name = cstring!(name.as_ref());
+        let mut c_has: i32 = out_ptr!();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_has_dimension_label(
+                ctx,
+                c_schema,
+                c_name.as_ptr(),
+                &mut c_has,
+            )
+        })?;
+        Ok(c_has != 0)
+    }
+
+    pub fn dimension_label<K: Into<LookupKey>>(
+        &self,
+        key: K,
+    ) -> TileDBResult<DimensionLabel> {
+        let c_schema = self.capi();
+        let mut c_label: *mut ffi::tiledb_dimension_label_t = out_ptr!();
+
+        match key.into() {
+            LookupKey::Index(idx) => {
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_array_schema_get_dimension_label_from_index(
+                        ctx,
+                        c_schema,
+                        idx as u64,
+                        &mut c_label,
+                    )
+                })?;
+            }
+            LookupKey::Name(name) => {
+                let c_name = cstring!(name);
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_array_schema_get_dimension_label_from_name(
+                        ctx,
+                        c_schema,
+                        c_name.as_ptr(),
+                        &mut c_label,
+                    )
+                })?;
+            }
+        }
+
+        Ok(DimensionLabel::new(
+            &self.context,
+            RawDimensionLabel::Owned(c_label),
+        ))
+    }
+
+    /// Returns an [Iterator] over the dimension labels of this schema.
+    pub fn dimension_labels(
+        &self,
+    ) -> TileDBResult<impl Iterator<Item = TileDBResult<DimensionLabel>> + '_>
+    {
+        Ok((0..self.num_dimension_labels()?).map(|l| self.dimension_label(l)))
+    }
+
     /// Returns the enumeration identified by the requested key.
     pub fn enumeration(
         &self,
@@ -463,6 +593,11 @@ impl PartialEq<Schema> for Schema {
             eq_helper!(self.attribute(a), other.attribute(a));
         }
 
+        eq_helper!(self.num_dimension_labels(), other.num_dimension_labels());
+        for l in 0..self.num_dimension_labels().unwrap() {
+            eq_helper!(self.dimension_label(l), other.dimension_label(l));
+        }
+
         eq_helper!(self.domain(), other.domain());
 
         true
@@ -654,6 +789,79 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sets the current domain of the schema, which must be contained
+    /// within its domain. This is only supported for sparse arrays.
+    pub fn current_domain(
+        self,
+        current_domain: CurrentDomain,
+    ) -> TileDBResult<Self> {
+        let c_schema = self.schema.capi();
+        let c_current_domain = current_domain.capi();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_set_current_domain(
+                ctx,
+                c_schema,
+                c_current_domain,
+            )
+        })?;
+        Ok(self)
+    }
+
+    /// Adds a label named `name` to the dimension at `dimension_index`.
+    /// The label values have type `label_type` and are ordered
+    /// relative to the dimension values by `label_order`.
+    pub fn add_dimension_label(
+        self,
+        dimension_index: usize,
+        name: &str,
+        label_order: DataOrder,
+        label_type: Datatype,
+    ) -> TileDBResult<Self> {
+        let c_schema = self.schema.capi();
+        let c_dimension_index: u32 = dimension_index.try_into().map_err(
+            |e: <usize as TryInto<u32>>::Error| {
+                Error::InvalidArgument(anyhow!(e))
+            },
+        )?;
+        let c_name = cstring!(name);
+        let c_order = ffi::tiledb_data_order_t::from(label_order);
+        let c_datatype = ffi::tiledb_datatype_t::from(label_type);
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_add_dimension_label(
+                ctx,
+                c_schema,
+                c_dimension_index,
+                c_name.as_ptr(),
+                c_order,
+                c_datatype,
+            )
+        })?;
+        Ok(self)
+    }
+
+    /// Sets the filters used for the data of the dimension label named `name`.
+    pub fn dimension_label_filters<FL>(
+        self,
+        name: &str,
+        filters: FL,
+    ) -> TileDBResult<Self>
+    where
+        FL: Borrow<FilterList>,
+    {
+        let c_schema = self.schema.capi();
+        let c_name = cstring!(name);
+        let filters = filters.borrow();
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_array_schema_set_dimension_label_filter_list(
+                ctx,
+                c_schema,
+                c_name.as_ptr(),
+                filters.capi(),
+            )
+        })?;
+        Ok(self)
+    }
+
     fn filter_list<FL>(
         self,
         filters: FL,
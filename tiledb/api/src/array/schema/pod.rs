@@ -1,10 +1,15 @@
+use anyhow::anyhow;
 use itertools::Itertools;
 use tiledb_common::filter::FilterData;
 use tiledb_pod::array::attribute::AttributeData;
 use tiledb_pod::array::schema::{FieldData, SchemaData};
-use tiledb_pod::array::{DimensionData, DomainData, EnumerationData};
+use tiledb_pod::array::{
+    CurrentDomainData, DimensionData, DimensionLabelData, DomainData,
+    EnumerationData,
+};
 
-use super::{Builder, EnumerationKey, Field, Schema};
+use super::{Builder, DimensionLabel, EnumerationKey, Field, Schema};
+use crate::array::CurrentDomain;
 use crate::error::Error;
 use crate::{Context, Factory, Result as TileDBResult};
 
@@ -46,6 +51,18 @@ impl TryFrom<&Schema> for SchemaData {
             nullity_filters: Vec::<FilterData>::try_from(
                 &schema.nullity_filters()?,
             )?,
+            current_domain: {
+                let current_domain = schema.current_domain()?;
+                if current_domain.is_empty()? {
+                    None
+                } else {
+                    Some(CurrentDomainData::try_from(&current_domain)?)
+                }
+            },
+            dimension_labels: schema
+                .dimension_labels()?
+                .map(|l| DimensionLabelData::try_from(&l?))
+                .collect::<TileDBResult<Vec<DimensionLabelData>>>()?,
         })
     }
 }
@@ -93,6 +110,22 @@ impl Factory for SchemaData {
         if let Some(o) = self.tile_order {
             b = b.tile_order(o)?;
         }
+        b = self.dimension_labels.iter().try_fold(b, |b, l| {
+            b.add_dimension_label(
+                l.dimension_index,
+                &l.name,
+                l.label_order,
+                l.label_type,
+            )
+        })?;
+        if let Some(ref current_domain) = self.current_domain {
+            let domain = self.domain.create(context)?;
+            b = b.current_domain(CurrentDomain::from_data(
+                context,
+                &domain,
+                current_domain,
+            )?)?;
+        }
 
         b.build()
     }
@@ -105,6 +138,10 @@ impl TryFrom<&Field> for FieldData {
         match field {
             Field::Dimension(d) => Ok(Self::from(DimensionData::try_from(d)?)),
             Field::Attribute(a) => Ok(Self::from(AttributeData::try_from(a)?)),
+            Field::DimensionLabel(l) => Err(Error::InvalidArgument(anyhow!(
+                "Dimension label '{}' has no field data",
+                l.name()?
+            ))),
         }
     }
 }
@@ -116,3 +153,24 @@ impl TryFrom<Field> for FieldData {
         Self::try_from(&field)
     }
 }
+
+impl TryFrom<&DimensionLabel> for DimensionLabelData {
+    type Error = Error;
+
+    fn try_from(label: &DimensionLabel) -> Result<Self, Self::Error> {
+        Ok(DimensionLabelData {
+            name: label.name()?,
+            dimension_index: label.dimension_index()?,
+            label_order: label.label_order()?,
+            label_type: label.label_type()?,
+        })
+    }
+}
+
+impl TryFrom<DimensionLabel> for DimensionLabelData {
+    type Error = Error;
+
+    fn try_from(label: DimensionLabel) -> Result<Self, Self::Error> {
+        Self::try_from(&label)
+    }
+}
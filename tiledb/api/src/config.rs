@@ -23,6 +23,11 @@ impl Drop for RawConfig {
     }
 }
 
+// SAFETY: a config is only modified through `&mut Config`, and libtiledb
+// permits concurrent reads of an unmodified config.
+unsafe impl Send for RawConfig {}
+unsafe impl Sync for RawConfig {}
+
 pub(crate) enum RawConfigIter {
     Owned(*mut ffi::tiledb_config_iter_t),
 }
@@ -44,6 +49,9 @@ impl Drop for RawConfigIter {
     }
 }
 
+// SAFETY: the iterator is only advanced through `&mut ConfigIterator`.
+unsafe impl Send for RawConfigIter {}
+
 pub struct Config {
     pub(crate) raw: RawConfig,
 }
@@ -1,11 +1,11 @@
 use std::convert::From;
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use std::ops::Deref;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::config::{Config, RawConfig};
 use crate::filesystem::Filesystem;
-use crate::stats::RawStatsString;
+use crate::stats::{Metrics, RawStatsString};
 use crate::Result as TileDBResult;
 
 /// An error which can occur when creating a new `Context`.
@@ -75,6 +75,11 @@ pub(crate) struct RawContext {
     raw: *mut ffi::tiledb_ctx_t,
 }
 
+// SAFETY: libtiledb contexts are thread-safe. Every C API call which takes a
+// `tiledb_ctx_t` synchronizes internally, so the handle may be shared freely.
+unsafe impl Send for RawContext {}
+unsafe impl Sync for RawContext {}
+
 impl Drop for RawContext {
     fn drop(&mut self) {
         unsafe {
@@ -109,9 +114,17 @@ where
     }
 }
 
+/// A handle to a libtiledb context.
+///
+/// `Context` is cheap to clone and may be shared across threads.
+/// All clones refer to the same underlying libtiledb context.
+///
+/// Note that libtiledb tracks only the most recent error for each context,
+/// so when several threads encounter errors at once using the same context
+/// the error message reported by one thread may belong to another.
 #[derive(Clone)]
 pub struct Context {
-    raw: Rc<RawContext>,
+    raw: Arc<RawContext>,
 }
 
 impl Context {
@@ -125,7 +138,7 @@ impl Context {
         let res = unsafe { ffi::tiledb_ctx_alloc(cfg.capi(), &mut c_ctx) };
         match res {
             ffi::TILEDB_OK => Ok(Context {
-                raw: Rc::new(RawContext { raw: c_ctx }),
+                raw: Arc::new(RawContext { raw: c_ctx }),
             }),
             ffi::TILEDB_OOM => Err(CreateContextError::OutOfMemory),
             ffi::TILEDB_ERR => Err(CreateContextError::Fatal),
@@ -149,7 +162,24 @@ impl Context {
         }
     }
 
-    pub fn get_stats(&self) -> TileDBResult<String> {
+    /// Cancels all tasks which are in progress on this context.
+    ///
+    /// A query which is running when its tasks are cancelled fails.
+    /// Queries which have a [CancellationToken] report the failure
+    /// as [Error::Cancelled] if the token is cancelled before
+    /// calling this function.
+    ///
+    /// [CancellationToken]: crate::query::CancellationToken
+    /// [Error::Cancelled]: crate::error::Error::Cancelled
+    pub fn cancel_tasks(&self) -> TileDBResult<()> {
+        self.capi_call(|ctx| unsafe { ffi::tiledb_ctx_cancel_tasks(ctx) })?;
+        Ok(())
+    }
+
+    /// Returns the stats collected by this context, as a JSON string.
+    ///
+    /// This is empty unless stats are enabled using [crate::stats::enable].
+    pub fn get_stats_json(&self) -> TileDBResult<String> {
         let mut c_json: *mut std::ffi::c_char = out_ptr!();
         self.capi_call(|ctx| unsafe {
             ffi::tiledb_ctx_get_stats(
@@ -164,6 +194,14 @@ impl Context {
         Ok(String::from(json.to_string_lossy()))
     }
 
+    /// Returns the stats collected by this context.
+    ///
+    /// The timers and counters are empty unless stats are enabled
+    /// using [crate::stats::enable].
+    pub fn get_stats(&self) -> TileDBResult<Metrics> {
+        Ok(crate::stats::parse_metrics(&self.get_stats_json()?)?)
+    }
+
     pub fn get_config(&self) -> TileDBResult<Config> {
         let mut c_cfg: *mut ffi::tiledb_config_t = out_ptr!();
         self.capi_call(|ctx| unsafe {
@@ -271,6 +309,13 @@ pub enum ObjectTypeError {
 mod tests {
     use super::*;
 
+    #[test]
+    /// Ensure that Context is Send and Sync, fails to compile if not
+    fn is_send_sync() {
+        fn is_send_sync<T: Send + Sync>() {}
+        is_send_sync::<Context>()
+    }
+
     #[test]
     fn ctx_alloc() {
         Context::new().expect("Error creating context instance.");
@@ -284,11 +329,18 @@ mod tests {
 
     #[test]
     fn ctx_get_stats() {
+        // other tests enable stats, which would make these non-empty
+        let _lock = crate::stats::tests::lock_stats();
+
         let ctx = Context::new().expect("Error creating context instance.");
-        let json = ctx.get_stats();
+        let json = ctx.get_stats_json();
         // I have to wrap enable_stats/disable_stats before we'll get anything
         // useful out of this.
         assert!(json.unwrap() == "");
+
+        let metrics = ctx.get_stats().unwrap();
+        assert!(metrics.timers.is_empty());
+        assert!(metrics.counters.is_empty());
     }
 
     #[test]
@@ -53,6 +53,8 @@ pub enum Error {
     CellOrder(#[from] tiledb_common::array::CellOrderError),
     #[error("TileOrder error: {0}")]
     TileOrder(#[from] tiledb_common::array::TileOrderError),
+    #[error("DataOrder error: {0}")]
+    DataOrder(#[from] tiledb_common::array::DataOrderError),
     #[error("FilterType error: {0}")]
     FilterType(#[from] crate::filter::FilterTypeError),
     #[error("FilterOption error: {0}")]
@@ -92,6 +94,9 @@ pub enum Error {
     #[error("Query callback error for attribute [{}]: {1}",
         .0.iter().map(|s| s.as_ref()).collect::<Vec<&str>>().join(","))]
     QueryCallback(Vec<String>, #[source] anyhow::Error),
+    /// Error when a query is cancelled using its cancellation token.
+    #[error("Query cancelled")]
+    Cancelled,
     /// Any error which cannot be categorized as any of the above
     #[error("{0}")]
     Other(String),
@@ -26,6 +26,10 @@ impl Drop for RawFilterList {
     }
 }
 
+// SAFETY: filters are only added to a list through `Builder`.
+unsafe impl Send for RawFilterList {}
+unsafe impl Sync for RawFilterList {}
+
 pub struct FilterList {
     pub(crate) context: Context,
     pub(crate) raw: RawFilterList,
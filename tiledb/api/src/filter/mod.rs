@@ -41,6 +41,10 @@ impl Drop for RawFilter {
     }
 }
 
+// SAFETY: filter options are only set while the filter is being built.
+unsafe impl Send for RawFilter {}
+unsafe impl Sync for RawFilter {}
+
 pub struct Filter {
     context: Context,
     pub(crate) raw: RawFilter,
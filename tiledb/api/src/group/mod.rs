@@ -1,3 +1,5 @@
+mod tree;
+
 use std::ops::Deref;
 
 use crate::config::{Config, RawConfig};
@@ -6,18 +8,21 @@ use crate::context::{CApiInterface, ContextBound, ObjectType};
 use crate::error::Error;
 use crate::key::LookupKey;
 use crate::metadata;
-use crate::metadata::Metadata;
+use crate::metadata::{Metadata, MetadataIter};
 use crate::string::{RawTDBString, TDBString};
 use crate::Datatype;
 use crate::Result as TileDBResult;
 
+pub use self::tree::GroupTree;
+
 pub type QueryType = crate::array::Mode;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct GroupInfo {
     pub uri: String,
     pub group_type: ObjectType,
-    pub name: String,
+    /// Name of the member, if it was added with one.
+    pub name: Option<String>,
 }
 
 pub(crate) struct RawGroup {
@@ -43,6 +48,11 @@ impl Drop for RawGroup {
     }
 }
 
+// SAFETY: libtiledb synchronizes access to group state internally, and
+// group modifications are only exposed through `&mut Group`.
+unsafe impl Send for RawGroup {}
+unsafe impl Sync for RawGroup {}
+
 pub struct Group {
     context: Context,
     raw: RawGroup,
@@ -218,11 +228,14 @@ impl Group {
         Ok(c_count)
     }
 
+    /// Returns the member with the given index or name.
+    ///
+    /// The name of a member which was added without one is `None`.
     pub fn member(&self, key: LookupKey) -> TileDBResult<GroupInfo> {
         let c_group = self.capi();
         let mut tiledb_uri: *mut ffi::tiledb_string_t = out_ptr!();
         let mut tiledb_type: ffi::tiledb_object_t = out_ptr!();
-        let name: String = match key {
+        let name = match key {
             LookupKey::Index(index) => {
                 let mut tiledb_name: *mut ffi::tiledb_string_t = out_ptr!();
                 self.capi_call(|ctx| unsafe {
@@ -235,15 +248,20 @@ impl Group {
                         &mut tiledb_name as *mut *mut ffi::tiledb_string_t,
                     )
                 })?;
-                let name = TDBString {
-                    raw: RawTDBString::Owned(tiledb_name),
+                // libtiledb returns no name for a member added without one
+                if tiledb_name.is_null() {
+                    None
+                } else {
+                    Some(
+                        TDBString {
+                            raw: RawTDBString::Owned(tiledb_name),
+                        }
+                        .to_string()?,
+                    )
                 }
-                .to_string();
-                let name = name?;
-                Ok(name) as TileDBResult<String>
             }
             LookupKey::Name(name) => {
-                let c_name = cstring!(name.as_ref() as &str);
+                let c_name = cstring!(name.as_str());
                 self.capi_call(|ctx| unsafe {
                     ffi::tiledb_group_get_member_by_name_v2(
                         ctx,
@@ -253,15 +271,14 @@ impl Group {
                         &mut tiledb_type,
                     )
                 })?;
-                Ok(name.to_owned())
+                Some(name)
             }
-        }?;
+        };
 
         let uri = TDBString {
             raw: RawTDBString::Owned(tiledb_uri),
         }
-        .to_string();
-        let uri = uri?;
+        .to_string()?;
 
         let object_type = ObjectType::from_capi(tiledb_type)?.unwrap();
         Ok(GroupInfo {
@@ -271,6 +288,15 @@ impl Group {
         })
     }
 
+    /// Returns an iterator over all of the members of this group.
+    pub fn members(&self) -> TileDBResult<Members<'_>> {
+        Ok(Members {
+            group: self,
+            index: 0,
+            count: self.num_members()?,
+        })
+    }
+
     pub fn is_relative_uri<S>(&self, name: S) -> TileDBResult<bool>
     where
         S: AsRef<str>,
@@ -346,6 +372,17 @@ impl Group {
         Ok(())
     }
 
+    /// Writes each of the items of `metadata`.
+    pub fn put_metadata_all<I>(&mut self, metadata: I) -> TileDBResult<()>
+    where
+        I: IntoIterator<Item = Metadata>,
+    {
+        for m in metadata {
+            self.put_metadata(m)?;
+        }
+        Ok(())
+    }
+
     pub fn delete_metadata<S>(&mut self, name: S) -> TileDBResult<()>
     where
         S: AsRef<str>,
@@ -411,11 +448,12 @@ impl Group {
             }
         }?;
         let datatype = Datatype::try_from(c_datatype)?;
-        Ok(metadata::metadata_from_ffi(
-            name,
-            datatype,
-            (vec_size, vec_ptr),
-        ))
+        metadata::metadata_from_ffi(name, datatype, (vec_size, vec_ptr))
+    }
+
+    /// Returns an iterator over all of the metadata of this group.
+    pub fn metadata_iter(&self) -> TileDBResult<MetadataIter<'_>> {
+        MetadataIter::for_group(self)
     }
 
     pub fn has_metadata_key<S>(&self, name: S) -> TileDBResult<Option<Datatype>>
@@ -480,6 +518,37 @@ impl Group {
     }
 }
 
+/// Iterates over the members of a [Group] in order of index.
+pub struct Members<'a> {
+    group: &'a Group,
+    index: u64,
+    count: u64,
+}
+
+impl Iterator for Members<'_> {
+    type Item = TileDBResult<GroupInfo>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.count {
+            let member =
+                self.group.member(LookupKey::Index(self.index as usize));
+            self.index += 1;
+            Some(member)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.count - self.index) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl std::iter::FusedIterator for Members<'_> {}
+
+impl ExactSizeIterator for Members<'_> {}
+
 impl Drop for Group {
     fn drop(&mut self) {
         let c_group = self.capi();
@@ -500,6 +569,9 @@ impl Drop for Group {
     }
 }
 
+#[cfg(any(test, feature = "pod"))]
+pub mod pod;
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -522,6 +594,13 @@ mod tests {
     };
     use uri::{self, TestArrayUri};
 
+    #[test]
+    /// Ensure that Group is Send and Sync, fails to compile if not
+    fn is_send_sync() {
+        fn is_send_sync<T: Send + Sync>() {}
+        is_send_sync::<Group>()
+    }
+
     #[test]
     fn test_group_metadata() -> TileDBResult<()> {
         let test_uri = uri::get_uri_generator()
@@ -612,6 +691,95 @@ mod tests {
         test_uri.close().map_err(|e| Error::Other(e.to_string()))
     }
 
+    #[test]
+    fn test_group_metadata_string_blob_bool() -> TileDBResult<()> {
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        let tdb = Context::new()?;
+        let group_uri = test_uri
+            .with_path("group")
+            .map_err(|e| Error::Other(e.to_string()))?;
+        Group::create(&tdb, &group_uri)?;
+
+        let values = vec![
+            ("s", Datatype::StringUtf8, metadata::Value::from("héllo")),
+            (
+                "b",
+                Datatype::Blob,
+                metadata::Value::BlobValue(vec![1, 2, 3]),
+            ),
+            (
+                "f",
+                Datatype::Boolean,
+                metadata::Value::from(vec![false, true]),
+            ),
+        ];
+
+        {
+            let mut group =
+                Group::open(&tdb, &group_uri, QueryType::Write, None)?;
+            for (key, datatype, value) in values.iter() {
+                group.put_metadata(Metadata::new(
+                    key.to_string(),
+                    *datatype,
+                    value.clone(),
+                )?)?;
+            }
+        }
+
+        {
+            let group = Group::open(&tdb, &group_uri, QueryType::Read, None)?;
+            for (key, datatype, value) in values.into_iter() {
+                let metadata = group.metadata(key.to_owned())?;
+                assert_eq!(datatype, metadata.datatype);
+                assert_eq!(value, metadata.value);
+            }
+        }
+
+        test_uri.close().map_err(|e| Error::Other(e.to_string()))
+    }
+
+    #[test]
+    fn test_group_metadata_iter() -> TileDBResult<()> {
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        let tdb = Context::new()?;
+        let group_uri = test_uri
+            .with_path("group")
+            .map_err(|e| Error::Other(e.to_string()))?;
+        Group::create(&tdb, &group_uri)?;
+
+        {
+            let group = Group::open(&tdb, &group_uri, QueryType::Read, None)?;
+            assert_eq!(0, group.metadata_iter()?.count());
+        }
+
+        {
+            let mut group =
+                Group::open(&tdb, &group_uri, QueryType::Write, None)?;
+            group.put_metadata_all([
+                Metadata::new("x".to_owned(), Datatype::UInt16, vec![1u16])?,
+                Metadata::new("y".to_owned(), Datatype::Blob, vec![2u8])?,
+            ])?;
+        }
+
+        {
+            let group = Group::open(&tdb, &group_uri, QueryType::Read, None)?;
+            let map = group.metadata_iter()?.into_map()?;
+            assert_eq!(
+                vec![
+                    ("x".to_owned(), metadata::Value::UInt16Value(vec![1])),
+                    ("y".to_owned(), metadata::Value::BlobValue(vec![2])),
+                ],
+                map.into_iter().collect::<Vec<_>>()
+            );
+        }
+
+        test_uri.close().map_err(|e| Error::Other(e.to_string()))
+    }
+
     fn create_array<S>(array_uri: S, array_type: ArrayType) -> TileDBResult<()>
     where
         S: AsRef<str>,
@@ -721,12 +889,12 @@ mod tests {
 
             let member_aa =
                 group_read.member(LookupKey::Name("aa".to_owned()))?;
-            assert_eq!(member_aa.name, "aa".to_owned());
+            assert_eq!(member_aa.name, Some("aa".to_owned()));
             assert_eq!(member_aa.group_type, ObjectType::Array);
             assert_eq!(group_uri.clone() + "/aa", member_aa.uri);
 
             let member_cc = group_read.member(LookupKey::Index(1))?;
-            assert_eq!(member_cc.name, "cc".to_owned());
+            assert_eq!(member_cc.name, Some("cc".to_owned()));
             assert_eq!(member_cc.group_type, ObjectType::Array);
             assert_eq!(group_uri.clone() + "/cc", member_cc.uri);
 
@@ -0,0 +1,487 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use tiledb_pod::array::SchemaData;
+use tiledb_pod::group::{ArrayData, DatasetData, MemberData, ObjectData};
+
+use super::tree::canonical_uri;
+use super::{Group, QueryType};
+use crate::array::{Array, Mode};
+use crate::context::{Context, ObjectType};
+use crate::error::Error;
+use crate::metadata::{Metadata, MetadataIter};
+use crate::{Factory, Result as TileDBResult};
+
+/// Creates the group described by `dataset` at `uri`,
+/// along with its metadata and all of its members.
+///
+/// Each member is created at `uri` joined with the member name.
+/// Groups and arrays which already exist are kept, as are members which
+/// a group already has under the same name, and metadata is only written
+/// if it differs from what is already there. As a result, creating the
+/// same dataset again does nothing. The schema of an existing array is
+/// not compared with the schema in `dataset`.
+pub fn create_dataset<S>(
+    context: &Context,
+    uri: S,
+    dataset: &DatasetData,
+) -> TileDBResult<()>
+where
+    S: AsRef<str>,
+{
+    let uri = uri.as_ref();
+
+    let mut names = HashSet::new();
+    for member in dataset.members.iter() {
+        if !names.insert(member.name.as_str()) {
+            return Err(Error::InvalidArgument(anyhow!(
+                "Duplicate member name '{}' for group '{}'",
+                member.name,
+                uri
+            )));
+        }
+    }
+
+    require_object(context, uri, ObjectType::Group)?;
+    for member in dataset.members.iter() {
+        let member_uri = member_uri(uri, &member.name)?;
+        match member.object {
+            ObjectData::Group(ref group) => {
+                create_dataset(context, &member_uri, group)?
+            }
+            ObjectData::Array(ref array) => {
+                create_array(context, &member_uri, array)?
+            }
+        }
+    }
+
+    let (existing, metadata) = {
+        let group = Group::open(context, uri, QueryType::Read, None)?;
+        let existing = group
+            .members()?
+            .filter_map(|member| member.map(|member| member.name).transpose())
+            .collect::<TileDBResult<HashSet<String>>>()?;
+        let metadata =
+            changed_metadata(group.metadata_iter()?, &dataset.metadata)?;
+        (existing, metadata)
+    };
+    let members = dataset
+        .members
+        .iter()
+        .filter(|member| !existing.contains(&member.name))
+        .collect::<Vec<_>>();
+    if members.is_empty() && metadata.is_empty() {
+        return Ok(());
+    }
+
+    let mut group = Group::open(context, uri, QueryType::Write, None)?;
+    for member in members {
+        if member.relative {
+            group.add_member(&member.name, true, Some(&member.name))?;
+        } else {
+            group.add_member(
+                member_uri(uri, &member.name)?,
+                false,
+                Some(&member.name),
+            )?;
+        }
+    }
+    group.put_metadata_all(metadata)
+}
+
+fn create_array(
+    context: &Context,
+    uri: &str,
+    array: &ArrayData,
+) -> TileDBResult<()> {
+    if !require_object(context, uri, ObjectType::Array)? {
+        Array::create(context, uri, array.schema.create(context)?)?;
+    }
+
+    let metadata = changed_metadata(
+        Array::open(context, uri, Mode::Read)?.metadata_iter()?,
+        &array.metadata,
+    )?;
+    if !metadata.is_empty() {
+        Array::open(context, uri, Mode::Write)?.put_metadata_all(metadata)?;
+    }
+    Ok(())
+}
+
+/// Checks that the object at `uri`, if any, has the expected type.
+/// If there is no object and a group is expected, creates the group.
+/// Returns whether the object already existed.
+fn require_object(
+    context: &Context,
+    uri: &str,
+    expect: ObjectType,
+) -> TileDBResult<bool> {
+    match context.object_type(uri)? {
+        None => {
+            if expect == ObjectType::Group {
+                Group::create(context, uri)?;
+            }
+            Ok(false)
+        }
+        Some(found) if found == expect => Ok(true),
+        Some(found) => Err(Error::InvalidArgument(anyhow!(
+            "Cannot create {} at '{}': found {}",
+            expect,
+            uri,
+            found
+        ))),
+    }
+}
+
+fn member_uri(uri: &str, name: &str) -> TileDBResult<String> {
+    if name.is_empty() || name == "." || name == ".." || name.contains('/') {
+        return Err(Error::InvalidArgument(anyhow!(
+            "Invalid member name '{}' for group '{}'",
+            name,
+            uri
+        )));
+    }
+    Ok(format!("{}/{}", uri.trim_end_matches('/'), name))
+}
+
+/// Returns the items of `wanted` which are not in `existing`.
+fn changed_metadata(
+    existing: MetadataIter,
+    wanted: &[Metadata],
+) -> TileDBResult<Vec<Metadata>> {
+    let existing = existing.collect::<TileDBResult<Vec<Metadata>>>()?;
+    Ok(wanted
+        .iter()
+        .filter(|m| !existing.contains(m))
+        .cloned()
+        .collect())
+}
+
+/// Describes a group, its metadata, and all of its members.
+/// The group must be open for reading.
+///
+/// [create_dataset] creates each member at the URI of its group joined with
+/// its name, which may differ from where the member is now.
+/// A member which was added without a name cannot be described, since
+/// [create_dataset] needs its name and libtiledb cannot tell whether its
+/// URI is relative, and returns an error.
+/// A group which is a member of itself, directly or through other groups,
+/// cannot be described and returns an error.
+impl TryFrom<&Group> for DatasetData {
+    type Error = Error;
+
+    fn try_from(group: &Group) -> Result<Self, Self::Error> {
+        let mut ancestors = vec![canonical_uri(&group.uri()?)];
+        dataset_data(group, &mut ancestors)
+    }
+}
+
+impl TryFrom<Group> for DatasetData {
+    type Error = Error;
+
+    fn try_from(group: Group) -> Result<Self, Self::Error> {
+        Self::try_from(&group)
+    }
+}
+
+fn dataset_data(
+    group: &Group,
+    ancestors: &mut Vec<String>,
+) -> TileDBResult<DatasetData> {
+    let metadata = group.metadata_iter()?.collect::<TileDBResult<_>>()?;
+
+    let mut members = vec![];
+    for member in group.members()? {
+        let member = member?;
+        let Some(name) = member.name else {
+            return Err(Error::InvalidArgument(anyhow!(
+                "Member '{}' of group '{}' has no name",
+                member.uri,
+                group.uri()?
+            )));
+        };
+        let relative = group.is_relative_uri(&name)?;
+        let object = match member.group_type {
+            ObjectType::Array => {
+                let array =
+                    Array::open(&group.context, &member.uri, Mode::Read)?;
+                ObjectData::Array(ArrayData {
+                    schema: SchemaData::try_from(array.schema()?)?,
+                    metadata: array
+                        .metadata_iter()?
+                        .collect::<TileDBResult<_>>()?,
+                })
+            }
+            ObjectType::Group => {
+                let key = canonical_uri(&member.uri);
+                if ancestors.contains(&key) {
+                    return Err(Error::InvalidArgument(anyhow!(
+                        "Group '{}' is a member of itself through '{}'",
+                        member.uri,
+                        name
+                    )));
+                }
+                let subgroup = Group::open(
+                    &group.context,
+                    &member.uri,
+                    QueryType::Read,
+                    Some(&group.config()?),
+                )?;
+                ancestors.push(key);
+                let dataset = dataset_data(&subgroup, ancestors);
+                ancestors.pop();
+                ObjectData::Group(dataset?)
+            }
+        };
+        members.push(MemberData {
+            name,
+            relative,
+            object,
+        });
+    }
+
+    Ok(DatasetData { metadata, members })
+}
+
+#[cfg(test)]
+mod tests {
+    use tiledb_common::array::ArrayType;
+    use uri::TestDirectory;
+
+    use super::*;
+    use crate::datatype::Datatype;
+    use crate::key::LookupKey;
+    use crate::tests::examples::quickstart;
+
+    fn metadata(key: &str, value: i32) -> Metadata {
+        Metadata::new(key.to_owned(), Datatype::Int32, vec![value]).unwrap()
+    }
+
+    /// Returns a dataset:
+    /// ```text
+    /// (root)        (metadata "version" = 1)
+    /// |-- arrays    (relative group)
+    /// |   |-- dense (relative array, metadata "rows" = 4)
+    /// |   |-- sparse (absolute array)
+    /// |-- notes     (absolute group)
+    /// ```
+    fn example_dataset() -> DatasetData {
+        let array = |array_type, relative, name: &str, metadata| MemberData {
+            name: name.to_owned(),
+            relative,
+            object: ObjectData::Array(ArrayData {
+                schema: quickstart::Builder::new(array_type).build(),
+                metadata,
+            }),
+        };
+        DatasetData {
+            metadata: vec![metadata("version", 1)],
+            members: vec![
+                MemberData {
+                    name: "arrays".to_owned(),
+                    relative: true,
+                    object: ObjectData::Group(DatasetData {
+                        metadata: vec![],
+                        members: vec![
+                            array(
+                                ArrayType::Dense,
+                                true,
+                                "dense",
+                                vec![metadata("rows", 4)],
+                            ),
+                            array(ArrayType::Sparse, false, "sparse", vec![]),
+                        ],
+                    }),
+                },
+                MemberData {
+                    name: "notes".to_owned(),
+                    relative: false,
+                    object: ObjectData::Group(DatasetData::default()),
+                },
+            ],
+        }
+    }
+
+    /// Sorts the members of `dataset` by name, recursively.
+    fn sorted(mut dataset: DatasetData) -> DatasetData {
+        dataset.members.sort_by(|l, r| l.name.cmp(&r.name));
+        for member in dataset.members.iter_mut() {
+            if let ObjectData::Group(ref mut group) = member.object {
+                *group = sorted(std::mem::take(group));
+            }
+        }
+        dataset
+    }
+
+    fn read_dataset(context: &Context, uri: &str) -> TileDBResult<DatasetData> {
+        let group = Group::open(context, uri, QueryType::Read, None)?;
+        DatasetData::try_from(&group).map(sorted)
+    }
+
+    #[test]
+    fn create_and_read() -> anyhow::Result<()> {
+        let context = Context::new()?;
+        let test_dir = TestDirectory::new()?;
+        let uri = test_dir.with_path("dataset")?;
+
+        let expect = example_dataset();
+        create_dataset(&context, &uri, &expect)?;
+
+        let dataset = read_dataset(&context, &uri)?;
+        assert_eq!(expect.metadata, dataset.metadata);
+        assert_eq!(
+            vec![("arrays", true), ("notes", false)],
+            dataset
+                .members
+                .iter()
+                .map(|m| (m.name.as_ref(), m.relative))
+                .collect::<Vec<_>>()
+        );
+        let ObjectData::Group(ref arrays) = dataset.members[0].object else {
+            unreachable!("Expected group: {:?}", dataset.members[0])
+        };
+        assert_eq!(
+            vec![("dense", true), ("sparse", false)],
+            arrays
+                .members
+                .iter()
+                .map(|m| (m.name.as_ref(), m.relative))
+                .collect::<Vec<_>>()
+        );
+        let ObjectData::Array(ref dense) = arrays.members[0].object else {
+            unreachable!("Expected array: {:?}", arrays.members[0])
+        };
+        assert_eq!(vec![metadata("rows", 4)], dense.metadata);
+        assert_eq!(ArrayType::Dense, dense.schema.array_type);
+
+        // creating again changes nothing
+        create_dataset(&context, &uri, &expect)?;
+        assert_eq!(dataset, read_dataset(&context, &uri)?);
+        assert_eq!(
+            2,
+            Group::open(&context, &uri, QueryType::Read, None)?
+                .num_members()?
+        );
+
+        // new members and metadata are added to the existing dataset
+        let mut more = expect.clone();
+        more.metadata = vec![metadata("version", 2)];
+        more.members.push(MemberData {
+            name: "extra".to_owned(),
+            relative: true,
+            object: ObjectData::Group(DatasetData::default()),
+        });
+        create_dataset(&context, &uri, &more)?;
+        let updated = read_dataset(&context, &uri)?;
+        assert_eq!(more.metadata, updated.metadata);
+        assert_eq!(3, updated.members.len());
+
+        // the extracted dataset recreates the same dataset elsewhere
+        let copy = test_dir.with_path("copy")?;
+        create_dataset(&context, &copy, &updated)?;
+        assert_eq!(updated, read_dataset(&context, &copy)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unnamed_member() -> anyhow::Result<()> {
+        let context = Context::new()?;
+        let test_dir = TestDirectory::new()?;
+        let uri = test_dir.with_path("dataset")?;
+        let table = test_dir.with_path("table")?;
+
+        create_dataset(&context, &uri, &DatasetData::default())?;
+        Array::create(
+            &context,
+            &table,
+            quickstart::Builder::new(ArrayType::Sparse)
+                .build()
+                .create(&context)?,
+        )?;
+        {
+            let mut group =
+                Group::open(&context, &uri, QueryType::Write, None)?;
+            group.add_member(&table, false, None::<&str>)?;
+        }
+
+        assert!(matches!(
+            read_dataset(&context, &uri),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        // the unnamed member does not stand in for a member named "table"
+        let dataset = DatasetData {
+            metadata: vec![],
+            members: vec![MemberData {
+                name: "table".to_owned(),
+                relative: true,
+                object: ObjectData::Array(ArrayData {
+                    schema: quickstart::Builder::new(ArrayType::Sparse).build(),
+                    metadata: vec![],
+                }),
+            }],
+        };
+        create_dataset(&context, &uri, &dataset)?;
+
+        let group = Group::open(&context, &uri, QueryType::Read, None)?;
+        assert_eq!(2, group.num_members()?);
+        let member = group.member(LookupKey::Name("table".to_owned()))?;
+        assert_eq!(
+            canonical_uri(&test_dir.with_paths(&["dataset", "table"])?),
+            canonical_uri(&member.uri)
+        );
+        assert!(group.is_relative_uri("table")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() -> anyhow::Result<()> {
+        let context = Context::new()?;
+        let test_dir = TestDirectory::new()?;
+        let uri = test_dir.with_path("dataset")?;
+
+        let mut duplicate = example_dataset();
+        duplicate.members.push(duplicate.members[1].clone());
+        assert!(matches!(
+            create_dataset(&context, &uri, &duplicate),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        let mut bad_name = example_dataset();
+        bad_name.members[1].name = "a/b".to_owned();
+        assert!(matches!(
+            create_dataset(&context, &uri, &bad_name),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        // a group is in the way of an array
+        create_dataset(&context, &uri, &example_dataset())?;
+        let mut conflict = example_dataset();
+        conflict.members[1].object = ObjectData::Array(ArrayData {
+            schema: quickstart::Builder::new(ArrayType::Sparse).build(),
+            metadata: vec![],
+        });
+        assert!(matches!(
+            create_dataset(&context, &uri, &conflict),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        // a cycle cannot be described
+        let cycle = test_dir.with_path("cycle")?;
+        create_dataset(&context, &cycle, &example_dataset())?;
+        {
+            let notes = member_uri(&cycle, "notes")?;
+            let mut group =
+                Group::open(&context, &notes, QueryType::Write, None)?;
+            group.add_member(&cycle, false, Some("back"))?;
+        }
+        assert!(matches!(
+            read_dataset(&context, &cycle),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,334 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Group, QueryType};
+use crate::config::Config;
+use crate::context::ObjectType;
+use crate::metadata::Metadata;
+use crate::Result as TileDBResult;
+
+/// The recursive contents of a [Group], returned by [Group::tree].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum GroupTree {
+    /// A group, with its metadata and members.
+    Group {
+        name: Option<String>,
+        uri: String,
+        relative: Option<bool>,
+        metadata: Vec<Metadata>,
+        members: Vec<GroupTree>,
+    },
+    /// An array which is a member of a group.
+    Array {
+        name: Option<String>,
+        uri: String,
+        relative: Option<bool>,
+    },
+    /// A member group which contains the group it is a member of,
+    /// directly or through other groups. Its contents are not listed again.
+    Cycle {
+        name: Option<String>,
+        uri: String,
+        relative: Option<bool>,
+    },
+}
+
+impl GroupTree {
+    /// Returns the name of this node within its parent group.
+    /// This is `None` for the root of the tree,
+    /// and for a member which was added without a name.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Self::Group { name, .. }
+            | Self::Array { name, .. }
+            | Self::Cycle { name, .. } => name.as_deref(),
+        }
+    }
+
+    /// Returns the URI of this node.
+    pub fn uri(&self) -> &str {
+        match self {
+            Self::Group { uri, .. }
+            | Self::Array { uri, .. }
+            | Self::Cycle { uri, .. } => uri,
+        }
+    }
+
+    /// Returns whether this node's URI is stored relative to its parent group.
+    /// This is `None` for the root of the tree, and for a member which was
+    /// added without a name, since libtiledb only reports whether a member
+    /// is relative by its name.
+    pub fn is_relative(&self) -> Option<bool> {
+        match self {
+            Self::Group { relative, .. }
+            | Self::Array { relative, .. }
+            | Self::Cycle { relative, .. } => *relative,
+        }
+    }
+
+    /// Returns the members of this node if it is a group,
+    /// and an empty slice otherwise.
+    pub fn members(&self) -> &[GroupTree] {
+        match self {
+            Self::Group { members, .. } => members,
+            Self::Array { .. } | Self::Cycle { .. } => &[],
+        }
+    }
+}
+
+/// Returns a key which identifies the object at `uri`, so that
+/// local paths and `file://` URIs of the same group compare equal.
+pub(super) fn canonical_uri(uri: &str) -> String {
+    let uri = uri.trim_end_matches('/');
+    if uri.contains("://") {
+        uri.to_owned()
+    } else {
+        let path = std::path::absolute(uri)
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| uri.to_owned());
+        format!("file://{}", path)
+    }
+}
+
+impl Group {
+    /// Returns the contents of this group, recursively opening each member
+    /// group for reading with the configuration of this group.
+    ///
+    /// A member group which is also an ancestor of its parent is reported as
+    /// [GroupTree::Cycle]. A group which is a member of more than one group
+    /// without forming a cycle appears once for each of them.
+    ///
+    /// This group must be open for reading.
+    pub fn tree(&self) -> TileDBResult<GroupTree> {
+        let uri = self.uri()?;
+        let config = self.config()?;
+        let mut ancestors = vec![canonical_uri(&uri)];
+        self.subtree(None, uri, None, &config, &mut ancestors)
+    }
+
+    fn subtree(
+        &self,
+        name: Option<String>,
+        uri: String,
+        relative: Option<bool>,
+        config: &Config,
+        ancestors: &mut Vec<String>,
+    ) -> TileDBResult<GroupTree> {
+        let metadata = self.metadata_iter()?.collect::<TileDBResult<_>>()?;
+
+        let mut members = vec![];
+        for member in self.members()? {
+            let member = member?;
+            let relative = member
+                .name
+                .as_ref()
+                .map(|name| self.is_relative_uri(name))
+                .transpose()?;
+            let node = match member.group_type {
+                ObjectType::Array => GroupTree::Array {
+                    name: member.name,
+                    uri: member.uri,
+                    relative,
+                },
+                ObjectType::Group => {
+                    let key = canonical_uri(&member.uri);
+                    if ancestors.contains(&key) {
+                        GroupTree::Cycle {
+                            name: member.name,
+                            uri: member.uri,
+                            relative,
+                        }
+                    } else {
+                        let group = Group::open(
+                            &self.context,
+                            &member.uri,
+                            QueryType::Read,
+                            Some(config),
+                        )?;
+                        ancestors.push(key);
+                        let subtree = group.subtree(
+                            member.name,
+                            member.uri,
+                            relative,
+                            config,
+                            ancestors,
+                        );
+                        ancestors.pop();
+                        subtree?
+                    }
+                }
+            };
+            members.push(node);
+        }
+
+        Ok(GroupTree::Group {
+            name,
+            uri,
+            relative,
+            metadata,
+            members,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tiledb_common::array::ArrayType;
+    use uri::TestDirectory;
+
+    use super::*;
+    use crate::array::Array;
+    use crate::context::Context;
+    use crate::datatype::Datatype;
+    use crate::metadata::Value;
+    use crate::tests::examples::quickstart;
+    use crate::Factory;
+
+    /// Creates a group `root` in `test_dir`:
+    /// ```text
+    /// root          (metadata "version" = 1)
+    /// |-- a         (relative array)
+    /// |-- sub       (relative group)
+    ///     |-- b     (absolute array outside of `root`)
+    ///     |-- back  (absolute member `root`)
+    ///     |-- c     (unnamed absolute array outside of `root`)
+    /// ```
+    fn create_groups(
+        context: &Context,
+        test_dir: &TestDirectory,
+    ) -> anyhow::Result<String> {
+        let root = test_dir.with_path("root")?;
+        let sub = test_dir.with_paths(&["root", "sub"])?;
+        let a = test_dir.with_paths(&["root", "a"])?;
+        let b = test_dir.with_path("b")?;
+        let c = test_dir.with_path("c")?;
+
+        Group::create(context, &root)?;
+        Group::create(context, &sub)?;
+        let schema = quickstart::Builder::new(ArrayType::Sparse).build();
+        Array::create(context, &a, schema.create(context)?)?;
+        Array::create(context, &b, schema.create(context)?)?;
+        Array::create(context, &c, schema.create(context)?)?;
+
+        {
+            let mut g = Group::open(context, &root, QueryType::Write, None)?;
+            g.add_member("a", true, Some("a"))?;
+            g.add_member("sub", true, Some("sub"))?;
+            g.put_metadata(Metadata::new(
+                "version".to_owned(),
+                Datatype::Int32,
+                vec![1i32],
+            )?)?;
+        }
+        {
+            let mut g = Group::open(context, &sub, QueryType::Write, None)?;
+            g.add_member(&b, false, Some("b"))?;
+            g.add_member(&root, false, Some("back"))?;
+            g.add_member(&c, false, None::<&str>)?;
+        }
+
+        Ok(root)
+    }
+
+    #[test]
+    fn members() -> anyhow::Result<()> {
+        let context = Context::new()?;
+        let test_dir = TestDirectory::new()?;
+        let root = create_groups(&context, &test_dir)?;
+
+        let group = Group::open(&context, &root, QueryType::Read, None)?;
+        let members = group.members()?;
+        assert_eq!(2, members.len());
+
+        let mut members = members.collect::<TileDBResult<Vec<_>>>()?;
+        members.sort_by(|l, r| l.name.cmp(&r.name));
+        assert_eq!(
+            vec![
+                (Some("a"), ObjectType::Array),
+                (Some("sub"), ObjectType::Group)
+            ],
+            members
+                .iter()
+                .map(|m| (m.name.as_deref(), m.group_type))
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn tree() -> anyhow::Result<()> {
+        let context = Context::new()?;
+        let test_dir = TestDirectory::new()?;
+        let root = create_groups(&context, &test_dir)?;
+
+        let tree =
+            Group::open(&context, &root, QueryType::Read, None)?.tree()?;
+
+        let GroupTree::Group {
+            name,
+            relative,
+            metadata,
+            members,
+            ..
+        } = &tree
+        else {
+            unreachable!("Expected group: {:?}", tree)
+        };
+        assert_eq!(&None, name);
+        assert_eq!(&None, relative);
+        assert_eq!(1, metadata.len());
+        assert_eq!("version", metadata[0].key);
+        assert_eq!(Value::Int32Value(vec![1]), metadata[0].value);
+
+        let mut members = members.iter().collect::<Vec<_>>();
+        members.sort_by_key(|m| m.name());
+        assert_eq!(2, members.len());
+
+        assert!(matches!(members[0], GroupTree::Array { .. }));
+        assert_eq!(Some("a"), members[0].name());
+        assert_eq!(Some(true), members[0].is_relative());
+
+        let sub = members[1];
+        assert!(matches!(sub, GroupTree::Group { .. }));
+        assert_eq!(Some("sub"), sub.name());
+        assert_eq!(Some(true), sub.is_relative());
+
+        let mut sub_members = sub.members().iter().collect::<Vec<_>>();
+        sub_members.sort_by_key(|m| m.name());
+        assert_eq!(3, sub_members.len());
+
+        // the unnamed member sorts first, and libtiledb cannot tell
+        // whether it is relative
+        assert!(matches!(sub_members[0], GroupTree::Array { .. }));
+        assert_eq!(None, sub_members[0].name());
+        assert_eq!(None, sub_members[0].is_relative());
+        assert!(sub_members[0].uri().ends_with("/c"));
+
+        assert!(matches!(sub_members[1], GroupTree::Array { .. }));
+        assert_eq!(Some("b"), sub_members[1].name());
+        assert_eq!(Some(false), sub_members[1].is_relative());
+
+        assert!(matches!(sub_members[2], GroupTree::Cycle { .. }));
+        assert_eq!(Some("back"), sub_members[2].name());
+        assert_eq!(canonical_uri(&root), canonical_uri(sub_members[2].uri()));
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_value(&tree)?;
+            assert_eq!("group", json["type"]);
+            assert!(json["name"].is_null());
+            assert_eq!(tree, serde_json::from_value::<GroupTree>(json)?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn canonical() {
+        assert_eq!("file:///tmp/g", canonical_uri("file:///tmp/g/"));
+        assert_eq!("file:///tmp/g", canonical_uri("/tmp/g"));
+        assert_eq!("s3://bucket/g", canonical_uri("s3://bucket/g"));
+    }
+}
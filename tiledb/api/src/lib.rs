@@ -46,6 +46,7 @@ pub mod filesystem;
 pub mod filter;
 pub mod group;
 pub mod metadata;
+pub mod object;
 pub mod query;
 pub mod stats;
 pub mod string;
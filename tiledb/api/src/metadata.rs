@@ -1,6 +1,11 @@
+use std::collections::BTreeMap;
+
 use tiledb_common::datatype::Datatype;
 use tiledb_common::physical_type_go;
 
+use crate::group::Group;
+use crate::{Array, Result as TileDBResult};
+
 pub use tiledb_common::metadata::*;
 pub use tiledb_common::metadata_value_go;
 
@@ -20,7 +25,7 @@ pub(crate) fn metadata_from_ffi(
     key: String,
     datatype: Datatype,
     ffi: (u32, *const std::ffi::c_void),
-) -> Metadata {
+) -> TileDBResult<Metadata> {
     let value = physical_type_go!(datatype, DT, {
         let slice = {
             let vec_ptr = if ffi.0 == 0 {
@@ -36,9 +41,86 @@ pub(crate) fn metadata_from_ffi(
         Value::from(slice.to_vec())
     });
 
-    Metadata {
+    Ok(Metadata {
         key,
         datatype,
-        value,
+        value: value.conform(datatype)?,
+    })
+}
+
+enum MetadataSource<'a> {
+    Array(&'a Array),
+    Group(&'a Group),
+}
+
+/// Iterates over all of the metadata of an [Array] or [Group]
+/// in order of key.
+///
+/// The metadata can be copied from one object to another:
+///
+/// ```no_run
+/// # use tiledb_api::{Array, Context, Result as TileDBResult};
+/// # use tiledb_api::array::Mode;
+/// # fn copy(ctx: &Context, src: &str, dst: &str) -> TileDBResult<()> {
+/// let metadata = Array::open(ctx, src, Mode::Read)?
+///     .metadata_iter()?
+///     .collect::<TileDBResult<Vec<_>>>()?;
+/// Array::open(ctx, dst, Mode::Write)?.put_metadata_all(metadata)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct MetadataIter<'a> {
+    source: MetadataSource<'a>,
+    cursor: u64,
+    bound: u64,
+}
+
+impl<'a> MetadataIter<'a> {
+    pub(crate) fn for_array(array: &'a Array) -> TileDBResult<Self> {
+        Ok(MetadataIter {
+            source: MetadataSource::Array(array),
+            cursor: 0,
+            bound: array.num_metadata()?,
+        })
+    }
+
+    pub(crate) fn for_group(group: &'a Group) -> TileDBResult<Self> {
+        Ok(MetadataIter {
+            source: MetadataSource::Group(group),
+            cursor: 0,
+            bound: group.num_metadata()?,
+        })
+    }
+
+    /// Collects the remaining metadata into a map from key to value.
+    pub fn into_map(self) -> TileDBResult<BTreeMap<String, Value>> {
+        self.map(|m| m.map(|m| (m.key, m.value))).collect()
+    }
+}
+
+impl Iterator for MetadataIter<'_> {
+    type Item = TileDBResult<Metadata>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cursor < self.bound {
+            let index = self.cursor as usize;
+            let item = match self.source {
+                MetadataSource::Array(array) => array.metadata(index),
+                MetadataSource::Group(group) => group.metadata(index),
+            };
+            self.cursor += 1;
+            Some(item)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let exact = (self.bound - self.cursor) as usize;
+        (exact, Some(exact))
     }
 }
+
+impl std::iter::FusedIterator for MetadataIter<'_> {}
+
+impl ExactSizeIterator for MetadataIter<'_> {}
@@ -0,0 +1,317 @@
+//! Discovers and manages the TileDB objects (arrays and groups) under a URI.
+//!
+//! Unlike [VFS](crate::vfs::VFS) listings, these functions only report
+//! resources which libtiledb recognizes as arrays or groups.
+
+use anyhow::anyhow;
+
+use crate::context::{CApiResult, Context, ObjectType};
+use crate::error::Error;
+use crate::Result as TileDBResult;
+
+/// Order in which [walk] visits a group and its contents.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WalkOrder {
+    /// Visits each group before its contents.
+    PreOrder,
+    /// Visits each group after its contents.
+    PostOrder,
+}
+
+impl From<WalkOrder> for ffi::tiledb_walk_order_t {
+    fn from(value: WalkOrder) -> Self {
+        match value {
+            WalkOrder::PreOrder => ffi::tiledb_walk_order_t_TILEDB_PREORDER,
+            WalkOrder::PostOrder => ffi::tiledb_walk_order_t_TILEDB_POSTORDER,
+        }
+    }
+}
+
+/// Returns the URI and type of each TileDB object directly within `uri`.
+pub fn ls<S>(
+    context: &Context,
+    uri: S,
+) -> TileDBResult<Vec<(String, ObjectType)>>
+where
+    S: AsRef<str>,
+{
+    let c_uri = cstring!(uri.as_ref());
+    let mut objects = ObjectCollector::default();
+    let c_objects = &mut objects as *mut ObjectCollector;
+
+    let result = context.capi_call(|ctx| unsafe {
+        ffi::tiledb_object_ls(
+            ctx,
+            c_uri.as_ptr(),
+            Some(object_cb_handler),
+            c_objects as *mut std::ffi::c_void,
+        )
+    });
+    objects.finish(result)
+}
+
+/// Returns the URI and type of each TileDB object within `uri`,
+/// recursively visiting the contents of each group in the given `order`.
+pub fn walk<S>(
+    context: &Context,
+    uri: S,
+    order: WalkOrder,
+) -> TileDBResult<Vec<(String, ObjectType)>>
+where
+    S: AsRef<str>,
+{
+    let c_uri = cstring!(uri.as_ref());
+    let c_order = ffi::tiledb_walk_order_t::from(order);
+    let mut objects = ObjectCollector::default();
+    let c_objects = &mut objects as *mut ObjectCollector;
+
+    let result = context.capi_call(|ctx| unsafe {
+        ffi::tiledb_object_walk(
+            ctx,
+            c_uri.as_ptr(),
+            c_order,
+            Some(object_cb_handler),
+            c_objects as *mut std::ffi::c_void,
+        )
+    });
+    objects.finish(result)
+}
+
+/// Returns the type of the object at `uri`, or an error if there
+/// is no TileDB object there.
+fn require_object(context: &Context, uri: &str) -> TileDBResult<ObjectType> {
+    context.object_type(uri)?.ok_or_else(|| {
+        Error::InvalidArgument(anyhow!("No TileDB object found at '{}'", uri))
+    })
+}
+
+/// Moves the array or group at `old_uri` to `new_uri`,
+/// returning the type of the object which was moved.
+pub fn move_object<S, T>(
+    context: &Context,
+    old_uri: S,
+    new_uri: T,
+) -> TileDBResult<ObjectType>
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    let object_type = require_object(context, old_uri.as_ref())?;
+
+    let c_old_uri = cstring!(old_uri.as_ref());
+    let c_new_uri = cstring!(new_uri.as_ref());
+    context.capi_call(|ctx| unsafe {
+        ffi::tiledb_object_move(ctx, c_old_uri.as_ptr(), c_new_uri.as_ptr())
+    })?;
+
+    Ok(object_type)
+}
+
+/// Removes the array or group at `uri`, including all of its contents,
+/// returning the type of the object which was removed.
+pub fn remove_object<S>(context: &Context, uri: S) -> TileDBResult<ObjectType>
+where
+    S: AsRef<str>,
+{
+    let object_type = require_object(context, uri.as_ref())?;
+
+    let c_uri = cstring!(uri.as_ref());
+    context.capi_call(|ctx| unsafe {
+        ffi::tiledb_object_remove(ctx, c_uri.as_ptr())
+    })?;
+
+    Ok(object_type)
+}
+
+/// Accumulates the objects passed to [object_cb_handler].
+#[derive(Default)]
+struct ObjectCollector {
+    objects: Vec<(String, ObjectType)>,
+    /// The first error encountered by the callback, which stops the
+    /// iteration and takes precedence over the error which libtiledb returns.
+    error: Option<Error>,
+}
+
+impl ObjectCollector {
+    fn finish(
+        self,
+        result: CApiResult<()>,
+    ) -> TileDBResult<Vec<(String, ObjectType)>> {
+        if let Some(e) = self.error {
+            Err(e)
+        } else {
+            result?;
+            Ok(self.objects)
+        }
+    }
+}
+
+extern "C" fn object_cb_handler(
+    path: *const ::std::os::raw::c_char,
+    object_type: ffi::tiledb_object_t,
+    data: *mut ::std::os::raw::c_void,
+) -> std::ffi::c_int {
+    // SAFETY: `data` is the `ObjectCollector` passed by `ls` or `walk`,
+    // which outlives the libtiledb call
+    let collector = unsafe { &mut *(data as *mut ObjectCollector) };
+
+    let c_path = unsafe { std::ffi::CStr::from_ptr(path) };
+    let path = match c_path.to_str() {
+        Ok(path) => path.to_owned(),
+        Err(e) => {
+            collector.error = Some(Error::Other(format!(
+                "Object URI is not valid UTF-8: {}",
+                e
+            )));
+            return -1;
+        }
+    };
+
+    match ObjectType::from_capi(object_type) {
+        Ok(Some(object_type)) => {
+            collector.objects.push((path, object_type));
+            1
+        }
+        Ok(None) => 1,
+        Err(e) => {
+            collector.error = Some(e.into());
+            -1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tiledb_common::array::ArrayType;
+    use uri::TestDirectory;
+
+    use super::*;
+    use crate::array::Array;
+    use crate::group::Group;
+    use crate::tests::examples::quickstart;
+    use crate::Factory;
+
+    fn create_array(context: &Context, uri: &str) -> TileDBResult<()> {
+        let schema = quickstart::Builder::new(ArrayType::Sparse)
+            .build()
+            .create(context)?;
+        Array::create(context, uri, schema)
+    }
+
+    /// Creates objects in `test_dir`:
+    /// ```text
+    /// a1
+    /// g1/
+    /// g1/a2
+    /// g1/g2/
+    /// g1/g2/a3
+    /// ```
+    fn create_objects(
+        context: &Context,
+        test_dir: &TestDirectory,
+    ) -> anyhow::Result<()> {
+        create_array(context, &test_dir.with_path("a1")?)?;
+        Group::create(context, test_dir.with_path("g1")?)?;
+        create_array(context, &test_dir.with_paths(&["g1", "a2"])?)?;
+        Group::create(context, test_dir.with_paths(&["g1", "g2"])?)?;
+        create_array(context, &test_dir.with_paths(&["g1", "g2", "a3"])?)?;
+        Ok(())
+    }
+
+    /// Returns the path of each object relative to `root`.
+    fn relative(
+        root: &str,
+        objects: Vec<(String, ObjectType)>,
+    ) -> Vec<(String, ObjectType)> {
+        objects
+            .into_iter()
+            .map(|(uri, object_type)| {
+                let uri = uri.trim_end_matches('/');
+                let start = uri.find(root).unwrap() + root.len() + 1;
+                (uri[start..].to_owned(), object_type)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ls_and_walk() -> anyhow::Result<()> {
+        let context = Context::new()?;
+        let test_dir = TestDirectory::new()?;
+        create_objects(&context, &test_dir)?;
+        let root = test_dir.base_dir()?;
+
+        let mut objects = relative(&root, ls(&context, &root)?);
+        objects.sort_by(|l, r| l.0.cmp(&r.0));
+        assert_eq!(
+            vec![
+                ("a1".to_owned(), ObjectType::Array),
+                ("g1".to_owned(), ObjectType::Group)
+            ],
+            objects
+        );
+
+        let expect = vec![
+            ("a1".to_owned(), ObjectType::Array),
+            ("g1".to_owned(), ObjectType::Group),
+            ("g1/a2".to_owned(), ObjectType::Array),
+            ("g1/g2".to_owned(), ObjectType::Group),
+            ("g1/g2/a3".to_owned(), ObjectType::Array),
+        ];
+        for order in [WalkOrder::PreOrder, WalkOrder::PostOrder] {
+            let objects = relative(&root, walk(&context, &root, order)?);
+
+            let mut sorted = objects.clone();
+            sorted.sort_by(|l, r| l.0.cmp(&r.0));
+            assert_eq!(expect, sorted);
+
+            let position =
+                |path: &str| objects.iter().position(|o| o.0 == path).unwrap();
+            for (group, member) in
+                [("g1", "g1/a2"), ("g1", "g1/g2"), ("g1/g2", "g1/g2/a3")]
+            {
+                if order == WalkOrder::PreOrder {
+                    assert!(position(group) < position(member));
+                } else {
+                    assert!(position(group) > position(member));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn move_and_remove() -> anyhow::Result<()> {
+        let context = Context::new()?;
+        let test_dir = TestDirectory::new()?;
+        create_objects(&context, &test_dir)?;
+
+        let g1 = test_dir.with_path("g1")?;
+        let g3 = test_dir.with_path("g3")?;
+        assert_eq!(ObjectType::Group, move_object(&context, &g1, &g3)?);
+        assert_eq!(None, context.object_type(&g1)?);
+        assert_eq!(Some(ObjectType::Group), context.object_type(&g3)?);
+        assert_eq!(
+            Some(ObjectType::Array),
+            context.object_type(test_dir.with_paths(&["g3", "g2", "a3"])?)?
+        );
+
+        let a1 = test_dir.with_path("a1")?;
+        assert_eq!(ObjectType::Array, remove_object(&context, &a1)?);
+        assert_eq!(None, context.object_type(&a1)?);
+
+        assert_eq!(ObjectType::Group, remove_object(&context, &g3)?);
+        assert!(ls(&context, test_dir.base_dir()?)?.is_empty());
+
+        assert!(matches!(
+            remove_object(&context, &a1),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            move_object(&context, &g1, &a1),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        Ok(())
+    }
+}
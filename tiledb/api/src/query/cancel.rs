@@ -0,0 +1,267 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A flag which is shared between a query and the code which may cancel it.
+///
+/// A query checks its token before each step. After the token is cancelled,
+/// the next step returns [Error::Cancelled] without submitting the query.
+/// A step which is already running is not interrupted, but can be aborted
+/// using [Context::cancel_tasks].
+///
+/// Cancelling a query does not affect its array, which may be
+/// recovered using [Query::finalize] and used for other queries.
+///
+/// ```no_run
+/// # use tiledb_api::{Array, Context, Result as TileDBResult};
+/// # use tiledb_api::array::Mode;
+/// # use tiledb_api::query::{
+/// #     CancellationToken, QueryBuilder, ReadBuilder, ReadQuery,
+/// #     ReadQueryBuilder,
+/// # };
+/// # fn read(ctx: &Context, uri: &str) -> TileDBResult<Vec<i32>> {
+/// let token = CancellationToken::new();
+///
+/// // e.g. when the client disconnects
+/// let on_disconnect = token.clone();
+/// std::thread::spawn(move || on_disconnect.cancel());
+///
+/// let mut q = ReadBuilder::new(Array::open(ctx, uri, Mode::Read)?)?
+///     .cancellation_token(token)
+///     .register_constructor::<_, Vec<i32>>("a", Default::default())?
+///     .build();
+/// let (a, ()) = q.execute()?;
+/// # Ok(a)
+/// # }
+/// ```
+///
+/// [Error::Cancelled]: crate::error::Error::Cancelled
+/// [Context::cancel_tasks]: crate::Context::cancel_tasks
+/// [Query::finalize]: crate::query::Query::finalize
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation of all queries which share this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tiledb_common::array::ArrayType;
+
+    use super::*;
+    use crate::error::Error;
+    use crate::query::read::output::NonVarSized;
+    use crate::query::read::ScratchStrategy;
+    use crate::query::{
+        Query, QueryBuilder, ReadBuilder, ReadQuery, ReadQueryBuilder,
+        ReadStepOutput, WriteBuilder,
+    };
+    use crate::tests::examples::{quickstart, TestArray};
+    use crate::Result as TileDBResult;
+
+    fn write_cells(
+        array: &mut TestArray,
+        token: Option<CancellationToken>,
+    ) -> TileDBResult<()> {
+        let rows = (1..=4).flat_map(|r| [r; 4]).collect::<Vec<i32>>();
+        let cols = (1..=4).cycle().take(16).collect::<Vec<i32>>();
+        let a = (0..16).collect::<Vec<i32>>();
+
+        let mut b = WriteBuilder::new(array.for_write()?)?
+            .data("rows", &rows)?
+            .data("cols", &cols)?
+            .data("a", &a)?;
+        if let Some(token) = token {
+            b = b.cancellation_token(token);
+        }
+        let q = b.build();
+        q.submit()?;
+        q.finalize()?;
+        Ok(())
+    }
+
+    fn read_cells(
+        array: &TestArray,
+        token: CancellationToken,
+    ) -> TileDBResult<Vec<i32>> {
+        let mut q = ReadBuilder::new(array.for_read()?)?
+            .cancellation_token(token)
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?
+            .build();
+        let (a, ()) = q.execute()?;
+        Ok(a)
+    }
+
+    #[test]
+    fn shared_token() {
+        let token = CancellationToken::new();
+        let other = token.clone();
+        assert!(!token.is_cancelled());
+        other.cancel();
+        assert!(token.is_cancelled());
+        assert!(!CancellationToken::default().is_cancelled());
+    }
+
+    #[test]
+    fn cancel_execute() -> TileDBResult<()> {
+        let mut array = TestArray::new(
+            "cancel_execute",
+            quickstart::Builder::new(ArrayType::Sparse).build().into(),
+        )?;
+        write_cells(&mut array, None)?;
+
+        let token = CancellationToken::new();
+        assert_eq!((0..16).collect::<Vec<i32>>(), read_cells(&array, token)?);
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(matches!(read_cells(&array, token), Err(Error::Cancelled)));
+
+        // the array is still usable afterwards
+        let token = CancellationToken::new();
+        assert_eq!((0..16).collect::<Vec<i32>>(), read_cells(&array, token)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn cancel_iterator() -> TileDBResult<()> {
+        let mut array = TestArray::new(
+            "cancel_iterator",
+            quickstart::Builder::new(ArrayType::Sparse).build().into(),
+        )?;
+        write_cells(&mut array, None)?;
+
+        let token = CancellationToken::new();
+        let q = ReadBuilder::new(array.for_read()?)?
+            .cancellation_token(token.clone())
+            .register_constructor::<_, Vec<i32>>(
+                "a",
+                ScratchStrategy::CustomAllocator(Box::new(NonVarSized {
+                    capacity: 4,
+                    ..Default::default()
+                })),
+            )?
+            .build();
+
+        let mut steps = q.into_iter();
+        assert!(matches!(
+            steps.next(),
+            Some(Ok(ReadStepOutput::Intermediate(_)))
+        ));
+
+        token.cancel();
+        assert!(matches!(steps.next(), Some(Err(Error::Cancelled))));
+        assert!(steps.next().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn cancel_reuse_array() -> TileDBResult<()> {
+        let mut array = TestArray::new(
+            "cancel_reuse_array",
+            quickstart::Builder::new(ArrayType::Sparse).build().into(),
+        )?;
+        write_cells(&mut array, None)?;
+
+        let token = CancellationToken::new();
+        let mut q = ReadBuilder::new(array.for_read()?)?
+            .cancellation_token(token.clone())
+            .register_constructor::<_, Vec<i32>>(
+                "a",
+                ScratchStrategy::CustomAllocator(Box::new(NonVarSized {
+                    capacity: 4,
+                    ..Default::default()
+                })),
+            )?
+            .build();
+        assert!(matches!(q.step()?, ReadStepOutput::Intermediate(_)));
+
+        token.cancel();
+        assert!(matches!(q.step(), Err(Error::Cancelled)));
+
+        // the same array runs another query to completion
+        let mut q = ReadBuilder::new(q.finalize()?)?
+            .cancellation_token(CancellationToken::new())
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?
+            .build();
+        let (a, ()) = q.execute()?;
+        assert_eq!((0..16).collect::<Vec<i32>>(), a);
+
+        Ok(())
+    }
+
+    #[test]
+    fn cancel_tasks() -> TileDBResult<()> {
+        let mut array = TestArray::new(
+            "cancel_tasks",
+            quickstart::Builder::new(ArrayType::Sparse).build().into(),
+        )?;
+        write_cells(&mut array, None)?;
+        let expect = (0..16).collect::<Vec<i32>>();
+
+        // there is nothing to cancel
+        array.context.cancel_tasks()?;
+        assert_eq!(expect, read_cells(&array, CancellationToken::new())?);
+
+        // the query either finishes first or is reported as cancelled
+        let token = CancellationToken::new();
+        let canceller = {
+            let token = token.clone();
+            let context = array.context.clone();
+            std::thread::spawn(move || {
+                token.cancel();
+                context.cancel_tasks()
+            })
+        };
+        let result = read_cells(&array, token);
+        canceller.join().unwrap()?;
+        match result {
+            Ok(a) => assert_eq!(expect, a),
+            Err(e) => assert!(matches!(e, Error::Cancelled), "{:?}", e),
+        }
+
+        // later queries on the context are not affected
+        assert_eq!(expect, read_cells(&array, CancellationToken::new())?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn cancel_write() -> TileDBResult<()> {
+        let mut array = TestArray::new(
+            "cancel_write",
+            quickstart::Builder::new(ArrayType::Sparse).build().into(),
+        )?;
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(matches!(
+            write_cells(&mut array, Some(token)),
+            Err(Error::Cancelled)
+        ));
+
+        // nothing was written
+        let token = CancellationToken::new();
+        assert!(read_cells(&array, token.clone())?.is_empty());
+
+        write_cells(&mut array, Some(token.clone()))?;
+        assert_eq!((0..16).collect::<Vec<i32>>(), read_cells(&array, token)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,366 @@
+use super::*;
+
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+
+use crate::array::ArrayType;
+use crate::vfs::{VFSLsStatus, VFS};
+
+/// Name of the array subdirectory which holds commit files.
+const COMMITS_DIR: &str = "__commits";
+
+/// File extension of the commit files written by delete queries.
+const DELETE_COMMIT_EXTENSION: &str = ".del";
+
+/// Describes the commit written by a [DeleteQuery].
+///
+/// libtiledb does not write a data fragment for a delete.
+/// Instead the condition is recorded in a commit file whose name has the
+/// same form as a fragment name, and is applied to all cells written at or
+/// before its timestamp whenever the array is read.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeleteCommit {
+    /// URI of the commit file.
+    pub uri: String,
+    /// Timestamp range of the commit.
+    /// The start and end are both the timestamp the array was opened at.
+    pub timestamp_range: [u64; 2],
+}
+
+/// A delete query removes all cells of a sparse array
+/// which satisfy a query condition.
+pub struct DeleteQuery {
+    base: QueryBase,
+    has_condition: bool,
+}
+
+impl ContextBound for DeleteQuery {
+    fn context(&self) -> Context {
+        self.base.context()
+    }
+}
+
+impl Query for DeleteQuery {
+    fn base(&self) -> &QueryBase {
+        self.base.base()
+    }
+
+    fn finalize(self) -> TileDBResult<Array> {
+        self.base.finalize()
+    }
+}
+
+impl DeleteQuery {
+    /// Runs the delete and returns a description of the commit it wrote.
+    ///
+    /// libtiledb does not report the commit written by a delete, so it is
+    /// found by listing the commits of the array before and after the delete.
+    /// Returns `None` if this cannot tell the commit apart from one which
+    /// another writer added with the same timestamp in the meantime.
+    ///
+    /// An error listing the commits after the delete has been submitted is
+    /// returned even though the delete has been applied.
+    pub fn submit(&self) -> TileDBResult<Option<DeleteCommit>> {
+        if !self.has_condition {
+            return Err(Error::InvalidArgument(anyhow!(
+                "Delete query requires a query condition"
+            )));
+        }
+
+        let vfs = VFS::new(&self.context(), &self.context().get_config()?)?;
+        let commits_uri =
+            format!("{}/{}", self.base.array().uri(), COMMITS_DIR);
+        // the commit is written at the end of the timestamp range
+        // which the array was opened at
+        let timestamp = self.base.array().open_timestamp_end()?;
+
+        let before = list_delete_commits(&vfs, &commits_uri)?;
+        self.base.do_submit()?;
+        let after = list_delete_commits(&vfs, &commits_uri)?;
+
+        let mut written = after
+            .difference(&before)
+            .filter_map(|uri| {
+                let timestamp_range = parse_timestamp_range(uri)?;
+                (timestamp_range[1] == timestamp).then(|| DeleteCommit {
+                    uri: uri.to_owned(),
+                    timestamp_range,
+                })
+            })
+            .collect::<Vec<_>>();
+        if written.len() == 1 {
+            Ok(written.pop())
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Builds a [DeleteQuery] against a sparse array opened in [Mode::Delete].
+///
+/// The cells to delete are selected using
+/// [query_condition](QueryBuilder::query_condition).
+/// Deleting all cells is not permitted, so the condition is required.
+///
+/// [Mode::Delete]: crate::array::Mode::Delete
+pub struct DeleteBuilder {
+    base: BuilderBase,
+    has_condition: bool,
+}
+
+impl ContextBound for DeleteBuilder {
+    fn context(&self) -> Context {
+        self.base.context()
+    }
+}
+
+impl QueryBuilder for DeleteBuilder {
+    type Query = DeleteQuery;
+
+    fn base(&self) -> &BuilderBase {
+        &self.base
+    }
+
+    fn query_condition(mut self, qc: QueryConditionExpr) -> TileDBResult<Self> {
+        self.base.set_query_condition(&qc)?;
+        self.has_condition = true;
+        Ok(self)
+    }
+
+    fn build(self) -> Self::Query {
+        DeleteQuery {
+            base: self.base.build(),
+            has_condition: self.has_condition,
+        }
+    }
+}
+
+impl BuilderBaseMut for DeleteBuilder {
+    fn base_mut(&mut self) -> &mut BuilderBase {
+        &mut self.base
+    }
+}
+
+impl DeleteBuilder {
+    pub fn new(array: Array) -> TileDBResult<Self> {
+        if array.schema()?.array_type()? == ArrayType::Dense {
+            return Err(Error::InvalidArgument(anyhow!(
+                "Cannot delete cells from dense array '{}'",
+                array.uri()
+            )));
+        }
+
+        Ok(DeleteBuilder {
+            base: BuilderBase::new(array, QueryType::Delete)?,
+            has_condition: false,
+        })
+    }
+}
+
+fn list_delete_commits(
+    vfs: &VFS,
+    commits_uri: &str,
+) -> TileDBResult<HashSet<String>> {
+    let mut commits = HashSet::new();
+    if !vfs.is_dir(commits_uri)? {
+        return Ok(commits);
+    }
+
+    vfs.ls(commits_uri, |uri| {
+        if uri.ends_with(DELETE_COMMIT_EXTENSION) {
+            commits.insert(uri.to_owned());
+        }
+        VFSLsStatus::Continue
+    })?;
+
+    Ok(commits)
+}
+
+/// Parses the timestamp range from a name of the form
+/// `__<start>_<end>_<uuid>_<version>`.
+fn parse_timestamp_range(uri: &str) -> Option<[u64; 2]> {
+    let name = uri.rsplit('/').next()?.strip_prefix("__")?;
+    let mut parts = name.split('_');
+    let start = parts.next()?.parse::<u64>().ok()?;
+    let end = parts.next()?.parse::<u64>().ok()?;
+    Some([start, end])
+}
+
+#[cfg(test)]
+mod tests {
+    use uri::{self, TestArrayUri};
+
+    use super::*;
+    use crate::array::{
+        ArrayOpener, AttributeBuilder, DimensionBuilder, DomainBuilder, Mode,
+        SchemaBuilder,
+    };
+    use crate::config::Config;
+    use crate::query::condition::QueryConditionExpr as QC;
+    use crate::query::{
+        ReadBuilder, ReadQuery, ReadQueryBuilder, WriteBuilder,
+    };
+    use crate::Datatype;
+
+    fn create_sparse(
+        test_uri: &dyn TestArrayUri,
+        ctx: &Context,
+    ) -> TileDBResult<String> {
+        let domain = {
+            let id = DimensionBuilder::new(
+                ctx,
+                "id",
+                Datatype::Int32,
+                ([0, 9], 10),
+            )?
+            .build();
+            DomainBuilder::new(ctx)?.add_dimension(id)?.build()
+        };
+        let schema = SchemaBuilder::new(ctx, ArrayType::Sparse, domain)?
+            .add_attribute(
+                AttributeBuilder::new(ctx, "a", Datatype::Int32)?.build(),
+            )?
+            .build()?;
+
+        let uri = test_uri
+            .with_path("delete")
+            .map_err(|e| Error::Other(e.to_string()))?;
+        Array::create(ctx, &uri, schema)?;
+        Ok(uri)
+    }
+
+    fn write_at(
+        ctx: &Context,
+        uri: &str,
+        timestamp: u64,
+        cells: &[(i32, i32)],
+    ) -> TileDBResult<()> {
+        let (id, a): (Vec<i32>, Vec<i32>) = cells.iter().copied().unzip();
+        let array = ArrayOpener::new(ctx, uri, Mode::Write)?
+            .end_timestamp(timestamp)?
+            .open()?;
+        let q = WriteBuilder::new(array)?
+            .data("id", &id)?
+            .data("a", &a)?
+            .build();
+        q.submit()?;
+        q.finalize()?;
+        Ok(())
+    }
+
+    fn delete_at(
+        ctx: &Context,
+        uri: &str,
+        timestamp: u64,
+        qc: QC,
+    ) -> TileDBResult<Option<DeleteCommit>> {
+        let array = ArrayOpener::new(ctx, uri, Mode::Delete)?
+            .end_timestamp(timestamp)?
+            .open()?;
+        let q = DeleteBuilder::new(array)?.query_condition(qc)?.build();
+        let commit = q.submit()?;
+        q.finalize()?;
+        Ok(commit)
+    }
+
+    fn read_at(
+        ctx: &Context,
+        uri: &str,
+        timestamp: u64,
+    ) -> TileDBResult<Vec<(i32, i32)>> {
+        let array = ArrayOpener::new(ctx, uri, Mode::Read)?
+            .end_timestamp(timestamp)?
+            .open()?;
+        let mut q = ReadBuilder::new(array)?
+            .layout(QueryLayout::RowMajor)?
+            .register_constructor::<_, Vec<i32>>("id", Default::default())?
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?
+            .build();
+        let (a, (id, ())) = q.execute()?;
+        Ok(id.into_iter().zip(a).collect())
+    }
+
+    #[test]
+    fn delete_timestamps() -> TileDBResult<()> {
+        let ctx = Context::new()?;
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let uri = create_sparse(&test_uri, &ctx)?;
+
+        let cells = (0..10).map(|i| (i, i * 10)).collect::<Vec<_>>();
+        write_at(&ctx, &uri, 1, &cells)?;
+
+        let commit = delete_at(&ctx, &uri, 2, QC::field("a").lt(50i32))?
+            .expect("Expected the delete commit to be found");
+        assert_eq!([2, 2], commit.timestamp_range);
+        assert!(commit.uri.ends_with(DELETE_COMMIT_EXTENSION));
+        let vfs = VFS::new(&ctx, &Config::new()?)?;
+        assert!(vfs.is_file(&commit.uri)?);
+
+        write_at(&ctx, &uri, 3, &[(0, 100), (1, 110)])?;
+
+        let commit = delete_at(&ctx, &uri, 4, QC::field("id").eq(9i32))?;
+        assert_eq!(Some([4, 4]), commit.map(|c| c.timestamp_range));
+
+        // before the delete
+        assert_eq!(cells, read_at(&ctx, &uri, 1)?);
+
+        // the delete applies to cells written at or before its timestamp
+        assert_eq!(cells[5..].to_vec(), read_at(&ctx, &uri, 2)?);
+
+        // cells written after the delete are not affected by it
+        let mut expect = vec![(0, 100), (1, 110)];
+        expect.extend_from_slice(&cells[5..]);
+        assert_eq!(expect, read_at(&ctx, &uri, 3)?);
+
+        expect.pop();
+        assert_eq!(expect, read_at(&ctx, &uri, 4)?);
+
+        test_uri.close().map_err(|e| Error::Other(e.to_string()))
+    }
+
+    #[test]
+    fn delete_invalid() -> TileDBResult<()> {
+        let ctx = Context::new()?;
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        // dense arrays are rejected
+        {
+            let uri =
+                crate::array::tests::create_quickstart_dense(&test_uri, &ctx)?;
+            let array = Array::open(&ctx, &uri, Mode::Delete)?;
+            assert!(matches!(
+                DeleteBuilder::new(array),
+                Err(Error::InvalidArgument(_))
+            ));
+        }
+
+        // the condition is required
+        {
+            let uri = create_sparse(&test_uri, &ctx)?;
+            let cells = (0..10).map(|i| (i, i * 10)).collect::<Vec<_>>();
+            write_at(&ctx, &uri, 1, &cells)?;
+
+            let array = Array::open(&ctx, &uri, Mode::Delete)?;
+            let q = DeleteBuilder::new(array)?.build();
+            assert!(matches!(q.submit(), Err(Error::InvalidArgument(_))));
+
+            assert_eq!(cells, read_at(&ctx, &uri, u64::MAX)?);
+        }
+
+        test_uri.close().map_err(|e| Error::Other(e.to_string()))
+    }
+
+    #[test]
+    fn timestamp_range_from_uri() {
+        assert_eq!(
+            Some([12, 34]),
+            parse_timestamp_range(
+                "file:///a/__commits/__12_34_0123456789abcdef_22.del"
+            )
+        );
+        assert_eq!(None, parse_timestamp_range("file:///a/__commits/foo.del"));
+    }
+}
@@ -2,21 +2,32 @@ use std::ops::Deref;
 
 use crate::context::{CApiInterface, Context, ContextBound};
 use crate::error::Error;
+use crate::stats::{Metrics, RawStatsString};
 use crate::{array::RawArray, Array, Result as TileDBResult};
 
 pub mod buffer;
+pub mod cancel;
 pub mod condition;
+pub mod delete;
+pub mod plan;
 pub mod read;
+pub mod record;
 pub mod subarray;
+pub mod update;
 pub mod write;
 
+pub use self::cancel::CancellationToken;
 pub use self::condition::QueryConditionExpr;
+pub use self::delete::{DeleteBuilder, DeleteQuery};
+pub use self::plan::{QueryPlan, QueryStrategy};
 pub use self::read::{
     ReadBuilder, ReadQuery, ReadQueryBuilder, ReadStepOutput, TypedReadBuilder,
 };
 pub use self::subarray::{Builder as SubarrayBuilder, Subarray};
-pub use self::write::{WriteBuilder, WriteQuery};
+pub use self::update::{UpdateBuilder, UpdateQuery};
+pub use self::write::{WriteBuilder, WriteQuery, WrittenFragment};
 
+use self::condition::BuildQueryCondition;
 use self::subarray::RawSubarray;
 
 pub type QueryType = crate::array::Mode;
@@ -59,6 +70,11 @@ impl Drop for RawQuery {
     }
 }
 
+// SAFETY: a query may be moved to another thread, but libtiledb does not
+// permit submitting the same query from multiple threads at once,
+// so `RawQuery` is deliberately not `Sync`.
+unsafe impl Send for RawQuery {}
+
 pub trait Query {
     fn base(&self) -> &QueryBase;
 
@@ -92,11 +108,36 @@ pub trait Query {
             RawSubarray::Owned(c_subarray),
         ))
     }
+
+    /// Returns the stats collected while running this query, as a JSON string.
+    fn stats_json(&self) -> TileDBResult<String> {
+        let c_query = *self.base().raw;
+        let mut c_json: *mut std::ffi::c_char = out_ptr!();
+        self.base().capi_call(|ctx| unsafe {
+            ffi::tiledb_query_get_stats(ctx, c_query, &mut c_json)
+        })?;
+
+        assert!(!c_json.is_null());
+        let raw = RawStatsString::Owned(c_json);
+        let json = unsafe { std::ffi::CStr::from_ptr(*raw) };
+        Ok(String::from(json.to_string_lossy()))
+    }
+
+    /// Returns the stats collected while running this query.
+    ///
+    /// Unlike [crate::stats::dump], these cover only this query,
+    /// which makes them useful for profiling an individual slow query.
+    /// The timers and counters are empty unless stats are enabled
+    /// using [crate::stats::enable].
+    fn stats(&self) -> TileDBResult<Metrics> {
+        Ok(crate::stats::parse_metrics(&self.stats_json()?)?)
+    }
 }
 
 pub struct QueryBase {
     array: Array,
     raw: RawQuery,
+    cancellation: Option<CancellationToken>,
 }
 
 impl ContextBound for QueryBase {
@@ -110,11 +151,37 @@ impl QueryBase {
         &self.raw
     }
 
+    /// Returns whether the cancellation token of this query, if any,
+    /// has been cancelled.
+    fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(|token| token.is_cancelled())
+    }
+
     /// Executes a single step of the query.
     fn do_submit(&self) -> TileDBResult<()> {
+        if self.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+
+        let c_query = **self.cquery();
+        self.capi_call(|ctx| unsafe { ffi::tiledb_query_submit(ctx, c_query) })
+            .map_err(|e| {
+                // the step may have been aborted by `Context::cancel_tasks`
+                if self.is_cancelled() {
+                    Error::Cancelled
+                } else {
+                    Error::from(e)
+                }
+            })
+    }
+
+    /// Flushes any buffered state of the query.
+    fn do_finalize(&self) -> TileDBResult<()> {
         let c_query = **self.cquery();
         self.capi_call(|ctx| unsafe {
-            ffi::tiledb_query_submit(ctx, c_query)
+            ffi::tiledb_query_finalize(ctx, c_query)
         })?;
         Ok(())
     }
@@ -140,11 +207,7 @@ impl Query for QueryBase {
     }
 
     fn finalize(self) -> TileDBResult<Array> {
-        let c_query = **self.base().cquery();
-        self.capi_call(|ctx| unsafe {
-            ffi::tiledb_query_finalize(ctx, c_query)
-        })?;
-
+        self.do_finalize()?;
         Ok(self.array)
     }
 }
@@ -193,6 +256,20 @@ impl ReadQuery for QueryBase {
     }
 }
 
+mod private {
+    use super::BuilderBase;
+
+    // Sealed so that settings which mutate the `BuilderBase` of a query
+    // builder do not add required methods to `QueryBuilder`.
+    // Builders outside of this crate cannot implement this trait,
+    // and so cannot use those settings.
+    pub trait BuilderBaseMut {
+        fn base_mut(&mut self) -> &mut BuilderBase;
+    }
+}
+
+pub(crate) use self::private::BuilderBaseMut;
+
 pub trait QueryBuilder: Sized {
     type Query: Query;
 
@@ -235,15 +312,20 @@ pub trait QueryBuilder: Sized {
     }
 
     fn query_condition(self, qc: QueryConditionExpr) -> TileDBResult<Self> {
-        let raw = qc.build(&self.base().context())?;
-        let c_query = **self.base().cquery();
-        let c_cond = *raw;
-        self.base().capi_call(|ctx| unsafe {
-            ffi::tiledb_query_set_condition(ctx, c_query, c_cond)
-        })?;
+        self.base().set_query_condition(&qc)?;
         Ok(self)
     }
 
+    /// Sets a token which can be used to cancel the query
+    /// in between its steps.
+    fn cancellation_token(mut self, token: CancellationToken) -> Self
+    where
+        Self: BuilderBaseMut,
+    {
+        self.base_mut().query.cancellation = Some(token);
+        self
+    }
+
     fn build(self) -> Self::Query;
 }
 
@@ -265,6 +347,16 @@ impl BuilderBase {
         &self.query.raw
     }
 
+    fn set_query_condition(&self, qc: &QueryConditionExpr) -> TileDBResult<()> {
+        let raw = qc.build(&self.context())?;
+        let c_query = **self.cquery();
+        let c_cond = *raw;
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_query_set_condition(ctx, c_query, c_cond)
+        })?;
+        Ok(())
+    }
+
     pub fn array(&self) -> &Array {
         &self.query.array
     }
@@ -282,6 +374,12 @@ impl QueryBuilder for BuilderBase {
     }
 }
 
+impl BuilderBaseMut for BuilderBase {
+    fn base_mut(&mut self) -> &mut BuilderBase {
+        self
+    }
+}
+
 impl BuilderBase {
     fn new(array: Array, query_type: QueryType) -> TileDBResult<Self> {
         let c_array = **array.capi();
@@ -295,6 +393,7 @@ impl BuilderBase {
             query: QueryBase {
                 array,
                 raw: RawQuery::Owned(c_query),
+                cancellation: None,
             },
         })
     }
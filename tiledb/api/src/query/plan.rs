@@ -0,0 +1,262 @@
+//! Describes how libtiledb will execute a query.
+//!
+//! A [QueryPlan] is requested from a query builder using
+//! [ReadQueryBuilder::explain](crate::query::ReadQueryBuilder::explain).
+
+use anyhow::anyhow;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::array::{Array, FragmentInfoBuilder};
+use crate::context::ContextBound;
+use crate::error::Error;
+use crate::query::subarray::Subarray;
+use crate::Result as TileDBResult;
+
+/// The algorithm which libtiledb uses to execute a query.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(from = "String", into = "String"))]
+pub enum QueryStrategy {
+    /// Reads cells of a dense array.
+    DenseReader,
+    /// Reads cells of a sparse array in global order.
+    SparseGlobalOrderReader,
+    /// Reads cells of a sparse array which allows duplicates,
+    /// in no particular order.
+    SparseUnorderedWithDupsReader,
+    /// Reads the ranges of a dimension label.
+    OrderedDimLabelReader,
+    /// Reads cells using the legacy reader.
+    Reader,
+    /// Any strategy not listed above.
+    Other(String),
+}
+
+impl From<String> for QueryStrategy {
+    fn from(value: String) -> Self {
+        match value.as_ref() {
+            "DenseReader" => Self::DenseReader,
+            "SparseGlobalOrderReader" => Self::SparseGlobalOrderReader,
+            "SparseUnorderedWithDupsReader" => {
+                Self::SparseUnorderedWithDupsReader
+            }
+            "OrderedDimLabelReader" => Self::OrderedDimLabelReader,
+            "Reader" => Self::Reader,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+impl From<QueryStrategy> for String {
+    fn from(value: QueryStrategy) -> Self {
+        match value {
+            QueryStrategy::DenseReader => "DenseReader".to_owned(),
+            QueryStrategy::SparseGlobalOrderReader => {
+                "SparseGlobalOrderReader".to_owned()
+            }
+            QueryStrategy::SparseUnorderedWithDupsReader => {
+                "SparseUnorderedWithDupsReader".to_owned()
+            }
+            QueryStrategy::OrderedDimLabelReader => {
+                "OrderedDimLabelReader".to_owned()
+            }
+            QueryStrategy::Reader => "Reader".to_owned(),
+            QueryStrategy::Other(s) => s,
+        }
+    }
+}
+
+/// The plan which libtiledb produces for a query.
+///
+/// The plan is determined by the array, the query layout, and the fields
+/// which the query reads.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct QueryPlan {
+    /// URI of the array which the query targets.
+    #[cfg_attr(feature = "serde", serde(rename = "Array.URI"))]
+    pub array_uri: String,
+    /// Type of the array, e.g. `dense` or `sparse`.
+    #[cfg_attr(feature = "serde", serde(rename = "Array.Type"))]
+    pub array_type: String,
+    /// Storage backend of the array URI, e.g. `file` or `s3`.
+    #[cfg_attr(feature = "serde", serde(rename = "VFS.Backend"))]
+    pub vfs_backend: String,
+    /// Order of the query results, e.g. `row-major` or `unordered`.
+    #[cfg_attr(feature = "serde", serde(rename = "Query.Layout"))]
+    pub layout: String,
+    /// The algorithm which libtiledb will use to execute the query.
+    #[cfg_attr(feature = "serde", serde(rename = "Query.Strategy.Name"))]
+    pub strategy: QueryStrategy,
+    /// Names of the attributes which the query reads.
+    #[cfg_attr(feature = "serde", serde(rename = "Query.Attributes"))]
+    pub attributes: Vec<String>,
+    /// Names of the dimensions which the query reads.
+    #[cfg_attr(feature = "serde", serde(rename = "Query.Dimensions"))]
+    pub dimensions: Vec<String>,
+    /// URIs of the fragments which the query will read, oldest first.
+    ///
+    /// These are the fragments which were written within the timestamp range
+    /// that the array is open at, and whose non-empty domain intersects the
+    /// subarray of the query. libtiledb does not report this itself, and may
+    /// skip some tiles of these fragments, for example when evaluating a
+    /// query condition.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fragments: Vec<String>,
+}
+
+/// Parses the JSON representation of a query plan produced by libtiledb.
+/// The fragments of the plan are left empty.
+pub(crate) fn parse_plan(json: &str) -> TileDBResult<QueryPlan> {
+    let error = |e: anyhow::Error| {
+        Error::Deserialization(String::from("query plan"), e)
+    };
+
+    let document =
+        serde_json::from_str::<Value>(json).map_err(|e| error(anyhow!(e)))?;
+    let Some(plan) = document.get("TileDB Query Plan") else {
+        return Err(error(anyhow!(
+            "Expected key 'TileDB Query Plan', found: {}",
+            document
+        )));
+    };
+
+    let string = |key: &str| {
+        plan.get(key)
+            .and_then(Value::as_str)
+            .map(str::to_owned)
+            .ok_or_else(|| error(anyhow!("Expected string for '{}'", key)))
+    };
+    let strings = |key: &str| {
+        plan.get(key)
+            .and_then(Value::as_array)
+            .and_then(|values| {
+                values
+                    .iter()
+                    .map(|v| v.as_str().map(str::to_owned))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or_else(|| {
+                error(anyhow!("Expected list of strings for '{}'", key))
+            })
+    };
+
+    Ok(QueryPlan {
+        array_uri: string("Array.URI")?,
+        array_type: string("Array.Type")?,
+        vfs_backend: string("VFS.Backend")?,
+        layout: string("Query.Layout")?,
+        strategy: QueryStrategy::from(string("Query.Strategy.Name")?),
+        attributes: strings("Query.Attributes")?,
+        dimensions: strings("Query.Dimensions")?,
+        fragments: vec![],
+    })
+}
+
+/// Returns the URIs of the fragments of `array` which a query with
+/// `subarray` will read. See [QueryPlan::fragments].
+pub(crate) fn planned_fragments(
+    array: &Array,
+    subarray: &Subarray,
+) -> TileDBResult<Vec<String>> {
+    let timestamp_start = array.open_timestamp_start()?;
+    let timestamp_end = array.open_timestamp_end()?;
+    let ranges = subarray.ranges()?;
+
+    let fragments =
+        FragmentInfoBuilder::new(&array.context(), array.uri())?.build()?;
+    let mut uris = vec![];
+    for index in 0..fragments.num_fragments()? {
+        let fragment = fragments.get_fragment(index)?;
+
+        let [start, end] = fragment.timestamp_range()?;
+        if start < timestamp_start || end > timestamp_end {
+            continue;
+        }
+
+        let non_empty_domain = fragment.non_empty_domain()?;
+        let intersects =
+            non_empty_domain
+                .iter()
+                .zip(ranges.iter())
+                .all(|(d, ranges)| {
+                    ranges.iter().any(|r| d.range.intersection(r).is_some())
+                });
+        if intersects {
+            uris.push(fragment.uri()?);
+        }
+    }
+    Ok(uris)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strategy_names() {
+        for strategy in [
+            QueryStrategy::DenseReader,
+            QueryStrategy::SparseGlobalOrderReader,
+            QueryStrategy::SparseUnorderedWithDupsReader,
+            QueryStrategy::OrderedDimLabelReader,
+            QueryStrategy::Reader,
+            QueryStrategy::Other("Writer".to_owned()),
+        ] {
+            assert_eq!(
+                strategy,
+                QueryStrategy::from(String::from(strategy.clone()))
+            );
+        }
+    }
+
+    #[test]
+    fn plan_from_json() -> crate::Result<()> {
+        let plan = parse_plan(
+            r#"{
+                "TileDB Query Plan": {
+                    "Array.URI": "file:///tmp/quickstart_sparse",
+                    "Array.Type": "sparse",
+                    "VFS.Backend": "file",
+                    "Query.Layout": "row-major",
+                    "Query.Strategy.Name": "SparseGlobalOrderReader",
+                    "Query.Attributes": ["a"],
+                    "Query.Dimensions": ["cols", "rows"]
+                }
+            }"#,
+        )?;
+        assert_eq!(
+            QueryPlan {
+                array_uri: "file:///tmp/quickstart_sparse".to_owned(),
+                array_type: "sparse".to_owned(),
+                vfs_backend: "file".to_owned(),
+                layout: "row-major".to_owned(),
+                strategy: QueryStrategy::SparseGlobalOrderReader,
+                attributes: vec!["a".to_owned()],
+                dimensions: vec!["cols".to_owned(), "rows".to_owned()],
+                fragments: vec![],
+            },
+            plan
+        );
+
+        assert!(parse_plan("{}").is_err());
+        assert!(parse_plan(
+            r#"{
+                "TileDB Query Plan": {
+                    "Array.URI": "file:///tmp/quickstart_sparse",
+                    "Array.Type": "sparse",
+                    "VFS.Backend": "file",
+                    "Query.Layout": "row-major",
+                    "Query.Strategy.Name": "SparseGlobalOrderReader",
+                    "Query.Attributes": [1],
+                    "Query.Dimensions": []
+                }
+            }"#
+        )
+        .is_err());
+
+        Ok(())
+    }
+}
@@ -209,6 +209,15 @@ where
     }
 }
 
+impl<T, B> BuilderBaseMut for AggregateBuilder<T, B>
+where
+    B: BuilderBaseMut,
+{
+    fn base_mut(&mut self) -> &mut BuilderBase {
+        self.base.base_mut()
+    }
+}
+
 impl<T, Q> Query for AggregateQuery<T, Q>
 where
     Q: Query,
@@ -0,0 +1,551 @@
+use std::sync::Arc;
+
+use ::arrow::array::Array as ArrowArray;
+use ::arrow::datatypes::SchemaRef;
+use ::arrow::error::ArrowError;
+use ::arrow::ffi_stream::FFI_ArrowArrayStream;
+use ::arrow::record_batch::{RecordBatch, RecordBatchReader};
+use anyhow::anyhow;
+use tiledb_common::physical_type_go;
+
+use super::*;
+use crate::error::Error;
+use crate::query::buffer::RefTypedQueryBuffersMut;
+use crate::query::read::output::TypedRawReadOutput;
+
+/// Callback which assembles the fields read by each step of a query
+/// into a [RecordBatch].
+pub struct RecordBatchConstructor {
+    schema: SchemaRef,
+}
+
+impl RecordBatchConstructor {
+    fn construct(
+        &self,
+        args: Vec<TypedRawReadOutput>,
+    ) -> Result<RecordBatch, ArrowError> {
+        let columns = args
+            .into_iter()
+            .map(|rr| {
+                Arc::<dyn ArrowArray>::try_from(rr)
+                    .map_err(|e| ArrowError::ExternalError(Box::new(e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        RecordBatch::try_new(Arc::clone(&self.schema), columns)
+    }
+}
+
+impl ReadCallbackVarArg for RecordBatchConstructor {
+    type Intermediate = RecordBatch;
+    type Final = RecordBatch;
+    type Error = ArrowError;
+
+    fn intermediate_result(
+        &mut self,
+        args: Vec<TypedRawReadOutput>,
+    ) -> Result<Self::Intermediate, Self::Error> {
+        self.construct(args)
+    }
+
+    fn final_result(
+        self,
+        args: Vec<TypedRawReadOutput>,
+    ) -> Result<Self::Final, Self::Error> {
+        self.construct(args)
+    }
+}
+
+/// Builds an [ArrowReadQuery], which reads the results of a query
+/// as a sequence of arrow [RecordBatch]es.
+///
+/// By default all of the dimensions and attributes of the array are read.
+/// The subarray, query condition, and layout are set the same way
+/// as for any other query, using the methods of [QueryBuilder].
+///
+/// ```no_run
+/// # use tiledb_api::{Array, Context, Result as TileDBResult};
+/// # use tiledb_api::array::Mode;
+/// # use tiledb_api::query::{QueryBuilder, QueryConditionExpr as QC};
+/// # use tiledb_api::query::read::arrow::ArrowReadBuilder;
+/// # fn read(ctx: &Context, uri: &str) -> TileDBResult<()> {
+/// let reader = ArrowReadBuilder::new(Array::open(ctx, uri, Mode::Read)?)?
+///     .fields(["rows", "a"])?
+///     .start_subarray()?
+///     .add_range("rows", &[1, 2])?
+///     .finish_subarray()?
+///     .query_condition(QC::field("a").gt(10))?
+///     .build();
+/// for batch in reader {
+///     let batch = batch.expect("Error reading batch");
+///     println!("{} rows", batch.num_rows());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ArrowReadBuilder {
+    base: ReadBuilder,
+    /// Arrow schema of all fields which can be read
+    array_schema: SchemaRef,
+    /// Names of the fields which will be read
+    fields: Vec<String>,
+    memory_limit: Option<usize>,
+    /// Arrow schema of the fields which will be read
+    schema: SchemaRef,
+    handles: Vec<TypedReadHandle<'static>>,
+}
+
+impl ContextBound for ArrowReadBuilder {
+    fn context(&self) -> Context {
+        self.base.context()
+    }
+}
+
+impl ArrowReadBuilder {
+    pub fn new(array: Array) -> TileDBResult<Self> {
+        let array_schema =
+            array.schema()?.to_arrow()?.ok().ok_or_else(|| {
+                Error::InvalidArgument(anyhow!(
+                    "Array '{}' has no arrow representation",
+                    array.uri()
+                ))
+            })?;
+        let fields = array_schema
+            .fields()
+            .iter()
+            .map(|f| f.name().to_owned())
+            .collect::<Vec<_>>();
+
+        let mut b = ArrowReadBuilder {
+            base: ReadBuilder::new(array)?,
+            schema: Arc::new(array_schema.clone()),
+            array_schema: Arc::new(array_schema),
+            fields,
+            memory_limit: None,
+            handles: vec![],
+        };
+        b.register()?;
+        Ok(b)
+    }
+
+    /// Selects the fields to read, in the order which
+    /// they will appear in each [RecordBatch].
+    pub fn fields<I, S>(mut self, fields: I) -> TileDBResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.fields = fields
+            .into_iter()
+            .map(|f| f.as_ref().to_owned())
+            .collect::<Vec<_>>();
+        self.register()?;
+        Ok(self)
+    }
+
+    /// Sets the memory limit in bytes of the scratch space of each field.
+    /// This bounds the number of rows of each [RecordBatch].
+    pub fn memory_limit(mut self, memory_limit: usize) -> TileDBResult<Self> {
+        self.memory_limit = Some(memory_limit);
+        self.register()?;
+        Ok(self)
+    }
+
+    /// Returns the schema of the record batches which will be read.
+    pub fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    /// Allocates scratch space for the selected fields.
+    fn register(&mut self) -> TileDBResult<()> {
+        let array_schema = self.base.base().array().schema()?;
+
+        let mut arrow_fields = Vec::with_capacity(self.fields.len());
+        let mut handles = Vec::with_capacity(self.fields.len());
+        for name in self.fields.iter() {
+            let arrow_field =
+                self.array_schema.field_with_name(name).map_err(|_| {
+                    Error::InvalidArgument(anyhow!(
+                        "Field '{}' has no arrow representation",
+                        name
+                    ))
+                })?;
+            arrow_fields.push(arrow_field.clone());
+
+            let field = array_schema.field(name.as_str())?;
+            let metadata = FieldMetadata::try_from(&field)?;
+            let allocator = field.query_scratch_allocator(self.memory_limit)?;
+            handles.push(physical_type_go!(metadata.datatype, DT, {
+                let managed: ManagedBuffer<DT> = ManagedBuffer::new(allocator);
+                TypedReadHandle::from(RawReadHandle::managed(metadata, managed))
+            }));
+        }
+
+        self.schema = Arc::new(::arrow::datatypes::Schema::new_with_metadata(
+            arrow_fields,
+            self.array_schema.metadata().clone(),
+        ));
+        self.handles = handles;
+        Ok(())
+    }
+}
+
+impl QueryBuilder for ArrowReadBuilder {
+    type Query = ArrowReadQuery;
+
+    fn base(&self) -> &BuilderBase {
+        self.base.base()
+    }
+
+    fn build(self) -> Self::Query {
+        ArrowReadQuery {
+            base: self.base.build(),
+            handles: ScratchHandles(self.handles),
+            constructor: RecordBatchConstructor {
+                schema: self.schema,
+            },
+            done: false,
+        }
+    }
+}
+
+impl BuilderBaseMut for ArrowReadBuilder {
+    fn base_mut(&mut self) -> &mut BuilderBase {
+        self.base.base_mut()
+    }
+}
+
+/// The scratch space of the fields read by an [ArrowReadQuery].
+struct ScratchHandles(Vec<TypedReadHandle<'static>>);
+
+// SAFETY: each handle is created by `ArrowReadBuilder::register` from a
+// `ManagedBuffer` with a `FieldScratchAllocator`, so it refers only to
+// pinned scratch space which it owns itself. Nothing outside of the handle
+// can reach that scratch space, so it can be moved to another thread.
+unsafe impl Send for ScratchHandles {}
+
+/// A read query which produces one [RecordBatch] for each step of the query.
+///
+/// Empty steps are skipped. Once the query has completed,
+/// the array can be recovered using [Query::finalize].
+pub struct ArrowReadQuery {
+    base: QueryBase,
+    handles: ScratchHandles,
+    constructor: RecordBatchConstructor,
+    done: bool,
+}
+
+impl ArrowReadQuery {
+    /// Runs one step of the query and assembles the cells which it read.
+    /// Returns `None` if the scratch space was too small to read any cells,
+    /// in which case the scratch space is grown for the next step.
+    fn step(&mut self) -> TileDBResult<Option<RecordBatch>> {
+        let handles = &mut self.handles.0;
+        {
+            let context = self.base.context();
+            let c_query = **self.base.cquery();
+            for handle in handles.iter_mut() {
+                handle.attach_query(&context, c_query)?;
+            }
+        }
+
+        let status = self.base.step()?;
+        let sizes = handles
+            .iter()
+            .map(|handle| handle.last_read_ncells())
+            .collect::<Vec<usize>>();
+        let has_cells = sizes.iter().all(|ncells| *ncells > 0);
+        match status {
+            ReadStepOutput::Intermediate(()) if has_cells => (),
+            ReadStepOutput::Final(()) => self.done = true,
+            ReadStepOutput::NotEnoughSpace
+            | ReadStepOutput::Intermediate(()) => {
+                for handle in handles.iter_mut() {
+                    handle.realloc_if_managed();
+                }
+                return Ok(None);
+            }
+        }
+
+        let buffers = handles
+            .iter()
+            .map(|handle| handle.borrow_mut())
+            .collect::<Vec<RefTypedQueryBuffersMut>>();
+        let args = handles
+            .iter()
+            .zip(sizes.iter())
+            .zip(buffers.iter())
+            .map(|((handle, ncells), buffers)| TypedRawReadOutput {
+                datatype: handle.field().datatype,
+                ncells: *ncells,
+                buffers: buffers.as_shared(),
+            })
+            .collect::<Vec<TypedRawReadOutput>>();
+        self.constructor.construct(args).map(Some).map_err(|e| {
+            let fields = handles
+                .iter()
+                .map(|handle| handle.field().name.clone())
+                .collect::<Vec<String>>();
+            Error::QueryCallback(fields, anyhow!(e))
+        })
+    }
+}
+
+impl ContextBound for ArrowReadQuery {
+    fn context(&self) -> Context {
+        self.base.context()
+    }
+}
+
+impl Query for ArrowReadQuery {
+    fn base(&self) -> &QueryBase {
+        self.base.base()
+    }
+
+    fn finalize(self) -> TileDBResult<Array> {
+        self.base.finalize()
+    }
+}
+
+impl Iterator for ArrowReadQuery {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.step() {
+                Ok(Some(batch)) if batch.num_rows() > 0 => {
+                    return Some(Ok(batch))
+                }
+                Ok(_) => continue,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(ArrowError::ExternalError(Box::new(e))));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl std::iter::FusedIterator for ArrowReadQuery {}
+
+impl RecordBatchReader for ArrowReadQuery {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.constructor.schema)
+    }
+}
+
+/// Exports the query through the Arrow C stream interface, so that
+/// other libraries in the same process can consume its results without
+/// copying them. The query is submitted as the consumer requests batches.
+impl From<ArrowReadQuery> for FFI_ArrowArrayStream {
+    fn from(value: ArrowReadQuery) -> Self {
+        FFI_ArrowArrayStream::new(Box::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::arrow::array::Int32Array;
+    use ::arrow::ffi_stream::ArrowArrayStreamReader;
+    use tiledb_common::array::ArrayType;
+
+    use super::*;
+    use crate::query::write::input::arrow::ArrowStreamRecords;
+    use crate::query::{QueryConditionExpr as QC, WriteBuilder};
+    use crate::tests::examples::{quickstart, TestArray};
+
+    fn quickstart_sparse(name: &str) -> TileDBResult<TestArray> {
+        let mut array = TestArray::new(
+            name,
+            quickstart::Builder::new(ArrayType::Sparse).build().into(),
+        )?;
+
+        let rows = (1..=4).flat_map(|r| [r; 4]).collect::<Vec<i32>>();
+        let cols = (1..=4).cycle().take(16).collect::<Vec<i32>>();
+        let a = (0..16).collect::<Vec<i32>>();
+
+        let q = WriteBuilder::new(array.for_write()?)?
+            .data("rows", &rows)?
+            .data("cols", &cols)?
+            .data("a", &a)?
+            .build();
+        q.submit()?;
+        q.finalize()?;
+
+        Ok(array)
+    }
+
+    fn column(batch: &RecordBatch, name: &str) -> Vec<i32> {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap()
+            .values()
+            .to_vec()
+    }
+
+    #[test]
+    /// Ensure that ArrowReadQuery is Send, fails to compile if not
+    fn is_send() {
+        fn is_send<T: Send>() {}
+        is_send::<ArrowReadQuery>()
+    }
+
+    #[test]
+    fn read_all_fields() -> TileDBResult<()> {
+        let array = quickstart_sparse("arrow_read_all_fields")?;
+
+        let b = ArrowReadBuilder::new(array.for_read()?)?
+            .layout(QueryLayout::RowMajor)?;
+        let expect_schema = b.schema();
+        assert_eq!(
+            vec!["rows", "cols", "a"],
+            expect_schema
+                .fields()
+                .iter()
+                .map(|f| f.name().as_str())
+                .collect::<Vec<_>>()
+        );
+
+        let reader = b.build();
+        assert_eq!(expect_schema, RecordBatchReader::schema(&reader));
+
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(1, batches.len());
+        assert_eq!(expect_schema, batches[0].schema());
+        assert_eq!((0..16).collect::<Vec<i32>>(), column(&batches[0], "a"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_batches() -> TileDBResult<()> {
+        let array = quickstart_sparse("arrow_read_batches")?;
+
+        // room for four `Int32` cells per step
+        let mut reader = ArrowReadBuilder::new(array.for_read()?)?
+            .fields(["a", "rows"])?
+            .memory_limit(16)?
+            .layout(QueryLayout::RowMajor)?
+            .build();
+
+        let mut a = vec![];
+        let mut rows = vec![];
+        for batch in reader.by_ref() {
+            let batch = batch.unwrap();
+            assert_eq!(2, batch.num_columns());
+            assert!(batch.num_rows() <= 4);
+            a.extend(column(&batch, "a"));
+            rows.extend(column(&batch, "rows"));
+        }
+        assert_eq!((0..16).collect::<Vec<i32>>(), a);
+        assert_eq!((1..=4).flat_map(|r| [r; 4]).collect::<Vec<i32>>(), rows);
+
+        assert!(reader.next().is_none());
+        let _ = reader.finalize()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_subarray_condition() -> TileDBResult<()> {
+        let array = quickstart_sparse("arrow_read_subarray_condition")?;
+
+        let reader = ArrowReadBuilder::new(array.for_read()?)?
+            .fields(["a"])?
+            .layout(QueryLayout::RowMajor)?
+            .start_subarray()?
+            .add_range("rows", &[2i32, 3])?
+            .finish_subarray()?
+            .query_condition(QC::field("a").ge(6i32))?
+            .build();
+
+        let a = reader
+            .map(|batch| column(&batch.unwrap(), "a"))
+            .collect::<Vec<_>>()
+            .concat();
+        assert_eq!((6..12).collect::<Vec<i32>>(), a);
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_invalid_field() -> TileDBResult<()> {
+        let array = quickstart_sparse("arrow_read_invalid_field")?;
+
+        let b = ArrowReadBuilder::new(array.for_read()?)?;
+        assert!(matches!(b.fields(["b"]), Err(Error::InvalidArgument(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn ffi_stream_export() -> TileDBResult<()> {
+        let array = quickstart_sparse("arrow_ffi_stream_export")?;
+
+        let reader = ArrowReadBuilder::new(array.for_read()?)?
+            .memory_limit(16)?
+            .layout(QueryLayout::RowMajor)?
+            .build();
+        let expect_schema = RecordBatchReader::schema(&reader);
+        let stream = FFI_ArrowArrayStream::from(reader);
+
+        // the consumer may pull batches from another thread
+        let (schema, batches) = std::thread::spawn(move || {
+            let reader = ArrowArrayStreamReader::try_new(stream).unwrap();
+            let schema = reader.schema();
+            let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+            (schema, batches)
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(expect_schema, schema);
+        assert_eq!(4, batches.len());
+        let a = batches
+            .iter()
+            .map(|batch| column(batch, "a"))
+            .collect::<Vec<_>>()
+            .concat();
+        assert_eq!((0..16).collect::<Vec<i32>>(), a);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ffi_stream_round_trip() -> TileDBResult<()> {
+        let source = quickstart_sparse("arrow_ffi_stream_source")?;
+        let mut target = TestArray::new(
+            "arrow_ffi_stream_target",
+            quickstart::Builder::new(ArrayType::Sparse).build().into(),
+        )?;
+
+        // copy the source into the target through the C stream interface
+        let stream = FFI_ArrowArrayStream::from(
+            ArrowReadBuilder::new(source.for_read()?)?
+                .memory_limit(16)?
+                .build(),
+        );
+        let records = ArrowStreamRecords::try_from(stream)?;
+        assert_eq!(
+            ArrowReadBuilder::new(source.for_read()?)?.schema(),
+            records.schema()
+        );
+
+        // each batch of four cells is written separately
+        let (_, fragments) = records.write(target.for_write()?)?;
+        assert_eq!(4, fragments.len());
+
+        let read_all = |array: &TestArray| -> TileDBResult<Vec<RecordBatch>> {
+            let reader = ArrowReadBuilder::new(array.for_read()?)?
+                .layout(QueryLayout::RowMajor)?
+                .build();
+            Ok(reader.collect::<Result<Vec<_>, _>>().unwrap())
+        };
+        assert_eq!(read_all(&source)?, read_all(&target)?);
+
+        Ok(())
+    }
+}
@@ -423,7 +423,9 @@ map_impls!(ReadCallback4Arg, A1: Unit1, A2: Unit2, A3: Unit3, A4: Unit4);
 
 mod impls {
     use super::*;
-    use crate::query::read::output::VarDataIterator;
+    use crate::query::read::output::{
+        CellStructureSingleIterator, FixedDataIterator, VarDataIterator,
+    };
 
     impl<C> ReadCallback for Vec<C>
     where
@@ -582,6 +584,124 @@ mod impls {
             Some((vec![], vec![]))
         }
     }
+
+    /// Returns whether each cell of a read result is non-null.
+    /// All cells of a non-nullable field are non-null.
+    fn validity<C>(arg: &RawReadOutput<C>) -> Vec<bool> {
+        match arg.input.validity.as_ref() {
+            Some(validity) => validity.as_ref()[0..arg.ncells]
+                .iter()
+                .map(|v| *v != 0)
+                .collect(),
+            None => vec![true; arg.ncells],
+        }
+    }
+
+    impl<C> ReadCallback for Vec<Option<C>>
+    where
+        C: CellValue,
+    {
+        type Unit = C;
+        type Intermediate = ();
+        type Final = Self;
+        type Error = crate::error::Error;
+
+        fn intermediate_result(
+            &mut self,
+            arg: RawReadOutput<Self::Unit>,
+        ) -> Result<Self::Intermediate, Self::Error> {
+            let validity = validity(&arg);
+            let values = CellStructureSingleIterator::try_from(arg)?;
+            self.extend(
+                values.zip(validity).map(|(value, v)| v.then_some(value)),
+            );
+            Ok(())
+        }
+
+        fn final_result(
+            mut self,
+            arg: RawReadOutput<Self::Unit>,
+        ) -> Result<Self::Final, Self::Error> {
+            self.intermediate_result(arg).map(|_| self)
+        }
+
+        fn cleared(&self) -> Option<Self> {
+            Some(vec![])
+        }
+    }
+
+    impl<C> ReadCallback for Vec<Option<Vec<C>>>
+    where
+        C: CellValue,
+    {
+        type Unit = C;
+        type Intermediate = ();
+        type Final = Self;
+        type Error = crate::error::Error;
+
+        fn intermediate_result(
+            &mut self,
+            arg: RawReadOutput<Self::Unit>,
+        ) -> Result<Self::Intermediate, Self::Error> {
+            let validity = validity(&arg);
+            if arg.input.cell_structure.is_var() {
+                self.extend(
+                    VarDataIterator::try_from(arg)?
+                        .zip(validity)
+                        .map(|(cell, v)| v.then(|| cell.to_vec())),
+                );
+            } else {
+                self.extend(
+                    FixedDataIterator::try_from(arg)?
+                        .zip(validity)
+                        .map(|(cell, v)| v.then(|| cell.to_vec())),
+                );
+            }
+            Ok(())
+        }
+
+        fn final_result(
+            mut self,
+            arg: RawReadOutput<Self::Unit>,
+        ) -> Result<Self::Final, Self::Error> {
+            self.intermediate_result(arg).map(|_| self)
+        }
+
+        fn cleared(&self) -> Option<Self> {
+            Some(vec![])
+        }
+    }
+
+    impl ReadCallback for Vec<Option<String>> {
+        type Unit = u8;
+        type Intermediate = ();
+        type Final = Self;
+        type Error = crate::error::Error;
+
+        fn intermediate_result(
+            &mut self,
+            arg: RawReadOutput<Self::Unit>,
+        ) -> Result<Self::Intermediate, Self::Error> {
+            let validity = validity(&arg);
+            self.extend(VarDataIterator::try_from(arg)?.zip(validity).map(
+                |(cell, v)| {
+                    v.then(|| String::from_utf8_lossy(cell).to_string())
+                },
+            ));
+            Ok(())
+        }
+
+        fn final_result(
+            mut self,
+            arg: RawReadOutput<Self::Unit>,
+        ) -> Result<Self::Final, Self::Error> {
+            self.intermediate_result(arg).map(|_| self)
+        }
+
+        fn cleared(&self) -> Option<Self> {
+            Some(vec![])
+        }
+    }
 }
 
 macro_rules! query_read_callback {
@@ -856,11 +976,28 @@ macro_rules! query_read_callback {
                 }
             }
 
+            impl<T, B> BuilderBaseMut for $Builder <'_, T, B>
+            where T: $callback,
+                  B: BuilderBaseMut,
+            {
+                fn base_mut(&mut self) -> &mut BuilderBase {
+                    self.base.base_mut()
+                }
+            }
+
             impl<'data, T, B> ReadQueryBuilder<'data> for $Builder<'data, T, B>
             where
                 T: $callback,
                 B: ReadQueryBuilder<'data>,
             {
+                fn attach_buffers(&mut self) -> TileDBResult<()> {
+                    let context = self.base().context();
+                    let cquery = **self.base().cquery();
+                    $(
+                        self.[< arg_ $U:snake >].attach_query(&context, cquery)?;
+                    )+
+                    self.base.attach_buffers()
+                }
             }
         }
     }
@@ -1157,11 +1294,23 @@ where
     }
 }
 
+impl<T, B> BuilderBaseMut for CallbackVarArgReadBuilder<'_, T, B>
+where
+    B: BuilderBaseMut,
+{
+    fn base_mut(&mut self) -> &mut BuilderBase {
+        self.base.base_mut()
+    }
+}
+
 impl<'data, T, B> ReadQueryBuilder<'data>
     for CallbackVarArgReadBuilder<'data, T, B>
 where
-    B: QueryBuilder,
+    B: ReadQueryBuilder<'data>,
 {
+    fn attach_buffers(&mut self) -> TileDBResult<()> {
+        self.base.attach_buffers()
+    }
 }
 
 #[cfg(test)]
@@ -4,13 +4,24 @@ use std::cell::RefCell;
 use std::pin::Pin;
 
 use paste::paste;
+use tiledb_common::physical_type_go;
 
+use crate::array::CellValNum;
 use crate::config::Config;
+use crate::datatype::PhysicalType;
 use crate::query::buffer::{BufferMut, QueryBuffersMut};
-use crate::query::read::output::ScratchAllocator;
+use crate::query::plan::QueryPlan;
+use crate::query::read::output::{
+    NonVarSized, NullableNonVarSized, NullableVarSized, ScratchAllocator,
+    VarSized,
+};
+use crate::query::record::{RecordsConstructor, TileDBRecord};
+use crate::string::{RawTDBString, TDBString};
 use crate::Result as TileDBResult;
 
 pub mod aggregate;
+#[cfg(feature = "arrow")]
+pub mod arrow;
 mod callback;
 pub mod output;
 mod raw;
@@ -171,10 +182,88 @@ impl<U> ReadStepOutput<U, U> {
 pub enum ScratchStrategy<'data, C> {
     #[default]
     AttributeDefault,
+    /// Allocate scratch space using the estimated size of the field's results.
+    /// The subarray and query condition should be set before registering
+    /// a field with this strategy, so that the estimate accounts for them.
+    /// See [ReadQueryBuilder::estimated_result_size].
+    Estimated,
     RawBuffers(&'data RefCell<QueryBuffersMut<'data, C>>),
     CustomAllocator(Box<dyn ScratchAllocator<C> + 'data>),
 }
 
+/// Estimate of the number of bytes needed to hold the results
+/// of a read query for one field.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct EstimatedResultSize {
+    /// Size in bytes of the field data.
+    pub data: u64,
+    /// Size in bytes of the cell offsets, if the field is variable-length.
+    pub offsets: Option<u64>,
+    /// Size in bytes of the validity, if the field is nullable.
+    pub validity: Option<u64>,
+}
+
+impl EstimatedResultSize {
+    /// Returns a scratch allocator whose initial allocation
+    /// holds the estimated results of a field with `cell_val_num`.
+    pub fn scratch_allocator<C>(
+        &self,
+        cell_val_num: CellValNum,
+    ) -> Box<dyn ScratchAllocator<C>>
+    where
+        C: PhysicalType,
+    {
+        // the estimate may be zero, but the scratch space must not be empty
+        let values =
+            std::cmp::max(1, self.data as usize / std::mem::size_of::<C>());
+
+        match (cell_val_num, self.validity) {
+            (CellValNum::Fixed(nz), validity) => {
+                let values_per_cell = nz.get() as usize;
+                let cells = values.div_ceil(values_per_cell);
+                let capacity = cells * values_per_cell;
+                if let Some(validity) = validity {
+                    Box::new(NullableNonVarSized {
+                        cell_val_num: nz,
+                        data_capacity: capacity,
+                        validity_capacity: std::cmp::max(
+                            cells,
+                            validity as usize,
+                        ),
+                    })
+                } else {
+                    Box::new(NonVarSized {
+                        cell_val_num: nz,
+                        capacity,
+                    })
+                }
+            }
+            (CellValNum::Var, validity) => {
+                let cells = std::cmp::max(
+                    1,
+                    self.offsets.unwrap_or(0) as usize
+                        / std::mem::size_of::<u64>(),
+                );
+                if let Some(validity) = validity {
+                    Box::new(NullableVarSized {
+                        byte_capacity: values,
+                        offset_capacity: cells,
+                        validity_capacity: std::cmp::max(
+                            cells,
+                            validity as usize,
+                        ),
+                    })
+                } else {
+                    Box::new(VarSized {
+                        byte_capacity: values,
+                        offset_capacity: cells,
+                    })
+                }
+            }
+        }
+    }
+}
+
 impl<'data, C> From<&'data RefCell<QueryBuffersMut<'data, C>>>
     for ScratchStrategy<'data, C>
 {
@@ -246,6 +335,12 @@ macro_rules! fn_register_callback {
                                 let managed = ManagedBuffer::from(alloc);
                                 RawReadHandle::managed(metadata, managed)
                             },
+                            ScratchStrategy::Estimated => {
+                                let estimate = self.estimated_result_size([< field_ $U:snake >])?;
+                                let alloc = estimate.scratch_allocator::<<T as $Callback>::$U>(metadata.cell_val_num);
+                                let managed = ManagedBuffer::from(alloc);
+                                RawReadHandle::managed(metadata, managed)
+                            },
                             ScratchStrategy::RawBuffers(qb) => {
                                 RawReadHandle::new(metadata, qb)
                             },
@@ -301,6 +396,77 @@ pub trait ReadQueryBuilder<'data>: QueryBuilder {
         })
     }
 
+    /// Returns the estimated size of the results of this query for `field`.
+    ///
+    /// The estimate accounts for the subarray and query condition
+    /// which are set when this is called. It is not exact, and may be
+    /// either larger or smaller than the actual results.
+    fn estimated_result_size<S>(
+        &self,
+        field: S,
+    ) -> TileDBResult<EstimatedResultSize>
+    where
+        S: AsRef<str>,
+    {
+        let (is_var, is_nullable) = {
+            let schema = self.base().array().schema()?;
+            let field = schema.field(field.as_ref())?;
+            (field.cell_val_num()?.is_var_sized(), field.nullability()?)
+        };
+
+        let c_query = **self.base().cquery();
+        let c_name = cstring!(field.as_ref());
+
+        let mut data: u64 = 0;
+        let mut offsets: u64 = 0;
+        let mut validity: u64 = 0;
+
+        match (is_var, is_nullable) {
+            (false, false) => self.base().capi_call(|ctx| unsafe {
+                ffi::tiledb_query_get_est_result_size(
+                    ctx,
+                    c_query,
+                    c_name.as_ptr(),
+                    &mut data,
+                )
+            })?,
+            (true, false) => self.base().capi_call(|ctx| unsafe {
+                ffi::tiledb_query_get_est_result_size_var(
+                    ctx,
+                    c_query,
+                    c_name.as_ptr(),
+                    &mut offsets,
+                    &mut data,
+                )
+            })?,
+            (false, true) => self.base().capi_call(|ctx| unsafe {
+                ffi::tiledb_query_get_est_result_size_nullable(
+                    ctx,
+                    c_query,
+                    c_name.as_ptr(),
+                    &mut data,
+                    &mut validity,
+                )
+            })?,
+            (true, true) => self.base().capi_call(|ctx| unsafe {
+                ffi::tiledb_query_get_est_result_size_var_nullable(
+                    ctx,
+                    c_query,
+                    c_name.as_ptr(),
+                    &mut offsets,
+                    &mut data,
+                    &mut validity,
+                )
+            })?,
+        }
+
+        Ok(EstimatedResultSize {
+            data,
+            offsets: if is_var { Some(offsets) } else { None },
+            validity: if is_nullable { Some(validity) } else { None },
+        })
+    }
+
     /// Register raw memory locations to read query results from multiple attributes into
     fn register_var_raw<I>(
         self,
@@ -363,6 +529,78 @@ pub trait ReadQueryBuilder<'data>: QueryBuilder {
         })
     }
 
+    /// Sets the buffers of the fields registered so far on the
+    /// underlying libtiledb query. This otherwise happens when the
+    /// query is submitted.
+    #[doc(hidden)]
+    fn attach_buffers(&mut self) -> TileDBResult<()> {
+        Ok(())
+    }
+
+    /// Returns the JSON representation of the plan which libtiledb
+    /// will use to execute this query.
+    fn explain_json(&mut self) -> TileDBResult<String> {
+        // the plan depends on which fields are read
+        self.attach_buffers()?;
+
+        let c_query = **self.base().cquery();
+        let mut c_plan: *mut ffi::tiledb_string_t = out_ptr!();
+        self.base().capi_call(|ctx| unsafe {
+            ffi::tiledb_query_get_plan(ctx, c_query, &mut c_plan)
+        })?;
+
+        Ok(TDBString::from_raw(RawTDBString::Owned(c_plan)).to_string()?)
+    }
+
+    /// Returns the plan which libtiledb will use to execute this query.
+    ///
+    /// The plan accounts for the layout, subarray, and fields which are
+    /// registered when this is called, so this should be called just before
+    /// [build](QueryBuilder::build).
+    fn explain(&mut self) -> TileDBResult<QueryPlan> {
+        let mut plan = crate::query::plan::parse_plan(&self.explain_json()?)?;
+        plan.fragments = crate::query::plan::planned_fragments(
+            self.base().array(),
+            &self.subarray()?,
+        )?;
+        Ok(plan)
+    }
+
+    /// Register a struct to be constructed from the query results,
+    /// one value per cell. The struct fields are checked against the
+    /// schema here. See [crate::query::record].
+    fn register_records<R>(
+        self,
+    ) -> TileDBResult<
+        CallbackVarArgReadBuilder<'data, RecordsConstructor<R>, Self>,
+    >
+    where
+        R: TileDBRecord,
+    {
+        let handles = {
+            let schema = self.base().array().schema()?;
+            R::check_schema(&schema)?;
+
+            R::field_names()
+                .into_iter()
+                .map(|name| {
+                    let field = schema.field(name)?;
+                    let metadata = FieldMetadata::try_from(&field)?;
+                    let allocator = field.query_scratch_allocator(None)?;
+                    Ok(physical_type_go!(metadata.datatype, DT, {
+                        let managed: ManagedBuffer<DT> =
+                            ManagedBuffer::new(allocator);
+                        TypedReadHandle::from(RawReadHandle::managed(
+                            metadata, managed,
+                        ))
+                    }))
+                })
+                .collect::<TileDBResult<Vec<TypedReadHandle>>>()?
+        };
+
+        self.register_callback_var(handles, RecordsConstructor::default())
+    }
+
     /// Register a typed result to be constructed from the query results.
     /// Intermediate raw results are written into the provided scratch space.
     fn register_constructor<S, T>(
@@ -435,6 +673,12 @@ impl QueryBuilder for ReadBuilder {
     }
 }
 
+impl BuilderBaseMut for ReadBuilder {
+    fn base_mut(&mut self) -> &mut BuilderBase {
+        &mut self.base
+    }
+}
+
 impl ReadQueryBuilder<'_> for ReadBuilder {}
 
 pub struct ReadQueryIterator<I, F> {
@@ -456,3 +700,254 @@ impl<I, F> Iterator for ReadQueryIterator<I, F> {
 }
 
 impl<I, F> std::iter::FusedIterator for ReadQueryIterator<I, F> {}
+
+#[cfg(test)]
+mod tests {
+    use tiledb_common::array::ArrayType;
+
+    use super::*;
+    use crate::array::{ArrayOpener, FragmentInfoBuilder, Mode};
+    use crate::query::read::output::{ScratchCellStructure, ScratchSpace};
+    use crate::query::{Query, WriteBuilder};
+    use crate::tests::examples::{quickstart, TestArray};
+
+    #[test]
+    fn estimated_scratch_allocator() {
+        let fixed = EstimatedResultSize {
+            data: 40,
+            offsets: None,
+            validity: None,
+        };
+        let ScratchSpace(data, cell_structure, validity) = fixed
+            .scratch_allocator::<u32>(CellValNum::try_from(3).unwrap())
+            .alloc();
+        // rounded up to a whole number of cells
+        assert_eq!(12, data.len());
+        assert!(matches!(cell_structure, ScratchCellStructure::Fixed(_)));
+        assert!(validity.is_none());
+
+        let var = EstimatedResultSize {
+            data: 0,
+            offsets: Some(0),
+            validity: Some(0),
+        };
+        let ScratchSpace(data, cell_structure, validity) =
+            var.scratch_allocator::<u8>(CellValNum::Var).alloc();
+        // an empty estimate still allocates some space
+        assert_eq!(1, data.len());
+        match cell_structure {
+            ScratchCellStructure::Var(offsets) => assert_eq!(1, offsets.len()),
+            ScratchCellStructure::Fixed(_) => unreachable!(),
+        }
+        assert_eq!(Some(1), validity.map(|v| v.len()));
+    }
+
+    #[test]
+    fn estimated_read() -> TileDBResult<()> {
+        let mut array = TestArray::new(
+            "estimated_read",
+            quickstart::Builder::new(ArrayType::Sparse).build().into(),
+        )?;
+
+        let rows = (1..=4).flat_map(|r| [r; 4]).collect::<Vec<i32>>();
+        let cols = (1..=4).cycle().take(16).collect::<Vec<i32>>();
+        let a = (0..16).collect::<Vec<i32>>();
+        {
+            let q = WriteBuilder::new(array.for_write()?)?
+                .data("rows", &rows)?
+                .data("cols", &cols)?
+                .data("a", &a)?
+                .build();
+            q.submit()?;
+            q.finalize()?;
+        }
+
+        let b = ReadBuilder::new(array.for_read()?)?;
+        let estimate = b.estimated_result_size("a")?;
+        assert!(estimate.data >= (a.len() * std::mem::size_of::<i32>()) as u64);
+        assert_eq!(None, estimate.offsets);
+        assert_eq!(None, estimate.validity);
+
+        // the estimate is large enough to read everything in one step
+        let q = b
+            .register_constructor::<_, Vec<i32>>(
+                "a",
+                ScratchStrategy::Estimated,
+            )?
+            .build();
+        let mut steps = q.into_iter();
+        match steps.next() {
+            Some(Ok(ReadStepOutput::Final((a_out, ())))) => {
+                assert_eq!(a, a_out)
+            }
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn nullable_read() -> TileDBResult<()> {
+        use tiledb_common::datatype::Datatype;
+        use tiledb_pod::array::attribute::AttributeData;
+
+        let mut array = TestArray::new("nullable_read", {
+            let mut b = quickstart::Builder::new(ArrayType::Sparse);
+            b.schema.attributes = vec![
+                AttributeData {
+                    name: "a".to_owned(),
+                    datatype: Datatype::Int32,
+                    nullability: Some(true),
+                    ..Default::default()
+                },
+                AttributeData {
+                    name: "s".to_owned(),
+                    datatype: Datatype::StringUtf8,
+                    cell_val_num: Some(CellValNum::Var),
+                    nullability: Some(true),
+                    ..Default::default()
+                },
+                AttributeData {
+                    name: "v".to_owned(),
+                    datatype: Datatype::Float64,
+                    cell_val_num: Some(CellValNum::Var),
+                    nullability: Some(true),
+                    ..Default::default()
+                },
+            ];
+            b.build().into()
+        })?;
+
+        let rows = (1..=4).flat_map(|r| [r; 4]).collect::<Vec<i32>>();
+        let cols = (1..=4).cycle().take(16).collect::<Vec<i32>>();
+        let a = (0..16)
+            .map(|i| (i % 3 != 0).then_some(i))
+            .collect::<Vec<Option<i32>>>();
+        let s = (0..16)
+            .map(|i| (i % 2 != 0).then(|| i.to_string()))
+            .collect::<Vec<Option<String>>>();
+        let v = (0..16)
+            .map(|i| (i % 5 != 0).then(|| vec![i as f64; (i % 4) as usize]))
+            .collect::<Vec<Option<Vec<f64>>>>();
+        {
+            let q = WriteBuilder::new(array.for_write()?)?
+                .data("rows", &rows)?
+                .data("cols", &cols)?
+                .data("a", &a)?
+                .data("s", &s)?
+                .data("v", &v)?
+                .build();
+            q.submit()?;
+            q.finalize()?;
+        }
+
+        let mut q = ReadBuilder::new(array.for_read()?)?
+            .layout(QueryLayout::RowMajor)?
+            .register_constructor::<_, Vec<Option<i32>>>(
+                "a",
+                Default::default(),
+            )?
+            .register_constructor::<_, Vec<Option<String>>>(
+                "s",
+                Default::default(),
+            )?
+            .register_constructor::<_, Vec<Option<Vec<f64>>>>(
+                "v",
+                Default::default(),
+            )?
+            .build();
+        let (v_out, (s_out, (a_out, ()))) = q.execute()?;
+        assert_eq!(a, a_out);
+        assert_eq!(s, s_out);
+        assert_eq!(v, v_out);
+
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> TileDBResult<()> {
+        use crate::query::QueryStrategy;
+
+        let sparse = TestArray::new(
+            "explain_sparse",
+            quickstart::Builder::new(ArrayType::Sparse).build().into(),
+        )?;
+        let mut b = ReadBuilder::new(sparse.for_read()?)?
+            .layout(QueryLayout::Global)?
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?;
+        let plan = b.explain()?;
+        assert_eq!(QueryStrategy::SparseGlobalOrderReader, plan.strategy);
+        assert_eq!(vec!["a".to_owned()], plan.attributes);
+        assert!(plan.dimensions.is_empty());
+
+        assert!(plan.fragments.is_empty());
+
+        // the query can still run after planning
+        let (a, ()) = b.build().execute()?;
+        assert!(a.is_empty());
+
+        // two fragments, written at timestamps 1 and 2
+        for (timestamp, row) in [(1u64, 1i32), (2, 4)] {
+            let array =
+                ArrayOpener::new(&sparse.context, &sparse.uri, Mode::Write)?
+                    .end_timestamp(timestamp)?
+                    .open()?;
+            let (rows, cols) = (vec![row], vec![1i32]);
+            let q = WriteBuilder::new(array)?
+                .data("rows", &rows)?
+                .data("cols", &cols)?
+                .data("a", &rows)?
+                .build();
+            q.submit()?;
+            q.finalize()?;
+        }
+        let fragments = FragmentInfoBuilder::new(&sparse.context, &sparse.uri)?
+            .build()?
+            .iter()?
+            .map(|f| f.uri())
+            .collect::<TileDBResult<Vec<_>>>()?;
+        assert_eq!(2, fragments.len());
+
+        let plan = ReadBuilder::new(sparse.for_read()?)?
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?
+            .explain()?;
+        assert_eq!(fragments, plan.fragments);
+
+        // only fragments which intersect the subarray
+        let plan = ReadBuilder::new(sparse.for_read()?)?
+            .start_subarray()?
+            .add_range("rows", &[3i32, 4])?
+            .finish_subarray()?
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?
+            .explain()?;
+        assert_eq!(fragments[1..], plan.fragments);
+
+        // only fragments within the timestamp range the array is open at
+        let array = ArrayOpener::new(&sparse.context, &sparse.uri, Mode::Read)?
+            .end_timestamp(1)?
+            .open()?;
+        let plan = ReadBuilder::new(array)?
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?
+            .explain()?;
+        assert_eq!(fragments[..1], plan.fragments);
+
+        let dense = TestArray::new(
+            "explain_dense",
+            quickstart::Builder::new(ArrayType::Dense).build().into(),
+        )?;
+        let mut b = ReadBuilder::new(dense.for_read()?)?
+            .layout(QueryLayout::RowMajor)?
+            .start_subarray()?
+            .add_range("rows", &[1i32, 4])?
+            .add_range("cols", &[1i32, 4])?
+            .finish_subarray()?
+            .register_constructor::<_, Vec<i32>>("rows", Default::default())?
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?;
+        let plan = b.explain()?;
+        assert_eq!(QueryStrategy::DenseReader, plan.strategy);
+        assert_eq!(vec!["a".to_owned()], plan.attributes);
+        assert_eq!(vec!["rows".to_owned()], plan.dimensions);
+
+        Ok(())
+    }
+}
@@ -550,11 +550,25 @@ where
     }
 }
 
+impl<B> BuilderBaseMut for RawReadBuilder<'_, B>
+where
+    B: BuilderBaseMut,
+{
+    fn base_mut(&mut self) -> &mut BuilderBase {
+        self.base.base_mut()
+    }
+}
+
 impl<'data, B> ReadQueryBuilder<'data> for RawReadBuilder<'data, B>
 where
     B: ReadQueryBuilder<'data>,
     <B as QueryBuilder>::Query: ContextBound,
 {
+    fn attach_buffers(&mut self) -> TileDBResult<()> {
+        self.raw_read_output
+            .attach_query(&self.base().context(), **self.base().cquery())?;
+        self.base.attach_buffers()
+    }
 }
 
 /// Reads query results into raw buffers.
@@ -689,7 +703,25 @@ where
     }
 }
 
-impl<'data, B> ReadQueryBuilder<'data> for VarRawReadBuilder<'data, B> where
-    B: ReadQueryBuilder<'data>
+impl<B> BuilderBaseMut for VarRawReadBuilder<'_, B>
+where
+    B: BuilderBaseMut,
 {
+    fn base_mut(&mut self) -> &mut BuilderBase {
+        self.base.base_mut()
+    }
+}
+
+impl<'data, B> ReadQueryBuilder<'data> for VarRawReadBuilder<'data, B>
+where
+    B: ReadQueryBuilder<'data>,
+{
+    fn attach_buffers(&mut self) -> TileDBResult<()> {
+        let context = self.base().context();
+        let cquery = **self.base().cquery();
+        for handle in self.raw_read_output.iter_mut() {
+            handle.attach_query(&context, cquery)?;
+        }
+        self.base.attach_buffers()
+    }
 }
@@ -100,11 +100,24 @@ where
     }
 }
 
+impl<T, B> BuilderBaseMut for TypedReadBuilder<'_, T, B>
+where
+    T: ReadResult,
+    B: BuilderBaseMut,
+{
+    fn base_mut(&mut self) -> &mut BuilderBase {
+        self.base.base_mut()
+    }
+}
+
 impl<'data, T, B> ReadQueryBuilder<'data> for TypedReadBuilder<'data, T, B>
 where
     T: ReadResult,
     B: ReadQueryBuilder<'data>,
 {
+    fn attach_buffers(&mut self) -> TileDBResult<()> {
+        self.base.attach_buffers()
+    }
 }
 
 mod impls {
@@ -131,4 +144,22 @@ mod impls {
     impl ReadResult for (Vec<String>, Vec<u8>) {
         type Constructor = Self;
     }
+
+    impl<C> ReadResult for Vec<Option<C>>
+    where
+        C: CellValue,
+    {
+        type Constructor = Self;
+    }
+
+    impl<C> ReadResult for Vec<Option<Vec<C>>>
+    where
+        C: CellValue,
+    {
+        type Constructor = Self;
+    }
+
+    impl ReadResult for Vec<Option<String>> {
+        type Constructor = Self;
+    }
 }
@@ -0,0 +1,634 @@
+//! Reading and writing structs where each struct value is one cell.
+//!
+//! Deriving [TileDBRecord] for a struct maps each of its fields onto the
+//! dimension or attribute of the same name. The type of each struct field
+//! determines the cells it can hold:
+//! * a primitive type such as `i32` or `f64` holds one value per cell;
+//! * `[T; N]` holds `N` values per cell;
+//! * `Vec<T>` and `String` hold a variable number of values per cell;
+//! * `Option<T>` holds the cells of a nullable field.
+//!
+//! Reading a `String` cell which is not valid UTF-8 returns an error.
+//!
+//! The generated code refers to this crate as `tiledb_api`. If the crate is
+//! imported under another name, set the path to it using
+//! `#[tiledb(crate = "...")]` on the struct.
+//!
+//! ```no_run
+//! # use tiledb_api::{Array, Context, Result as TileDBResult};
+//! # use tiledb_api::array::Mode;
+//! # use tiledb_api::query::{
+//! #     Query, QueryBuilder, ReadBuilder, ReadQuery, ReadQueryBuilder,
+//! #     WriteBuilder,
+//! # };
+//! use tiledb_api::query::record::TileDBRecord;
+//!
+//! #[derive(TileDBRecord)]
+//! struct Observation {
+//!     id: i64,
+//!     #[tiledb(name = "label")]
+//!     name: String,
+//!     position: [f32; 2],
+//!     score: Option<f64>,
+//! }
+//!
+//! # fn copy(ctx: &Context, uri: &str) -> TileDBResult<()> {
+//! let observations: Vec<Observation> = {
+//!     let mut q = ReadBuilder::new(Array::open(ctx, uri, Mode::Read)?)?
+//!         .register_records::<Observation>()?
+//!         .build();
+//!     q.execute()?.0
+//! };
+//!
+//! let q = WriteBuilder::new(Array::open(ctx, uri, Mode::Write)?)?
+//!     .records(&observations)?
+//!     .build();
+//! q.submit().and_then(|_| q.finalize())?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::rc::Rc;
+
+use anyhow::anyhow;
+
+use crate::array::schema::Field;
+use crate::array::{CellValNum, Schema};
+use crate::error::{DatatypeError, Error};
+use crate::query::buffer::{CellStructure, QueryBuffers, TypedQueryBuffers};
+use crate::query::read::output::TypedRawReadOutput;
+use crate::query::read::ReadCallbackVarArg;
+use crate::query::write::input::RecordProvider;
+use crate::query::CellValue;
+use crate::Result as TileDBResult;
+
+pub use tiledb_proc_macro::TileDBRecord;
+
+/// A struct whose fields correspond to the fields of an array.
+///
+/// This should be derived using `#[derive(TileDBRecord)]`
+/// rather than implemented by hand.
+pub trait TileDBRecord: Sized {
+    /// Returns the names of the array fields, in the order of the struct fields.
+    fn field_names() -> Vec<&'static str>;
+
+    /// Returns an error if any struct field cannot hold
+    /// the cells of its array field.
+    fn check_schema(schema: &Schema) -> TileDBResult<()>;
+
+    /// Converts the results of one step of a read query into records.
+    /// The results are in the order of [field_names](Self::field_names).
+    fn from_read_output(
+        output: Vec<TypedRawReadOutput>,
+    ) -> TileDBResult<Vec<Self>>;
+
+    /// Converts records into write query input for each field,
+    /// in the order of [field_names](Self::field_names).
+    fn query_buffers(
+        records: &[Self],
+    ) -> TileDBResult<Vec<TypedQueryBuffers<'static>>>;
+}
+
+/// A type which holds the values of one non-null cell.
+pub trait RecordCell: Sized {
+    type Unit: CellValue;
+
+    fn cell_val_num() -> TileDBResult<CellValNum>;
+
+    /// Appends the values of this cell to `values`.
+    fn to_values(&self, values: &mut Vec<Self::Unit>);
+
+    /// Constructs a cell from its values.
+    fn from_values(values: &[Self::Unit]) -> TileDBResult<Self>;
+}
+
+macro_rules! record_cell_primitive {
+    ($($T:ty),+) => {
+        $(
+            impl RecordCell for $T {
+                type Unit = $T;
+
+                fn cell_val_num() -> TileDBResult<CellValNum> {
+                    Ok(CellValNum::single())
+                }
+
+                fn to_values(&self, values: &mut Vec<Self::Unit>) {
+                    values.push(*self)
+                }
+
+                fn from_values(values: &[Self::Unit]) -> TileDBResult<Self> {
+                    Ok(values[0])
+                }
+            }
+        )+
+    };
+}
+
+record_cell_primitive!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+impl<C, const N: usize> RecordCell for [C; N]
+where
+    C: CellValue,
+{
+    type Unit = C;
+
+    fn cell_val_num() -> TileDBResult<CellValNum> {
+        Ok(CellValNum::try_from(N as u32)?)
+    }
+
+    fn to_values(&self, values: &mut Vec<Self::Unit>) {
+        values.extend_from_slice(self)
+    }
+
+    fn from_values(values: &[Self::Unit]) -> TileDBResult<Self> {
+        Ok(std::array::from_fn(|i| values[i]))
+    }
+}
+
+impl<C> RecordCell for Vec<C>
+where
+    C: CellValue,
+{
+    type Unit = C;
+
+    fn cell_val_num() -> TileDBResult<CellValNum> {
+        Ok(CellValNum::Var)
+    }
+
+    fn to_values(&self, values: &mut Vec<Self::Unit>) {
+        values.extend_from_slice(self)
+    }
+
+    fn from_values(values: &[Self::Unit]) -> TileDBResult<Self> {
+        Ok(values.to_vec())
+    }
+}
+
+impl RecordCell for String {
+    type Unit = u8;
+
+    fn cell_val_num() -> TileDBResult<CellValNum> {
+        Ok(CellValNum::Var)
+    }
+
+    fn to_values(&self, values: &mut Vec<Self::Unit>) {
+        values.extend_from_slice(self.as_bytes())
+    }
+
+    fn from_values(values: &[Self::Unit]) -> TileDBResult<Self> {
+        String::from_utf8(values.to_vec()).map_err(|e| {
+            Error::Deserialization(
+                "String cell is not valid UTF-8".to_owned(),
+                anyhow!(e),
+            )
+        })
+    }
+}
+
+/// A type which holds the cells of one field of a [TileDBRecord].
+///
+/// This is implemented for each [RecordCell] type `T`, for non-nullable
+/// fields, and for `Option<T>`, for nullable fields.
+pub trait RecordField: Sized {
+    /// Returns an error if this type cannot hold the cells of `field`.
+    fn check_field(field: &Field) -> TileDBResult<()>;
+
+    fn query_buffers<'a, I>(
+        cells: I,
+    ) -> TileDBResult<TypedQueryBuffers<'static>>
+    where
+        I: IntoIterator<Item = &'a Self>,
+        Self: 'a;
+
+    fn from_read_output(output: TypedRawReadOutput) -> TileDBResult<Vec<Self>>;
+}
+
+impl<T> RecordField for T
+where
+    T: RecordCell,
+    for<'data> TypedQueryBuffers<'data>: From<QueryBuffers<'data, T::Unit>>,
+    for<'data> QueryBuffers<'data, T::Unit>:
+        TryFrom<TypedQueryBuffers<'data>, Error = ()>,
+{
+    fn check_field(field: &Field) -> TileDBResult<()> {
+        check_field_impl::<T>(field, false)
+    }
+
+    fn query_buffers<'a, I>(
+        cells: I,
+    ) -> TileDBResult<TypedQueryBuffers<'static>>
+    where
+        I: IntoIterator<Item = &'a Self>,
+        Self: 'a,
+    {
+        Ok(
+            query_buffers_impl::<T, _>(cells.into_iter().map(Some), false)?
+                .into(),
+        )
+    }
+
+    fn from_read_output(output: TypedRawReadOutput) -> TileDBResult<Vec<Self>> {
+        from_read_output_impl::<T>(output)?
+            .into_iter()
+            .map(|cell| cell.ok_or(Error::UnexpectedValidity))
+            .collect()
+    }
+}
+
+impl<T> RecordField for Option<T>
+where
+    T: RecordCell,
+    for<'data> TypedQueryBuffers<'data>: From<QueryBuffers<'data, T::Unit>>,
+    for<'data> QueryBuffers<'data, T::Unit>:
+        TryFrom<TypedQueryBuffers<'data>, Error = ()>,
+{
+    fn check_field(field: &Field) -> TileDBResult<()> {
+        check_field_impl::<T>(field, true)
+    }
+
+    fn query_buffers<'a, I>(
+        cells: I,
+    ) -> TileDBResult<TypedQueryBuffers<'static>>
+    where
+        I: IntoIterator<Item = &'a Self>,
+        Self: 'a,
+    {
+        Ok(query_buffers_impl::<T, _>(
+            cells.into_iter().map(Option::as_ref),
+            true,
+        )?
+        .into())
+    }
+
+    fn from_read_output(output: TypedRawReadOutput) -> TileDBResult<Vec<Self>> {
+        from_read_output_impl::<T>(output)
+    }
+}
+
+fn check_field_impl<T>(field: &Field, is_nullable: bool) -> TileDBResult<()>
+where
+    T: RecordCell,
+{
+    let datatype = field.datatype()?;
+    if !datatype.is_compatible_type::<T::Unit>() {
+        return Err(Error::Datatype(
+            DatatypeError::physical_type_incompatible::<T::Unit>(datatype),
+        ));
+    }
+
+    let cell_val_num = T::cell_val_num()?;
+    let field_cell_val_num = field.cell_val_num()?;
+    if cell_val_num != field_cell_val_num {
+        return Err(Error::UnexpectedCellStructure {
+            expected: field_cell_val_num,
+            found: cell_val_num,
+        });
+    }
+
+    let field_is_nullable = field.nullability()?;
+    if is_nullable != field_is_nullable {
+        return Err(Error::InvalidArgument(anyhow!(
+            "Field '{}' is {}, but the record field type {} `Option`",
+            field.name()?,
+            if field_is_nullable {
+                "nullable"
+            } else {
+                "not nullable"
+            },
+            if is_nullable { "is" } else { "is not" }
+        )));
+    }
+
+    Ok(())
+}
+
+fn query_buffers_impl<'a, T, I>(
+    cells: I,
+    is_nullable: bool,
+) -> TileDBResult<QueryBuffers<'static, T::Unit>>
+where
+    T: RecordCell + 'a,
+    I: Iterator<Item = Option<&'a T>>,
+{
+    let cell_val_num = T::cell_val_num()?;
+
+    let mut data = vec![];
+    let mut offsets = vec![0u64];
+    let mut validity = vec![];
+    for cell in cells {
+        if let Some(cell) = cell {
+            cell.to_values(&mut data);
+            validity.push(1);
+        } else {
+            // null cells of fixed-size fields still occupy their values
+            if let CellValNum::Fixed(nz) = cell_val_num {
+                data.resize(data.len() + nz.get() as usize, Default::default());
+            }
+            validity.push(0);
+        }
+        offsets.push(data.len() as u64);
+    }
+
+    let cell_structure = match cell_val_num {
+        CellValNum::Fixed(nz) => CellStructure::Fixed(nz),
+        CellValNum::Var => CellStructure::Var(offsets.into()),
+    };
+
+    Ok(QueryBuffers {
+        data: data.into(),
+        cell_structure,
+        validity: if is_nullable {
+            Some(validity.into())
+        } else {
+            None
+        },
+    })
+}
+
+fn from_read_output_impl<T>(
+    output: TypedRawReadOutput,
+) -> TileDBResult<Vec<Option<T>>>
+where
+    T: RecordCell,
+    for<'data> QueryBuffers<'data, T::Unit>:
+        TryFrom<TypedQueryBuffers<'data>, Error = ()>,
+{
+    let ncells = output.ncells;
+    let datatype = output.datatype;
+    let qb =
+        QueryBuffers::<T::Unit>::try_from(output.buffers).map_err(|_| {
+            Error::Datatype(
+                DatatypeError::physical_type_incompatible::<T::Unit>(datatype),
+            )
+        })?;
+
+    let data = qb.data.as_ref();
+    let cell = |c: usize| -> &[T::Unit] {
+        match qb.cell_structure {
+            CellStructure::Fixed(nz) => {
+                let len = nz.get() as usize;
+                &data[c * len..(c + 1) * len]
+            }
+            CellStructure::Var(ref offsets) => {
+                &data[offsets[c] as usize..offsets[c + 1] as usize]
+            }
+        }
+    };
+
+    (0..ncells)
+        .map(|c| {
+            let is_valid =
+                qb.validity.as_ref().map(|v| v[c] != 0).unwrap_or(true);
+            if is_valid {
+                T::from_values(cell(c)).map(Some)
+            } else {
+                Ok(None)
+            }
+        })
+        .collect()
+}
+
+#[doc(hidden)]
+pub fn read_field<T>(
+    name: &str,
+    output: Option<TypedRawReadOutput>,
+) -> TileDBResult<Vec<T>>
+where
+    T: RecordField,
+{
+    let output = output.ok_or_else(|| {
+        Error::Internal(format!("Missing read output for field '{}'", name))
+    })?;
+    T::from_read_output(output)
+}
+
+#[doc(hidden)]
+pub fn next_cell<T, I>(name: &str, cells: &mut I) -> TileDBResult<T>
+where
+    I: Iterator<Item = T>,
+{
+    cells.next().ok_or_else(|| {
+        Error::Internal(format!(
+            "Missing read output cell for field '{}'",
+            name
+        ))
+    })
+}
+
+/// Query callback which accumulates the records read by each step.
+pub struct RecordsConstructor<R> {
+    records: Vec<R>,
+}
+
+impl<R> Default for RecordsConstructor<R> {
+    fn default() -> Self {
+        RecordsConstructor { records: vec![] }
+    }
+}
+
+impl<R> ReadCallbackVarArg for RecordsConstructor<R>
+where
+    R: TileDBRecord,
+{
+    type Intermediate = ();
+    type Final = Vec<R>;
+    type Error = Error;
+
+    fn intermediate_result(
+        &mut self,
+        args: Vec<TypedRawReadOutput>,
+    ) -> Result<Self::Intermediate, Self::Error> {
+        self.records.extend(R::from_read_output(args)?);
+        Ok(())
+    }
+
+    fn final_result(
+        mut self,
+        args: Vec<TypedRawReadOutput>,
+    ) -> Result<Self::Final, Self::Error> {
+        self.intermediate_result(args)?;
+        Ok(self.records)
+    }
+
+    fn cleared(&self) -> Option<Self> {
+        Some(Self::default())
+    }
+}
+
+impl<'data, R> RecordProvider<'data> for Vec<R>
+where
+    R: TileDBRecord,
+{
+    type Iter =
+        std::vec::IntoIter<TileDBResult<(String, TypedQueryBuffers<'data>)>>;
+
+    fn tiledb_inputs(&'data self, schema: Rc<Schema>) -> Self::Iter {
+        let inputs = R::check_schema(&schema)
+            .and_then(|_| R::query_buffers(self))
+            .map(|buffers| {
+                R::field_names()
+                    .into_iter()
+                    .map(String::from)
+                    .zip(buffers)
+                    .map(Ok)
+                    .collect::<Vec<_>>()
+            });
+        match inputs {
+            Ok(inputs) => inputs.into_iter(),
+            Err(e) => vec![Err(e)].into_iter(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::{ReadQueryBuilder, WriteBuilder};
+    use crate::tests::examples::{quickstart, TestArray};
+    use crate::tests::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, TileDBRecord)]
+    #[tiledb(crate = "crate")]
+    struct Cell {
+        rows: i32,
+        cols: i32,
+        #[tiledb(name = "a")]
+        value: i32,
+        name: String,
+        position: [f32; 2],
+        score: Option<f64>,
+        tags: Option<Vec<i32>>,
+    }
+
+    fn test_array(name: &str) -> TileDBResult<TestArray> {
+        TestArray::new(name, {
+            let mut b = quickstart::Builder::new(ArrayType::Sparse);
+            b.schema.attributes.extend([
+                AttributeData {
+                    name: "name".to_owned(),
+                    datatype: Datatype::StringUtf8,
+                    cell_val_num: Some(CellValNum::Var),
+                    ..Default::default()
+                },
+                AttributeData {
+                    name: "position".to_owned(),
+                    datatype: Datatype::Float32,
+                    cell_val_num: Some(CellValNum::try_from(2).unwrap()),
+                    ..Default::default()
+                },
+                AttributeData {
+                    name: "score".to_owned(),
+                    datatype: Datatype::Float64,
+                    nullability: Some(true),
+                    ..Default::default()
+                },
+                AttributeData {
+                    name: "tags".to_owned(),
+                    datatype: Datatype::Int32,
+                    cell_val_num: Some(CellValNum::Var),
+                    nullability: Some(true),
+                    ..Default::default()
+                },
+            ]);
+            b.build().into()
+        })
+    }
+
+    fn cells() -> Vec<Cell> {
+        (0..16)
+            .map(|i| Cell {
+                rows: i / 4 + 1,
+                cols: i % 4 + 1,
+                value: i * 10,
+                name: "x".repeat(i as usize),
+                position: [i as f32, -i as f32],
+                score: (i % 3 != 0).then_some(i as f64 / 2.0),
+                tags: (i % 2 == 0).then(|| (0..i).collect()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn string_from_values() {
+        assert_eq!("abc", String::from_values(b"abc").unwrap());
+        assert!(matches!(
+            String::from_values(&[b'a', 0xff]),
+            Err(Error::Deserialization(_, _))
+        ));
+    }
+
+    #[test]
+    fn field_names() {
+        assert_eq!(
+            vec!["rows", "cols", "a", "name", "position", "score", "tags"],
+            Cell::field_names()
+        );
+    }
+
+    #[test]
+    fn write_read_round_trip() -> TileDBResult<()> {
+        let mut array = test_array("record_write_read_round_trip")?;
+
+        let expect = cells();
+        {
+            let q = WriteBuilder::new(array.for_write()?)?
+                .records(&expect)?
+                .build();
+            q.submit()?;
+            q.finalize()?;
+        }
+
+        let mut q = ReadBuilder::new(array.for_read()?)?
+            .layout(QueryLayout::RowMajor)?
+            .register_records::<Cell>()?
+            .build();
+        let (records, _) = q.execute()?;
+        assert_eq!(expect, records);
+
+        Ok(())
+    }
+
+    #[test]
+    fn schema_mismatch() -> TileDBResult<()> {
+        #[derive(TileDBRecord)]
+        #[tiledb(crate = "crate")]
+        struct WrongDatatype {
+            #[allow(dead_code)]
+            a: u64,
+        }
+
+        #[derive(TileDBRecord)]
+        #[tiledb(crate = "crate")]
+        struct WrongNullability {
+            #[allow(dead_code)]
+            a: Option<i32>,
+        }
+
+        #[derive(TileDBRecord)]
+        #[tiledb(crate = "crate")]
+        struct WrongCellValNum {
+            #[allow(dead_code)]
+            a: Vec<i32>,
+        }
+
+        #[derive(TileDBRecord)]
+        #[tiledb(crate = "crate")]
+        struct NoSuchField {
+            #[allow(dead_code)]
+            b: i32,
+        }
+
+        let array = TestArray::new(
+            "record_schema_mismatch",
+            quickstart::Builder::new(ArrayType::Sparse).build().into(),
+        )?;
+
+        let read = || ReadBuilder::new(array.for_read().unwrap());
+        assert!(read()?.register_records::<WrongDatatype>().is_err());
+        assert!(read()?.register_records::<WrongNullability>().is_err());
+        assert!(read()?.register_records::<WrongCellValNum>().is_err());
+        assert!(read()?.register_records::<NoSuchField>().is_err());
+
+        Ok(())
+    }
+}
@@ -378,9 +378,15 @@ mod tests {
     use super::*;
     use crate::array::{Array, ArrayOpener, Mode};
     use crate::error::Error;
+    use crate::query::condition::strategy::{
+        prop_query_condition, QueryConditionParameters,
+    };
+    use crate::query::condition::{Literal, SetMembers};
     use crate::query::{
-        Query, QueryBuilder, ReadBuilder, ReadQuery, WriteBuilder,
+        Query, QueryBuilder, QueryConditionExpr, ReadBuilder, ReadQuery,
+        WriteBuilder,
     };
+    use crate::Datatype;
     use crate::{Context, Factory};
 
     struct DenseCellsAccumulator {
@@ -745,6 +751,207 @@ mod tests {
         Ok(())
     }
 
+    /// Returns strategies which compare the field `name` with `value`
+    /// or test whether it is a member of a set of values.
+    fn prop_field_comparisons<T>(
+        name: String,
+        value: BoxedStrategy<T>,
+    ) -> [BoxedStrategy<QueryConditionExpr>; 2]
+    where
+        T: Clone + std::fmt::Debug + Into<Literal> + 'static,
+        for<'a> &'a [T]: Into<SetMembers>,
+    {
+        let name_cmp = name.clone();
+        let comparison = (value.clone(), 0..6usize)
+            .prop_map(move |(value, op)| {
+                let field = QueryConditionExpr::field(&name_cmp);
+                match op {
+                    0 => field.lt(value),
+                    1 => field.le(value),
+                    2 => field.eq(value),
+                    3 => field.ne(value),
+                    4 => field.ge(value),
+                    _ => field.gt(value),
+                }
+            })
+            .boxed();
+        let membership =
+            (proptest::collection::vec(value, 1..=4), any::<bool>())
+                .prop_map(move |(members, is_in)| {
+                    let field = QueryConditionExpr::field(&name);
+                    if is_in {
+                        field.is_in(members.as_slice())
+                    } else {
+                        field.not_in(members.as_slice())
+                    }
+                })
+                .boxed();
+        [comparison, membership]
+    }
+
+    /// Returns a strategy which produces query conditions over the attributes
+    /// of `schema` which libtiledb can evaluate, or `None` if there are no
+    /// such attributes. Values are drawn from `cells` as well as at random
+    /// so that predicates are satisfied by some cells and not others.
+    fn prop_schema_query_condition(
+        schema: &SchemaData,
+        cells: &Cells,
+    ) -> Option<BoxedStrategy<QueryConditionExpr>> {
+        use proptest::sample::select;
+        use proptest::strategy::Union;
+
+        let mut leaves = vec![];
+        for attr in schema.attributes.iter() {
+            if attr.enumeration.is_some() {
+                continue;
+            }
+            let data = &cells.fields()[&attr.name];
+            let cell_val_num = attr.cell_val_num.unwrap_or_default();
+
+            if cell_val_num.is_single_valued()
+                && (attr.datatype.is_integral_type()
+                    || attr.datatype.is_real_type()
+                    || attr.datatype.is_datetime_type()
+                    || attr.datatype.is_time_type())
+            {
+                typed_field_data_go!(
+                    data,
+                    DT,
+                    ref values,
+                    {
+                        let value = if values.is_empty() {
+                            any::<DT>().boxed()
+                        } else {
+                            prop_oneof![select(values.clone()), any::<DT>()]
+                                .boxed()
+                        };
+                        leaves.extend(prop_field_comparisons(
+                            attr.name.clone(),
+                            value,
+                        ))
+                    },
+                    unreachable!("{:?} has multiple values per cell", values)
+                )
+            } else if cell_val_num.is_var_sized()
+                && matches!(
+                    attr.datatype,
+                    Datatype::StringAscii | Datatype::StringUtf8
+                )
+            {
+                let FieldData::VecUInt8(ref values) = data else {
+                    unreachable!()
+                };
+                let strings = values
+                    .iter()
+                    .filter_map(|v| String::from_utf8(v.clone()).ok())
+                    .collect::<Vec<String>>();
+                let value = if strings.is_empty() {
+                    any::<String>().boxed()
+                } else {
+                    prop_oneof![select(strings), any::<String>()].boxed()
+                };
+                leaves.extend(prop_field_comparisons(attr.name.clone(), value))
+            } else {
+                continue;
+            }
+
+            if attr.nullability.unwrap_or(false) {
+                let name = attr.name.clone();
+                leaves.push(
+                    any::<bool>()
+                        .prop_map(move |is_null| {
+                            let field = QueryConditionExpr::field(&name);
+                            if is_null {
+                                field.is_null()
+                            } else {
+                                field.not_null()
+                            }
+                        })
+                        .boxed(),
+                );
+            }
+        }
+
+        if leaves.is_empty() {
+            None
+        } else {
+            let params = QueryConditionParameters {
+                max_depth: 3,
+                max_predicates: 8,
+                ..Default::default()
+            };
+            Some(prop_query_condition(Union::new(leaves), &params).boxed())
+        }
+    }
+
+    /// Writes `write` and then reads it back using each of `conditions`,
+    /// checking the result against the cells which satisfy the condition
+    /// according to [QueryConditionExpr::evaluate].
+    fn do_query_condition_readback(
+        ctx: &Context,
+        schema_spec: Rc<SchemaData>,
+        write: WriteInput,
+        conditions: Vec<QueryConditionExpr>,
+    ) -> TileDBResult<()> {
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let uri = test_uri
+            .with_path("array")
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        let schema_in = schema_spec
+            .create(ctx)
+            .expect("Error constructing arbitrary schema");
+        Array::create(ctx, &uri, schema_in).expect("Error creating array");
+
+        {
+            let array = Array::open(ctx, &uri, Mode::Write)
+                .expect("Error opening array");
+            let write_query = write
+                .attach_write(
+                    WriteBuilder::new(array)
+                        .expect("Error building write query"),
+                )
+                .expect("Error building write query")
+                .build();
+            write_query.submit().expect("Error running write query");
+            let _ = write_query
+                .finalize()
+                .expect("Error finalizing write query");
+        }
+
+        let sort_keys = schema_spec
+            .fields()
+            .map(|f| f.name().to_owned())
+            .collect::<Vec<String>>();
+
+        for qc in conditions {
+            let expect = {
+                let matches = write
+                    .cells()
+                    .evaluate(&qc)
+                    .expect("Error evaluating query condition");
+                write.cells().filter(&matches).sorted(&sort_keys)
+            };
+
+            let array = Array::open(ctx, &uri, Mode::Read)
+                .expect("Error opening array");
+            let mut read = write
+                .attach_read(ReadBuilder::new(array).unwrap())
+                .unwrap()
+                .query_condition(qc.clone())
+                .unwrap()
+                .build();
+
+            let (mut cells, _) = read.execute().unwrap();
+            cells.sort(&sort_keys);
+
+            assert_eq!(expect, cells, "Query condition: {}", qc);
+        }
+
+        Ok(())
+    }
+
     /// Test that a single write can be read back correctly
     #[test]
     fn write_once_readback() -> TileDBResult<()> {
@@ -798,4 +1005,135 @@ mod tests {
 
         Ok(())
     }
+
+    /// Test that reading with a query condition produces the cells
+    /// which satisfy the condition according to
+    /// [QueryConditionExpr::evaluate], so that it agrees with libtiledb
+    #[test]
+    fn query_condition_readback() -> TileDBResult<()> {
+        let ctx = Context::new().expect("Error creating context");
+
+        let schema_req =
+            query_write_schema_requirements(Some(ArrayType::Sparse));
+
+        let strategy = any_with::<SchemaData>(Rc::new(schema_req))
+            .prop_flat_map(|schema| {
+                let schema = Rc::new(schema);
+                (
+                    Just(Rc::clone(&schema)),
+                    any_with::<WriteInput>(WriteParameters::default_for(
+                        schema,
+                    )),
+                )
+            })
+            .prop_flat_map(|(schema, write)| {
+                let conditions =
+                    match prop_schema_query_condition(&schema, write.cells()) {
+                        Some(qc) => {
+                            proptest::collection::vec(qc, 1..=8).boxed()
+                        }
+                        None => Just(vec![]).boxed(),
+                    };
+                (Just(schema), Just(write), conditions)
+            });
+
+        proptest!(|((schema_spec, write, conditions) in strategy)| {
+            do_query_condition_readback(&ctx, schema_spec, write, conditions)?;
+        });
+
+        Ok(())
+    }
+
+    /// Test that libtiledb and [QueryConditionExpr::evaluate] agree on
+    /// which predicates a null value satisfies, including under negation.
+    /// The generated writes above do not contain nulls.
+    #[test]
+    fn query_condition_nulls() -> TileDBResult<()> {
+        use std::collections::HashMap;
+
+        use tiledb_pod::array::attribute::AttributeData;
+
+        use crate::query::condition::eval::CellValue;
+        use crate::query::QueryLayout;
+        use crate::tests::examples::{quickstart, TestArray};
+
+        let mut array = TestArray::new("query_condition_nulls", {
+            let mut b = quickstart::Builder::new(ArrayType::Sparse);
+            b.schema.attributes = vec![AttributeData {
+                name: "a".to_owned(),
+                datatype: Datatype::Int32,
+                nullability: Some(true),
+                ..Default::default()
+            }];
+            b.build().into()
+        })?;
+
+        let rows = vec![1, 1, 2, 2, 3, 3];
+        let cols = vec![1, 2, 1, 2, 1, 2];
+        let a = vec![Some(1), None, Some(2), Some(3), None, Some(2)];
+        {
+            let q = WriteBuilder::new(array.for_write()?)?
+                .data("rows", &rows)?
+                .data("cols", &cols)?
+                .data("a", &a)?
+                .build();
+            q.submit()?;
+            q.finalize()?;
+        }
+
+        let field = || QueryConditionExpr::field("a");
+        let conditions = vec![
+            (field().ne(2), vec![0, 1, 3, 4]),
+            (field().not_in([1, 3].as_slice()), vec![1, 2, 4, 5]),
+            (!field().eq(2), vec![0, 1, 3, 4]),
+            (!field().is_in([2].as_slice()), vec![0, 1, 3, 4]),
+            (!field().ne(2), vec![2, 5]),
+            (!field().not_in([1, 3].as_slice()), vec![0, 3]),
+            (!field().lt(3), vec![3]),
+            (!field().is_null(), vec![0, 2, 3, 5]),
+        ];
+
+        for (qc, expect) in conditions {
+            let expect = expect
+                .into_iter()
+                .map(|i| (rows[i], cols[i], a[i]))
+                .collect::<Vec<_>>();
+
+            let evaluated = (0..a.len())
+                .filter(|&i| {
+                    let row = HashMap::from([("a", CellValue::from(a[i]))]);
+                    qc.evaluate(&row).expect("Error evaluating condition")
+                })
+                .map(|i| (rows[i], cols[i], a[i]))
+                .collect::<Vec<_>>();
+            assert_eq!(expect, evaluated, "Query condition: {}", qc);
+
+            let mut q = ReadBuilder::new(array.for_read()?)?
+                .layout(QueryLayout::RowMajor)?
+                .query_condition(qc.clone())?
+                .register_constructor::<_, Vec<i32>>(
+                    "rows",
+                    Default::default(),
+                )?
+                .register_constructor::<_, Vec<i32>>(
+                    "cols",
+                    Default::default(),
+                )?
+                .register_constructor::<_, Vec<Option<i32>>>(
+                    "a",
+                    Default::default(),
+                )?
+                .build();
+            let (a_out, (cols_out, (rows_out, ()))) = q.execute()?;
+            let read = rows_out
+                .into_iter()
+                .zip(cols_out)
+                .zip(a_out)
+                .map(|((r, c), a)| (r, c, a))
+                .collect::<Vec<_>>();
+            assert_eq!(expect, read, "Query condition: {}", qc);
+        }
+
+        Ok(())
+    }
 }
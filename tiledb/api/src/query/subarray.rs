@@ -36,6 +36,9 @@ impl Drop for RawSubarray {
     }
 }
 
+// SAFETY: a subarray is owned by a single query or builder and moves with it.
+unsafe impl Send for RawSubarray {}
+
 pub struct Subarray<'query> {
     schema: Schema,
     raw: RawSubarray,
@@ -304,6 +307,76 @@ where
         Ok(self)
     }
 
+    /// Add a range of label values to the subarray. The range selects
+    /// the coordinates of the labelled dimension whose label value
+    /// is within the range.
+    ///
+    /// Ranges on a dimension and on its label cannot both be added
+    /// to the same subarray.
+    pub fn add_label_range<IntoRange: Into<Range>>(
+        self,
+        label: &str,
+        range: IntoRange,
+    ) -> TileDBResult<Self> {
+        let schema = self.query.base().query.array.schema()?;
+        let dimension_label = schema.dimension_label(label)?;
+
+        let range = range.into();
+        range
+            .check_dimension_compatibility(
+                dimension_label.label_type()?,
+                dimension_label.label_cell_val_num()?,
+            )
+            .map_err(|e| {
+                Error::InvalidArgument(
+                    anyhow!("Invalid range variant for dimension label")
+                        .context(e),
+                )
+            })?;
+
+        let c_subarray = *self.raw;
+        let c_label = cstring!(label);
+
+        match range {
+            Range::Single(range) => {
+                single_value_range_go!(range, _DT, start, end, {
+                    let start = start.to_le_bytes();
+                    let end = end.to_le_bytes();
+                    self.query.base().capi_call(|ctx| unsafe {
+                        ffi::tiledb_subarray_add_label_range(
+                            ctx,
+                            c_subarray,
+                            c_label.as_ptr(),
+                            start.as_ptr() as *const std::ffi::c_void,
+                            end.as_ptr() as *const std::ffi::c_void,
+                            std::ptr::null(),
+                        )
+                    })?;
+                })
+            }
+            Range::Multi(_) => unreachable!(
+                "This is rejected by range.check_dimension_compatibility"
+            ),
+            Range::Var(range) => {
+                var_value_range_go!(range, _DT, start, end, {
+                    self.query.base().capi_call(|ctx| unsafe {
+                        ffi::tiledb_subarray_add_label_range_var(
+                            ctx,
+                            c_subarray,
+                            c_label.as_ptr(),
+                            start.as_ptr() as *const std::ffi::c_void,
+                            start.len() as u64,
+                            end.as_ptr() as *const std::ffi::c_void,
+                            end.len() as u64,
+                        )
+                    })?;
+                })
+            }
+        }
+
+        Ok(self)
+    }
+
     /// Add a list of point ranges to the query.
     pub fn add_point_ranges<Key: Into<LookupKey>, T: PhysicalType>(
         self,
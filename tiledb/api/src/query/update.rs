@@ -0,0 +1,420 @@
+use super::*;
+
+use anyhow::anyhow;
+use tiledb_common::array::CellValNum;
+
+use crate::query::condition::Literal;
+use crate::Datatype;
+
+/// An update query replaces the values of attributes of all cells
+/// which satisfy a query condition.
+///
+/// Updates are experimental in libtiledb. An array can only be opened in
+/// [Mode::Update] if the configuration option
+/// `sm.allow_updates_experimental` is `true`. The updates are recorded
+/// as a new commit and are applied to the cells returned by later reads.
+///
+/// [Mode::Update]: crate::array::Mode::Update
+pub struct UpdateQuery {
+    base: QueryBase,
+    has_condition: bool,
+    num_values: usize,
+}
+
+impl ContextBound for UpdateQuery {
+    fn context(&self) -> Context {
+        self.base.context()
+    }
+}
+
+impl Query for UpdateQuery {
+    fn base(&self) -> &QueryBase {
+        self.base.base()
+    }
+
+    fn finalize(self) -> TileDBResult<Array> {
+        self.base.finalize()
+    }
+}
+
+impl UpdateQuery {
+    pub fn submit(&self) -> TileDBResult<()> {
+        if !self.has_condition {
+            return Err(Error::InvalidArgument(anyhow!(
+                "Update query requires a query condition"
+            )));
+        }
+        if self.num_values == 0 {
+            return Err(Error::InvalidArgument(anyhow!(
+                "Update query requires at least one update value"
+            )));
+        }
+        self.base.do_submit()
+    }
+}
+
+/// Builds an [UpdateQuery] against an array opened in [Mode::Update].
+///
+/// The cells to update are selected using
+/// [query_condition](QueryBuilder::query_condition), which is required.
+/// Each updated attribute is assigned a single value using
+/// [set_value](Self::set_value) or [set_null](Self::set_null).
+///
+/// Updates are experimental; see [UpdateQuery] for the configuration
+/// which they require.
+///
+/// [Mode::Update]: crate::array::Mode::Update
+pub struct UpdateBuilder {
+    base: BuilderBase,
+    has_condition: bool,
+    num_values: usize,
+}
+
+impl ContextBound for UpdateBuilder {
+    fn context(&self) -> Context {
+        self.base.context()
+    }
+}
+
+impl QueryBuilder for UpdateBuilder {
+    type Query = UpdateQuery;
+
+    fn base(&self) -> &BuilderBase {
+        &self.base
+    }
+
+    fn query_condition(mut self, qc: QueryConditionExpr) -> TileDBResult<Self> {
+        self.base.set_query_condition(&qc)?;
+        self.has_condition = true;
+        Ok(self)
+    }
+
+    fn build(self) -> Self::Query {
+        UpdateQuery {
+            base: self.base.build(),
+            has_condition: self.has_condition,
+            num_values: self.num_values,
+        }
+    }
+}
+
+impl BuilderBaseMut for UpdateBuilder {
+    fn base_mut(&mut self) -> &mut BuilderBase {
+        &mut self.base
+    }
+}
+
+impl UpdateBuilder {
+    pub fn new(array: Array) -> TileDBResult<Self> {
+        Ok(UpdateBuilder {
+            base: BuilderBase::new(array, QueryType::Update)?,
+            has_condition: false,
+            num_values: 0,
+        })
+    }
+
+    /// Sets the value which `field` will take for each updated cell.
+    ///
+    /// Returns an error if `field` is not an attribute of the array
+    /// or if `value` does not match the attribute's datatype
+    /// and number of values per cell.
+    pub fn set_value<S, V>(self, field: S, value: V) -> TileDBResult<Self>
+    where
+        S: AsRef<str>,
+        V: Into<Literal>,
+    {
+        self.add_update_value(field.as_ref(), Some(&value.into()))
+    }
+
+    /// Sets `field` to null for each updated cell.
+    ///
+    /// Returns an error if `field` is not a nullable attribute of the array.
+    pub fn set_null<S>(self, field: S) -> TileDBResult<Self>
+    where
+        S: AsRef<str>,
+    {
+        self.add_update_value(field.as_ref(), None)
+    }
+
+    fn add_update_value(
+        mut self,
+        field: &str,
+        value: Option<&Literal>,
+    ) -> TileDBResult<Self> {
+        {
+            let schema = self.base().array().schema()?;
+            let schema_field = schema.field(field)?;
+            if !schema_field.is_attribute() {
+                return Err(Error::InvalidArgument(anyhow!(
+                    "Cannot update dimension '{}'",
+                    field
+                )));
+            }
+
+            if let Some(value) = value {
+                check_update_value(
+                    field,
+                    schema_field.datatype()?,
+                    schema_field.cell_val_num()?,
+                    value,
+                )?;
+            } else if !schema_field.nullability()? {
+                return Err(Error::InvalidArgument(anyhow!(
+                    "Cannot set non-nullable attribute '{}' to null",
+                    field
+                )));
+            }
+        }
+
+        // libtiledb copies the value, so it need not outlive this call
+        let bytes = value.map(|v| v.to_bytes());
+        let (c_value, c_size) = match bytes {
+            Some(ref bytes) => (
+                bytes.as_ptr() as *const std::ffi::c_void,
+                bytes.len() as u64,
+            ),
+            None => (std::ptr::null(), 0),
+        };
+
+        let c_query = **self.base().cquery();
+        let c_name = cstring!(field);
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_query_add_update_value(
+                ctx,
+                c_query,
+                c_name.as_ptr(),
+                c_value,
+                c_size,
+            )
+        })?;
+
+        self.num_values += 1;
+        Ok(self)
+    }
+}
+
+/// Checks that `value` can be written into a cell of an attribute
+/// with the given `datatype` and `cell_val_num`.
+fn check_update_value(
+    field: &str,
+    datatype: Datatype,
+    cell_val_num: CellValNum,
+    value: &Literal,
+) -> TileDBResult<()> {
+    let compatible = match value {
+        Literal::UInt8(_) => datatype.is_compatible_type::<u8>(),
+        Literal::UInt16(_) => datatype.is_compatible_type::<u16>(),
+        Literal::UInt32(_) => datatype.is_compatible_type::<u32>(),
+        Literal::UInt64(_) => datatype.is_compatible_type::<u64>(),
+        Literal::Int8(_) => datatype.is_compatible_type::<i8>(),
+        Literal::Int16(_) => datatype.is_compatible_type::<i16>(),
+        Literal::Int32(_) => datatype.is_compatible_type::<i32>(),
+        Literal::Int64(_) => datatype.is_compatible_type::<i64>(),
+        Literal::Float32(_) => datatype.is_compatible_type::<f32>(),
+        Literal::Float64(_) => datatype.is_compatible_type::<f64>(),
+        Literal::String(_) => datatype.is_string_type(),
+    };
+    if !compatible {
+        return Err(Error::InvalidArgument(anyhow!(
+            "Update value {} is not compatible with datatype {} of attribute '{}'",
+            value,
+            datatype,
+            field
+        )));
+    }
+
+    let cell_val_num_ok = match value {
+        Literal::String(s) => match cell_val_num {
+            CellValNum::Var => true,
+            CellValNum::Fixed(nz) => nz.get() as usize == s.len(),
+        },
+        _ => cell_val_num.is_single_valued(),
+    };
+    if !cell_val_num_ok {
+        return Err(Error::InvalidArgument(anyhow!(
+            "Update value {} does not fit in cells of attribute '{}' ({:?})",
+            value,
+            field,
+            cell_val_num
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use uri::{self, TestArrayUri};
+
+    use super::*;
+    use crate::array::{
+        ArrayType, AttributeBuilder, DimensionBuilder, DomainBuilder, Mode,
+        SchemaBuilder,
+    };
+    use crate::query::buffer::{Buffer, QueryBuffers};
+    use crate::query::condition::QueryConditionExpr as QC;
+    use crate::query::{
+        ReadBuilder, ReadQuery, ReadQueryBuilder, WriteBuilder,
+    };
+
+    /// Returns a context which permits opening arrays in [Mode::Update].
+    fn update_context() -> TileDBResult<Context> {
+        let mut config = crate::config::Config::new()?;
+        config.set("sm.allow_updates_experimental", "true")?;
+        Ok(Context::from_config(&config)?)
+    }
+
+    /// Creates a sparse array with dimension "id" and attributes
+    /// "a" (Int32), "b" (var-sized StringAscii), and "c" (nullable Float64),
+    /// and writes ten cells into it.
+    fn create_and_write(
+        test_uri: &dyn TestArrayUri,
+        ctx: &Context,
+    ) -> TileDBResult<String> {
+        let domain = {
+            let id = DimensionBuilder::new(
+                ctx,
+                "id",
+                Datatype::Int32,
+                ([0, 9], 10),
+            )?
+            .build();
+            DomainBuilder::new(ctx)?.add_dimension(id)?.build()
+        };
+        let schema = SchemaBuilder::new(ctx, ArrayType::Sparse, domain)?
+            .add_attribute(
+                AttributeBuilder::new(ctx, "a", Datatype::Int32)?.build(),
+            )?
+            .add_attribute(
+                AttributeBuilder::new(ctx, "b", Datatype::StringAscii)?
+                    .var_sized()?
+                    .build(),
+            )?
+            .add_attribute(
+                AttributeBuilder::new(ctx, "c", Datatype::Float64)?
+                    .nullability(true)?
+                    .build(),
+            )?
+            .build()?;
+
+        let uri = test_uri
+            .with_path("update")
+            .map_err(|e| Error::Other(e.to_string()))?;
+        Array::create(ctx, &uri, schema)?;
+
+        let id = (0..10).collect::<Vec<i32>>();
+        let a = (0..10).map(|i| i * 10).collect::<Vec<i32>>();
+        let b = (0..10).map(|i| format!("b{}", i)).collect::<Vec<String>>();
+        let c = QueryBuffers {
+            data: Buffer::Owned(
+                (0..10).map(|i| i as f64).collect::<Vec<f64>>().into(),
+            ),
+            cell_structure: Default::default(),
+            validity: Some(Buffer::Owned(vec![1u8; 10].into())),
+        };
+
+        let w = WriteBuilder::new(Array::open(ctx, &uri, Mode::Write)?)?
+            .data("id", &id)?
+            .data("a", &a)?
+            .data("b", &b)?
+            .data("c", &c)?
+            .build();
+        w.submit()?;
+        w.finalize()?;
+
+        Ok(uri)
+    }
+
+    type Cells = (Vec<i32>, Vec<i32>, Vec<String>, (Vec<f64>, Vec<u8>));
+
+    /// Reads all cells in global order, which for this one-dimensional
+    /// array is the order of "id". The global order reader is the one which
+    /// applies the update commits of the array to the cells it returns.
+    fn read_all(ctx: &Context, uri: &str) -> TileDBResult<Cells> {
+        let mut q = ReadBuilder::new(Array::open(ctx, uri, Mode::Read)?)?
+            .layout(QueryLayout::Global)?
+            .register_constructor::<_, Vec<i32>>("id", Default::default())?
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?
+            .register_constructor::<_, Vec<String>>("b", Default::default())?
+            .register_constructor::<_, (Vec<f64>, Vec<u8>)>(
+                "c",
+                Default::default(),
+            )?
+            .build();
+        let (c, (b, (a, (id, ())))) = q.execute()?;
+        Ok((id, a, b, c))
+    }
+
+    #[test]
+    fn update_values() -> TileDBResult<()> {
+        let ctx = update_context()?;
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let uri = create_and_write(&test_uri, &ctx)?;
+
+        let q = UpdateBuilder::new(Array::open(&ctx, &uri, Mode::Update)?)?
+            .query_condition(QC::field("a").ge(50i32))?
+            .set_value("a", -1i32)?
+            .set_value("b", "updated")?
+            .set_null("c")?
+            .build();
+        q.submit()?;
+        q.finalize()?;
+
+        let (id, a, b, (c, c_validity)) = read_all(&ctx, &uri)?;
+        assert_eq!((0..10).collect::<Vec<i32>>(), id);
+        for i in 0..5 {
+            assert_eq!(i as i32 * 10, a[i]);
+            assert_eq!(format!("b{}", i), b[i]);
+            assert_eq!(i as f64, c[i]);
+            assert_eq!(1, c_validity[i]);
+        }
+        for i in 5..10 {
+            assert_eq!(-1, a[i]);
+            assert_eq!("updated", b[i]);
+            assert_eq!(0, c_validity[i]);
+        }
+
+        test_uri.close().map_err(|e| Error::Other(e.to_string()))
+    }
+
+    #[test]
+    fn update_invalid() -> TileDBResult<()> {
+        let ctx = update_context()?;
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let uri = create_and_write(&test_uri, &ctx)?;
+
+        // updates must be enabled in the configuration
+        assert!(Array::open(&Context::new()?, &uri, Mode::Update).is_err());
+
+        let builder = || -> TileDBResult<UpdateBuilder> {
+            UpdateBuilder::new(Array::open(&ctx, &uri, Mode::Update)?)
+        };
+
+        // dimensions cannot be updated
+        assert!(builder()?.set_value("id", 1i32).is_err());
+        // no such field
+        assert!(builder()?.set_value("d", 1i32).is_err());
+        // wrong physical type
+        assert!(builder()?.set_value("a", 1u32).is_err());
+        assert!(builder()?.set_value("a", "one").is_err());
+        assert!(builder()?.set_value("b", 1i32).is_err());
+        // non-nullable attribute
+        assert!(builder()?.set_null("a").is_err());
+
+        // condition is required
+        let q = builder()?.set_value("a", 1i32)?.build();
+        assert!(matches!(q.submit(), Err(Error::InvalidArgument(_))));
+
+        // at least one value is required
+        let q = builder()?.query_condition(QC::field("a").lt(5i32))?.build();
+        assert!(matches!(q.submit(), Err(Error::InvalidArgument(_))));
+
+        // nothing was updated
+        let (_, a, _, _) = read_all(&ctx, &uri)?;
+        assert_eq!((0..10).map(|i| i * 10).collect::<Vec<i32>>(), a);
+
+        test_uri.close().map_err(|e| Error::Other(e.to_string()))
+    }
+}
@@ -9,10 +9,12 @@ use arrow::array::{
     RecordBatch,
 };
 use arrow::buffer::OffsetBuffer;
-use arrow::datatypes::{ArrowPrimitiveType, Field};
+use arrow::datatypes::{ArrowPrimitiveType, Field, SchemaRef};
+use arrow::ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream};
+use arrow::record_batch::RecordBatchReader;
 use tiledb_common::array::CellValNum;
 
-use crate::array::Schema;
+use crate::array::{Array, Schema};
 use crate::error::{DatatypeError, Error};
 use crate::query::buffer::{
     Buffer, CellStructure, QueryBuffers, TypedQueryBuffers,
@@ -20,7 +22,9 @@ use crate::query::buffer::{
 use crate::query::write::input::{
     DataProvider, RecordProvider, TypedDataProvider,
 };
+use crate::query::write::{WriteBuilder, WrittenFragment};
 use crate::query::CellValue;
+use crate::query::QueryBuilder;
 use crate::Result as TileDBResult;
 
 fn cell_structure_var(
@@ -522,6 +526,69 @@ impl<'data> RecordProvider<'data> for RecordBatch {
     }
 }
 
+/// Records imported through the Arrow C stream interface.
+///
+/// The batches of the stream are read one at a time, so that memory use is
+/// bounded by the size of a batch rather than the size of the stream.
+/// Each batch is a [RecordBatch], which can be written using
+/// [WriteBuilder::records]; [write](Self::write) does this for each batch.
+pub struct ArrowStreamRecords {
+    reader: ArrowArrayStreamReader,
+}
+
+impl ArrowStreamRecords {
+    /// Returns the schema of the batches of the stream.
+    pub fn schema(&self) -> SchemaRef {
+        self.reader.schema()
+    }
+
+    /// Writes each remaining batch of the stream into `array` using its own
+    /// write query, and returns the array along with the fragments written.
+    ///
+    /// Each batch is written into a separate fragment, using the default
+    /// layout of the array. To write into a dense array, or to configure
+    /// each query, iterate over the batches and write each one instead.
+    pub fn write(
+        self,
+        array: Array,
+    ) -> TileDBResult<(Array, Vec<WrittenFragment>)> {
+        let mut array = array;
+        let mut fragments = vec![];
+        for batch in self {
+            let batch = batch?;
+            if batch.num_rows() == 0 {
+                continue;
+            }
+            let q = WriteBuilder::new(array)?.records(&batch)?.build();
+            q.submit()?;
+            let (a, written) = q.finalize_with_fragments()?;
+            array = a;
+            fragments.extend(written);
+        }
+        Ok((array, fragments))
+    }
+}
+
+impl TryFrom<FFI_ArrowArrayStream> for ArrowStreamRecords {
+    type Error = Error;
+
+    fn try_from(value: FFI_ArrowArrayStream) -> TileDBResult<Self> {
+        let reader = ArrowArrayStreamReader::try_new(value)
+            .map_err(|e| Error::InvalidArgument(anyhow!(e)))?;
+        Ok(ArrowStreamRecords { reader })
+    }
+}
+
+impl Iterator for ArrowStreamRecords {
+    type Item = TileDBResult<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.reader
+            .next()
+            .map(|batch| batch.map_err(|e| Error::InvalidArgument(anyhow!(e))))
+    }
+}
+
 pub struct RecordBatchTileDBInputs<'data> {
     schema: Rc<Schema>,
     fields: core::slice::Iter<'data, Arc<Field>>,
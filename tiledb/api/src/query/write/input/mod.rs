@@ -295,6 +295,128 @@ impl DataProvider for Vec<String> {
     }
 }
 
+/// Helper function to compute the validity buffer of nullable cells.
+fn validity_impl<T>(
+    value: &[Option<T>],
+    is_nullable: bool,
+) -> TileDBResult<Option<Buffer<'static, u8>>> {
+    if is_nullable {
+        Ok(Some(Buffer::Owned(
+            value
+                .iter()
+                .map(|cell| cell.is_some() as u8)
+                .collect::<Vec<u8>>()
+                .into_boxed_slice(),
+        )))
+    } else if value.iter().any(Option::is_none) {
+        Err(Error::UnexpectedValidity)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Helper function to implement `DataProvider::query_buffers`
+/// for nullable cells which resemble a nested slice.
+/// Null cells of fixed-size fields still occupy `cell_val_num` values,
+/// which are filled with the default value.
+fn nullable_query_buffers_impl<S>(
+    value: &[Option<S>],
+    cell_val_num: CellValNum,
+    is_nullable: bool,
+) -> TileDBResult<QueryBuffers<<S as AsSlice>::Item>>
+where
+    S: AsSlice,
+{
+    let validity = validity_impl(value, is_nullable)?;
+
+    let mut data = vec![];
+    let mut offsets = Vec::with_capacity(value.len() + 1);
+    offsets.push(0u64);
+    for cell in value.iter() {
+        match (cell, cell_val_num) {
+            (Some(s), CellValNum::Fixed(nz))
+                if s.values().len() != nz.get() as usize =>
+            {
+                return Err(Error::UnexpectedCellStructure {
+                    expected: CellValNum::Fixed(nz),
+                    found: CellValNum::Var,
+                });
+            }
+            (Some(s), _) => data.extend_from_slice(s.values()),
+            (None, CellValNum::Fixed(nz)) => {
+                data.resize(data.len() + nz.get() as usize, Default::default())
+            }
+            (None, CellValNum::Var) => {}
+        }
+        offsets.push(data.len() as u64);
+    }
+
+    let cell_structure = match cell_val_num {
+        CellValNum::Fixed(nz) => CellStructure::Fixed(nz),
+        CellValNum::Var => {
+            CellStructure::Var(offsets.into_boxed_slice().into())
+        }
+    };
+
+    Ok(QueryBuffers {
+        data: Buffer::Owned(data.into_boxed_slice()),
+        cell_structure,
+        validity,
+    })
+}
+
+impl<C> DataProvider for Vec<Option<C>>
+where
+    C: CellValue,
+{
+    type Unit = C;
+
+    fn query_buffers(
+        &self,
+        _cell_val_num: CellValNum,
+        is_nullable: bool,
+    ) -> TileDBResult<QueryBuffers<Self::Unit>> {
+        let validity = validity_impl(self, is_nullable)?;
+        let data = self
+            .iter()
+            .map(|cell| cell.unwrap_or_default())
+            .collect::<Vec<C>>();
+
+        Ok(QueryBuffers {
+            data: Buffer::Owned(data.into_boxed_slice()),
+            cell_structure: NonZeroU32::new(1).unwrap().into(),
+            validity,
+        })
+    }
+}
+
+impl<C> DataProvider for Vec<Option<Vec<C>>>
+where
+    C: CellValue,
+{
+    type Unit = C;
+
+    fn query_buffers(
+        &self,
+        cell_val_num: CellValNum,
+        is_nullable: bool,
+    ) -> TileDBResult<QueryBuffers<Self::Unit>> {
+        nullable_query_buffers_impl(self, cell_val_num, is_nullable)
+    }
+}
+
+impl DataProvider for Vec<Option<String>> {
+    type Unit = u8;
+
+    fn query_buffers(
+        &self,
+        cell_val_num: CellValNum,
+        is_nullable: bool,
+    ) -> TileDBResult<QueryBuffers<Self::Unit>> {
+        nullable_query_buffers_impl(self, cell_val_num, is_nullable)
+    }
+}
+
 pub trait RecordProvider<'data> {
     type Iter: Iterator<Item = TileDBResult<(String, TypedQueryBuffers<'data>)>>;
 
@@ -364,7 +486,72 @@ mod tests {
         }
     }
 
+    fn do_input_provider_nullable_strings(stringvec: Vec<Option<String>>) {
+        let input = stringvec.query_buffers(CellValNum::Var, true).unwrap();
+        let values = input.data.as_ref();
+        let offsets = input.cell_structure.offsets_ref().unwrap();
+        let validity = input.validity.as_ref().unwrap().as_ref();
+
+        assert_eq!(stringvec.len() + 1, offsets.len());
+        assert_eq!(stringvec.len(), validity.len());
+
+        for (expected, (offset, v)) in stringvec
+            .iter()
+            .zip(offsets.windows(2).zip(validity.iter()))
+        {
+            let s = &values[offset[0] as usize..offset[1] as usize];
+            if let Some(expected) = expected {
+                assert_eq!(1, *v);
+                assert_eq!(expected.as_bytes(), s);
+            } else {
+                assert_eq!(0, *v);
+                assert!(s.is_empty());
+            }
+        }
+
+        let has_nulls = stringvec.iter().any(Option::is_none);
+        let non_nullable = stringvec.query_buffers(CellValNum::Var, false);
+        assert_eq!(has_nulls, non_nullable.is_err());
+    }
+
+    #[test]
+    fn input_provider_nullable_fixed() {
+        let input = vec![Some(1i32), None, Some(3)];
+        let qb = input.query_buffers(CellValNum::single(), true).unwrap();
+        assert_eq!(&[1, 0, 3], qb.data.as_ref());
+        assert_eq!(&[1, 0, 1], qb.validity.unwrap().as_ref());
+
+        let input = vec![Some(vec![1i32, 2]), None, Some(vec![5, 6])];
+        let cell_val_num = CellValNum::try_from(2).unwrap();
+        let qb = input.query_buffers(cell_val_num, true).unwrap();
+        assert_eq!(&[1, 2, 0, 0, 5, 6], qb.data.as_ref());
+        assert!(qb.cell_structure.offsets_ref().is_none());
+        assert_eq!(&[1, 0, 1], qb.validity.unwrap().as_ref());
+
+        let input = vec![Some(vec![1i32, 2, 3])];
+        assert!(matches!(
+            input.query_buffers(cell_val_num, true),
+            Err(Error::UnexpectedCellStructure { .. })
+        ));
+
+        let input = vec![Some(1i32), None];
+        assert!(matches!(
+            input.query_buffers(CellValNum::single(), false),
+            Err(Error::UnexpectedValidity)
+        ));
+    }
+
     proptest! {
+        #[test]
+        fn input_provider_nullable_strings(
+            stringvec in vec(
+                proptest::option::of(".*"),
+                MIN_RECORDS..=MAX_RECORDS
+            )
+        ) {
+            do_input_provider_nullable_strings(stringvec)
+        }
+
         #[test]
         fn input_provider_u64(u64vec in vec(any::<u64>(), MIN_RECORDS..=MAX_RECORDS)) {
             do_input_provider_u64(u64vec)
@@ -4,6 +4,10 @@ use std::collections::HashMap;
 use std::pin::Pin;
 use std::rc::Rc;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::array::fragment_info::{FragmentInfo, FragmentInfoList};
 use crate::config::Config;
 use crate::query::buffer::{CellStructure, QueryBuffers, TypedQueryBuffers};
 use crate::query::write::input::{
@@ -49,6 +53,90 @@ impl WriteQuery<'_> {
     pub fn submit(&self) -> TileDBResult<()> {
         self.base.do_submit()
     }
+
+    /// Returns the fragments which this query has written so far.
+    ///
+    /// A fragment written in global order is not complete until the query
+    /// is finalized. Use [finalize_with_fragments](Self::finalize_with_fragments)
+    /// to list the fragments of such a write.
+    pub fn written_fragments(&self) -> TileDBResult<Vec<WrittenFragment>> {
+        let c_query = **self.base.cquery();
+
+        let mut c_num: u32 = 0;
+        self.capi_call(|ctx| unsafe {
+            ffi::tiledb_query_get_fragment_num(ctx, c_query, &mut c_num)
+        })?;
+
+        (0..c_num as u64)
+            .map(|idx| {
+                let mut c_uri: *const std::ffi::c_char = out_ptr!();
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_query_get_fragment_uri(
+                        ctx, c_query, idx, &mut c_uri,
+                    )
+                })?;
+
+                // N.B. The URI is owned by the query, so we copy it
+                // and do *not* free it.
+                let uri = unsafe { std::ffi::CStr::from_ptr(c_uri) };
+                let uri = String::from(uri.to_string_lossy());
+
+                let (mut t1, mut t2) = (0u64, 0u64);
+                self.capi_call(|ctx| unsafe {
+                    ffi::tiledb_query_get_fragment_timestamp_range(
+                        ctx, c_query, idx, &mut t1, &mut t2,
+                    )
+                })?;
+
+                Ok(WrittenFragment {
+                    uri,
+                    timestamp_range: [t1, t2],
+                })
+            })
+            .collect()
+    }
+
+    /// Finalizes the query and returns the array along with
+    /// all of the fragments which the query wrote.
+    pub fn finalize_with_fragments(
+        self,
+    ) -> TileDBResult<(Array, Vec<WrittenFragment>)> {
+        self.base.do_finalize()?;
+        let fragments = self.written_fragments()?;
+        Ok((self.base.array, fragments))
+    }
+}
+
+/// Identifies a fragment created by a [WriteQuery].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct WrittenFragment {
+    /// URI of the fragment.
+    pub uri: String,
+    /// Timestamp range of the fragment.
+    pub timestamp_range: [u64; 2],
+}
+
+impl WrittenFragment {
+    /// Returns the name of the fragment, which is the last
+    /// component of its URI.
+    pub fn name(&self) -> &str {
+        let uri = self.uri.trim_end_matches('/');
+        uri.rsplit('/').next().unwrap_or(uri)
+    }
+
+    /// Looks up this fragment in a [FragmentInfoList] loaded for the
+    /// array which it was written to.
+    ///
+    /// Returns `None` if the list does not contain the fragment,
+    /// which may occur if the list was loaded before the write,
+    /// or if the fragment has since been consolidated or deleted.
+    pub fn fragment_info<'info>(
+        &self,
+        fragments: &'info FragmentInfoList,
+    ) -> TileDBResult<Option<FragmentInfo<'info>>> {
+        fragments.find_fragment(self.name())
+    }
 }
 
 pub struct WriteBuilder<'data> {
@@ -77,6 +165,12 @@ impl<'data> QueryBuilder for WriteBuilder<'data> {
     }
 }
 
+impl BuilderBaseMut for WriteBuilder<'_> {
+    fn base_mut(&mut self) -> &mut BuilderBase {
+        &mut self.base
+    }
+}
+
 impl<'data> WriteBuilder<'data> {
     pub fn new(array: Array) -> TileDBResult<Self> {
         let base = BuilderBase::new(array, QueryType::Write)?;
@@ -249,3 +343,124 @@ impl<'data> WriteBuilder<'data> {
         Ok(b)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use uri::{self, TestArrayUri};
+
+    use super::*;
+    use crate::array::fragment_info::Builder as FragmentInfoBuilder;
+    use crate::array::{
+        ArrayOpener, ArrayType, AttributeBuilder, DimensionBuilder,
+        DomainBuilder, Mode, SchemaBuilder,
+    };
+    use crate::Datatype;
+
+    fn create_sparse(
+        test_uri: &dyn TestArrayUri,
+        ctx: &Context,
+    ) -> TileDBResult<String> {
+        let domain = {
+            let id = DimensionBuilder::new(
+                ctx,
+                "id",
+                Datatype::Int32,
+                ([0, 99], 10),
+            )?
+            .build();
+            DomainBuilder::new(ctx)?.add_dimension(id)?.build()
+        };
+        let schema = SchemaBuilder::new(ctx, ArrayType::Sparse, domain)?
+            .add_attribute(
+                AttributeBuilder::new(ctx, "a", Datatype::UInt64)?.build(),
+            )?
+            .build()?;
+
+        let uri = test_uri
+            .with_path("written_fragments")
+            .map_err(|e| Error::Other(e.to_string()))?;
+        Array::create(ctx, &uri, schema)?;
+        Ok(uri)
+    }
+
+    #[test]
+    fn written_fragments() -> TileDBResult<()> {
+        let ctx = Context::new()?;
+        let test_uri = uri::get_uri_generator()
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let uri = create_sparse(&test_uri, &ctx)?;
+
+        let id = (0..10).collect::<Vec<i32>>();
+        let a = (0..10).collect::<Vec<u64>>();
+
+        // unordered write, the fragment is complete after submit
+        let first = {
+            let array = ArrayOpener::new(&ctx, &uri, Mode::Write)?
+                .end_timestamp(5)?
+                .open()?;
+            let q = WriteBuilder::new(array)?
+                .layout(QueryLayout::Unordered)?
+                .data("id", &id)?
+                .data("a", &a)?
+                .build();
+            assert_eq!(Vec::<WrittenFragment>::new(), q.written_fragments()?);
+            q.submit()?;
+
+            let written = q.written_fragments()?;
+            assert_eq!(1, written.len());
+            assert_eq!(written, q.finalize_with_fragments()?.1);
+            written.into_iter().next().unwrap()
+        };
+        assert_eq!([5, 5], first.timestamp_range);
+
+        // global order write, the fragment is complete after finalize
+        let second = {
+            let (id, a) = (id[..4].to_vec(), a[..4].to_vec());
+            let array = ArrayOpener::new(&ctx, &uri, Mode::Write)?
+                .end_timestamp(7)?
+                .open()?;
+            let q = WriteBuilder::new(array)?
+                .layout(QueryLayout::Global)?
+                .data("id", &id)?
+                .data("a", &a)?
+                .build();
+            q.submit()?;
+
+            let (_, written) = q.finalize_with_fragments()?;
+            assert_eq!(1, written.len());
+            written.into_iter().next().unwrap()
+        };
+        assert_eq!([7, 7], second.timestamp_range);
+        assert_ne!(first.uri, second.uri);
+
+        let fragments = FragmentInfoBuilder::new(&ctx, &uri)?.build()?;
+        assert_eq!(2, fragments.num_fragments()?);
+        for (written, num_cells) in [(first, 10), (second, 4)] {
+            let info = written
+                .fragment_info(&fragments)?
+                .expect("Written fragment not found in fragment info");
+            assert_eq!(written.name(), info.name()?);
+            assert_eq!(written.timestamp_range, info.timestamp_range()?);
+            assert_eq!(num_cells, info.num_cells()?);
+            assert!(info.size()? > 0);
+            assert!(info.num_mbrs()? > 0);
+        }
+
+        test_uri.close().map_err(|e| Error::Other(e.to_string()))
+    }
+
+    #[test]
+    fn written_fragment_name() {
+        let f = WrittenFragment {
+            uri: "file:///tmp/a/__fragments/__1_1_abcdef_22".to_owned(),
+            timestamp_range: [1, 1],
+        };
+        assert_eq!("__1_1_abcdef_22", f.name());
+
+        let f = WrittenFragment {
+            uri: "s3://bucket/a/__1_1_abcdef_22/".to_owned(),
+            timestamp_range: [1, 1],
+        };
+        assert_eq!("__1_1_abcdef_22", f.name());
+    }
+}
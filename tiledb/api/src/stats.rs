@@ -44,8 +44,9 @@ impl Drop for RawStatsString {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Metrics {
     pub timers: HashMap<String, f64>,
     pub counters: HashMap<String, u64>,
@@ -126,10 +127,80 @@ pub fn dump_json() -> Result<Option<Vec<Metrics>>, Error> {
     }
 }
 
+/// Parses the stats of a single context or query.
+///
+/// libtiledb returns an empty string rather than an empty object
+/// when stats are not enabled, so that parses to empty [Metrics].
+///
+/// This does not use the `serde` implementation of [Metrics],
+/// so that it is available without the `serde` feature.
+pub(crate) fn parse_metrics(json: &str) -> Result<Metrics, Error> {
+    use anyhow::anyhow;
+    use serde_json::Value;
+
+    if json.trim().is_empty() {
+        return Ok(Metrics::default());
+    }
+
+    let value = serde_json::from_str::<Value>(json)
+        .map_err(|e| Error::ToJson(anyhow!(e)))?;
+    let Value::Object(mut stats) = value else {
+        return Err(Error::ToJson(anyhow!(
+            "Expected stats object, found: {}",
+            value
+        )));
+    };
+
+    let mut section = |key: &str| -> Result<Vec<(String, Value)>, Error> {
+        match stats.remove(key) {
+            None => Ok(vec![]),
+            Some(Value::Object(section)) => Ok(section.into_iter().collect()),
+            Some(value) => Err(Error::ToJson(anyhow!(
+                "Expected object for stats '{}', found: {}",
+                key,
+                value
+            ))),
+        }
+    };
+
+    let timers = section("timers")?
+        .into_iter()
+        .map(|(k, v)| match v.as_f64() {
+            Some(t) => Ok((k, t)),
+            None => Err(Error::ToJson(anyhow!(
+                "Expected number for timer '{}', found: {}",
+                k,
+                v
+            ))),
+        })
+        .collect::<Result<HashMap<String, f64>, Error>>()?;
+    let counters = section("counters")?
+        .into_iter()
+        .map(|(k, v)| match v.as_u64() {
+            Some(c) => Ok((k, c)),
+            None => Err(Error::ToJson(anyhow!(
+                "Expected unsigned integer for counter '{}', found: {}",
+                k,
+                v
+            ))),
+        })
+        .collect::<Result<HashMap<String, u64>, Error>>()?;
+
+    Ok(Metrics { timers, counters })
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
+    /// Serializes the tests which enable stats, or which depend on stats
+    /// not being enabled, since that is global state.
+    static STATS_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    pub(crate) fn lock_stats() -> std::sync::MutexGuard<'static, ()> {
+        STATS_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     struct ScopedStats {
         was_enabled: bool,
     }
@@ -152,6 +223,7 @@ mod tests {
 
     #[test]
     fn scoped_stats() -> anyhow::Result<()> {
+        let _lock = lock_stats();
         assert!(!is_enabled()?);
 
         {
@@ -163,4 +235,85 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn metrics_from_json() -> anyhow::Result<()> {
+        assert_eq!(Metrics::default(), parse_metrics("")?);
+
+        let metrics = parse_metrics(
+            r#"{
+                "timers": {"Context.Query.Reader.dowork.sum": 0.0125},
+                "counters": {"Context.Query.Reader.loop_num": 1}
+            }"#,
+        )?;
+        assert_eq!(
+            Some(&0.0125),
+            metrics.timers.get("Context.Query.Reader.dowork.sum")
+        );
+        assert_eq!(
+            Some(&1),
+            metrics.counters.get("Context.Query.Reader.loop_num")
+        );
+
+        // either section may be missing
+        let metrics = parse_metrics(r#"{"counters": {"a": 2}}"#)?;
+        assert!(metrics.timers.is_empty());
+        assert_eq!(Some(&2), metrics.counters.get("a"));
+
+        assert!(matches!(parse_metrics("[1, 2]"), Err(Error::ToJson(_))));
+        assert!(matches!(
+            parse_metrics(r#"{"counters": {"a": -1}}"#),
+            Err(Error::ToJson(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn query_stats() -> anyhow::Result<()> {
+        use tiledb_common::array::ArrayType;
+
+        use crate::query::{
+            Query, QueryBuilder, ReadBuilder, ReadQuery, ReadQueryBuilder,
+            WriteBuilder,
+        };
+        use crate::tests::examples::{quickstart, TestArray};
+
+        let _lock = lock_stats();
+        let _stats = ScopedStats::new()?;
+
+        let mut array = TestArray::new(
+            "query_stats",
+            quickstart::Builder::new(ArrayType::Sparse).build().into(),
+        )?;
+
+        let rows = vec![1i32, 2, 3, 4];
+        let cols = vec![1i32, 2, 3, 4];
+        let a = vec![10i32, 20, 30, 40];
+        {
+            let q = WriteBuilder::new(array.for_write()?)?
+                .data("rows", &rows)?
+                .data("cols", &cols)?
+                .data("a", &a)?
+                .build();
+            q.submit()?;
+            let metrics = q.stats()?;
+            assert!(!metrics.timers.is_empty());
+            q.finalize()?;
+        }
+
+        let mut q = ReadBuilder::new(array.for_read()?)?
+            .register_constructor::<_, Vec<i32>>("a", Default::default())?
+            .build();
+        let (a_out, ()) = q.execute()?;
+        assert_eq!(a, a_out);
+
+        let metrics = q.stats()?;
+        assert!(!metrics.timers.is_empty());
+        assert!(!metrics.counters.is_empty());
+        assert!(metrics.counters.keys().any(|k| k.contains("Reader")));
+        assert!(metrics.timers.values().all(|t| *t >= 0.0));
+
+        Ok(())
+    }
 }
@@ -30,6 +30,10 @@ impl Drop for RawTDBString {
     }
 }
 
+// SAFETY: a `tiledb_string_t` is an immutable owned buffer.
+unsafe impl Send for RawTDBString {}
+unsafe impl Sync for RawTDBString {}
+
 pub struct TDBString {
     pub(crate) raw: RawTDBString,
 }
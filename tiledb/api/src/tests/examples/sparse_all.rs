@@ -152,5 +152,7 @@ pub fn schema(params: Parameters) -> SchemaData {
         coordinate_filters: Default::default(),
         offsets_filters: Default::default(),
         nullity_filters: Default::default(),
+        current_domain: None,
+        dimension_labels: vec![],
     }
 }
@@ -0,0 +1,506 @@
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use anyhow::anyhow;
+use glob::{MatchOptions, Pattern};
+
+use super::{VFSLsStatus, VFS};
+use crate::error::Error;
+use crate::Result as TileDBResult;
+
+/// An entry of a directory listed by [VFS::read_dir] or [VFS::walk].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DirEntry {
+    /// URI of the entry, as reported by the VFS backend.
+    pub uri: String,
+    /// Path of the entry relative to the directory which was listed,
+    /// with components separated by `/`.
+    pub path: String,
+    /// Number of directories between the listed directory and the entry.
+    /// The children of the listed directory have depth 1.
+    pub depth: usize,
+    /// Whether the entry is a directory.
+    pub is_dir: bool,
+    /// Size of the entry in bytes if it is a file, or zero if it is
+    /// a directory.
+    pub size: u64,
+}
+
+impl DirEntry {
+    /// Returns the last component of the entry's path.
+    pub fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+}
+
+/// The type and size of the entries beneath a directory,
+/// found using a single recursive listing of the directory.
+struct Listing {
+    /// Size of each listed entry, keyed by URI without a trailing `/`.
+    sizes: HashMap<String, u64>,
+    /// URIs of the directories which contain a listed entry.
+    dirs: HashSet<String>,
+}
+
+impl Listing {
+    /// Lists `uri` recursively, or returns `None` if the VFS backend
+    /// cannot do so.
+    fn new(vfs: &VFS, uri: &str) -> Option<Self> {
+        let mut sizes = HashMap::new();
+        vfs.ls_recursive(uri, |child, size| {
+            sizes.insert(child.trim_end_matches('/').to_owned(), size);
+            VFSLsStatus::Continue
+        })
+        .ok()?;
+
+        // some backends only list files, so directories are found from
+        // the entries beneath them
+        let mut dirs = HashSet::new();
+        for uri in sizes.keys() {
+            let mut uri = uri.as_str();
+            while let Some((parent, _)) = uri.rsplit_once('/') {
+                if !dirs.insert(parent.to_owned()) {
+                    break;
+                }
+                uri = parent;
+            }
+        }
+
+        Some(Listing { sizes, dirs })
+    }
+
+    /// Returns whether the entry at `uri` is a directory, and its size,
+    /// or `None` if the listing cannot tell. This is the case for an entry
+    /// with size zero and nothing beneath it, which may be an empty file or
+    /// an empty directory.
+    fn entry(&self, uri: &str) -> Option<(bool, u64)> {
+        let uri = uri.trim_end_matches('/');
+        if self.dirs.contains(uri) {
+            Some((true, 0))
+        } else {
+            match self.sizes.get(uri) {
+                Some(&size) if size > 0 => Some((false, size)),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Iterator over the entries of a directory, returned by [VFS::read_dir].
+///
+/// The directory is listed when the iterator is created. Where the VFS
+/// backend supports it, the directory is also listed recursively once to
+/// find the type and size of each entry. Otherwise, and for entries which
+/// the recursive listing cannot tell apart, such as an empty file and an
+/// empty directory, they are requested from the VFS as the entry is produced.
+pub struct ReadDir<'vfs> {
+    vfs: &'vfs VFS,
+    listing: Option<Rc<Listing>>,
+    parent: String,
+    depth: usize,
+    uris: std::vec::IntoIter<String>,
+}
+
+impl<'vfs> ReadDir<'vfs> {
+    pub(super) fn new(vfs: &'vfs VFS, uri: &str) -> TileDBResult<Self> {
+        let listing = Listing::new(vfs, uri).map(Rc::new);
+        Self::nested(vfs, listing, uri, String::new(), 1, false)
+    }
+
+    fn nested(
+        vfs: &'vfs VFS,
+        listing: Option<Rc<Listing>>,
+        uri: &str,
+        parent: String,
+        depth: usize,
+        sort: bool,
+    ) -> TileDBResult<Self> {
+        let mut uris = vec![];
+        vfs.ls(uri, |child| {
+            uris.push(child.to_owned());
+            VFSLsStatus::Continue
+        })?;
+        if sort {
+            uris.sort();
+        }
+        Ok(ReadDir {
+            vfs,
+            listing,
+            parent,
+            depth,
+            uris: uris.into_iter(),
+        })
+    }
+
+    fn entry(&self, uri: String) -> TileDBResult<DirEntry> {
+        let name = uri.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+        let path = if self.parent.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{}/{}", self.parent, name)
+        };
+        let listed = self
+            .listing
+            .as_ref()
+            .and_then(|listing| listing.entry(&uri));
+        let (is_dir, size) = match listed {
+            Some(entry) => entry,
+            None => {
+                let is_dir = self.vfs.is_dir(&uri)?;
+                let size = if is_dir { 0 } else { self.vfs.file_size(&uri)? };
+                (is_dir, size)
+            }
+        };
+        Ok(DirEntry {
+            uri,
+            path,
+            depth: self.depth,
+            is_dir,
+            size,
+        })
+    }
+}
+
+impl Iterator for ReadDir<'_> {
+    type Item = TileDBResult<DirEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let uri = self.uris.next()?;
+        Some(self.entry(uri))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.uris.size_hint()
+    }
+}
+
+impl ExactSizeIterator for ReadDir<'_> {}
+
+/// A glob pattern for [Walk::include] or [Walk::exclude].
+struct WalkPattern {
+    pattern: Pattern,
+    /// Whether the pattern matches the whole path of an entry,
+    /// rather than just its name.
+    full_path: bool,
+}
+
+impl WalkPattern {
+    fn new(pattern: &str) -> TileDBResult<Self> {
+        Ok(WalkPattern {
+            pattern: Pattern::new(pattern).map_err(|e| {
+                Error::InvalidArgument(anyhow!(
+                    "Invalid glob pattern '{}': {}",
+                    pattern,
+                    e
+                ))
+            })?,
+            full_path: pattern.contains('/'),
+        })
+    }
+
+    fn matches(&self, entry: &DirEntry) -> bool {
+        const OPTIONS: MatchOptions = MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+        if self.full_path {
+            self.pattern.matches_with(&entry.path, OPTIONS)
+        } else {
+            self.pattern.matches_with(entry.name(), OPTIONS)
+        }
+    }
+}
+
+/// Recursive iterator over the contents of a directory,
+/// returned by [VFS::walk].
+///
+/// Directories are produced before their contents. Entries can be filtered
+/// using glob patterns: a pattern which contains `/` is matched against
+/// the [path](DirEntry::path) of each entry, and any other pattern is
+/// matched against the [name](DirEntry::name) of each entry.
+///
+/// An error listing a directory is produced as an item, after which the walk
+/// continues with the next entry.
+///
+/// Each directory is listed as it is reached. As with [ReadDir], the type and
+/// size of each entry are found using a single recursive listing of the root
+/// where the VFS backend supports it.
+pub struct Walk<'vfs> {
+    vfs: &'vfs VFS,
+    listing: Option<Rc<Listing>>,
+    max_depth: Option<usize>,
+    include: Vec<WalkPattern>,
+    exclude: Vec<WalkPattern>,
+    sort: bool,
+    /// Directory which will be listed before producing the next entry.
+    pending: Option<(String, String, usize)>,
+    stack: Vec<ReadDir<'vfs>>,
+}
+
+impl<'vfs> Walk<'vfs> {
+    pub(super) fn new(vfs: &'vfs VFS, uri: &str) -> Self {
+        Walk {
+            vfs,
+            listing: None,
+            max_depth: None,
+            include: vec![],
+            exclude: vec![],
+            sort: false,
+            pending: Some((uri.to_owned(), String::new(), 1)),
+            stack: vec![],
+        }
+    }
+
+    /// Produces entries up to `depth` directories below the root.
+    /// A depth of 1 produces the same entries as [VFS::read_dir],
+    /// and a depth of 0 produces nothing.
+    pub fn max_depth(self, depth: usize) -> Self {
+        Walk {
+            max_depth: Some(depth),
+            ..self
+        }
+    }
+
+    /// Produces only entries which match `pattern`.
+    /// If called more than once, entries which match any of the patterns
+    /// are produced. Directories which do not match are still walked.
+    pub fn include(mut self, pattern: &str) -> TileDBResult<Self> {
+        self.include.push(WalkPattern::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Skips entries which match `pattern`, as well as the contents of
+    /// directories which match `pattern`.
+    pub fn exclude(mut self, pattern: &str) -> TileDBResult<Self> {
+        self.exclude.push(WalkPattern::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Produces the entries of each directory sorted by URI.
+    pub fn sorted(self) -> Self {
+        Walk { sort: true, ..self }
+    }
+
+    fn is_included(&self, entry: &DirEntry) -> bool {
+        self.include.is_empty() || self.include.iter().any(|p| p.matches(entry))
+    }
+
+    fn is_max_depth(&self, entry: &DirEntry) -> bool {
+        matches!(self.max_depth, Some(depth) if entry.depth >= depth)
+    }
+
+    fn is_excluded(&self, entry: &DirEntry) -> bool {
+        self.exclude.iter().any(|p| p.matches(entry))
+    }
+}
+
+impl Iterator for Walk<'_> {
+    type Item = TileDBResult<DirEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.max_depth == Some(0) {
+            return None;
+        }
+        loop {
+            if let Some((uri, parent, depth)) = self.pending.take() {
+                if self.stack.is_empty() && self.listing.is_none() {
+                    // this is the root
+                    self.listing = Listing::new(self.vfs, &uri).map(Rc::new);
+                }
+                match ReadDir::nested(
+                    self.vfs,
+                    self.listing.clone(),
+                    &uri,
+                    parent,
+                    depth,
+                    self.sort,
+                ) {
+                    Ok(dir) => self.stack.push(dir),
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+
+            let entry = match self.stack.last_mut()?.next() {
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                Some(Ok(entry)) => entry,
+            };
+            if self.is_excluded(&entry) {
+                continue;
+            }
+            if entry.is_dir && !self.is_max_depth(&entry) {
+                self.pending = Some((
+                    entry.uri.clone(),
+                    entry.path.clone(),
+                    entry.depth + 1,
+                ));
+            }
+            if self.is_included(&entry) {
+                return Some(Ok(entry));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uri::TestDirectory;
+
+    use super::*;
+    use crate::config::Config;
+    use crate::context::Context;
+    use crate::vfs::VFSMode;
+
+    /// Creates files and directories in `test_dir`:
+    /// ```text
+    /// a.txt
+    /// b.csv
+    /// d1/c.txt
+    /// d1/d2/e.txt
+    /// d1/d2/f.csv
+    /// skip/g.txt
+    /// ```
+    fn create_tree(vfs: &VFS, test_dir: &TestDirectory) -> anyhow::Result<()> {
+        for dir in ["d1", "d1/d2", "skip"] {
+            vfs.create_dir(&test_dir.with_path(dir)?)?;
+        }
+        for (i, file) in [
+            "a.txt",
+            "b.csv",
+            "d1/c.txt",
+            "d1/d2/e.txt",
+            "d1/d2/f.csv",
+            "skip/g.txt",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let fh = vfs.open(&test_dir.with_path(file)?, VFSMode::Write)?;
+            fh.write(&vec![0u8; i + 1])?;
+            fh.close()?;
+        }
+        Ok(())
+    }
+
+    fn paths(walk: Walk) -> TileDBResult<Vec<String>> {
+        walk.map(|entry| entry.map(|entry| entry.path)).collect()
+    }
+
+    #[test]
+    fn read_dir() -> anyhow::Result<()> {
+        let ctx = Context::new()?;
+        let vfs = VFS::new(&ctx, &Config::new()?)?;
+        let test_dir = TestDirectory::new()?;
+        create_tree(&vfs, &test_dir)?;
+
+        let mut entries = vfs
+            .read_dir(&test_dir.base_dir()?)?
+            .collect::<TileDBResult<Vec<DirEntry>>>()?;
+        entries.sort_by(|l, r| l.path.cmp(&r.path));
+
+        let summary = entries
+            .iter()
+            .map(|e| (e.path.as_ref(), e.depth, e.is_dir, e.size))
+            .collect::<Vec<(&str, usize, bool, u64)>>();
+        assert_eq!(
+            vec![
+                ("a.txt", 1, false, 1),
+                ("b.csv", 1, false, 2),
+                ("d1", 1, true, 0),
+                ("skip", 1, true, 0)
+            ],
+            summary
+        );
+
+        for entry in entries {
+            assert_eq!(entry.path, entry.name());
+            assert_eq!(entry.is_dir, vfs.is_dir(&entry.uri)?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn walk() -> anyhow::Result<()> {
+        let ctx = Context::new()?;
+        let vfs = VFS::new(&ctx, &Config::new()?)?;
+        let test_dir = TestDirectory::new()?;
+        create_tree(&vfs, &test_dir)?;
+        let root = test_dir.base_dir()?;
+
+        assert_eq!(
+            vec![
+                "a.txt",
+                "b.csv",
+                "d1",
+                "d1/c.txt",
+                "d1/d2",
+                "d1/d2/e.txt",
+                "d1/d2/f.csv",
+                "skip",
+                "skip/g.txt"
+            ],
+            paths(vfs.walk(&root).sorted())?
+        );
+
+        // unsorted produces the same entries, each directory before
+        // its contents
+        {
+            let unsorted = paths(vfs.walk(&root))?;
+            for (i, path) in unsorted.iter().enumerate() {
+                if let Some((parent, _)) = path.rsplit_once('/') {
+                    assert!(unsorted[..i].iter().any(|p| p == parent));
+                }
+            }
+            let mut unsorted = unsorted;
+            unsorted.sort();
+            assert_eq!(paths(vfs.walk(&root).sorted())?, unsorted);
+        }
+
+        assert!(paths(vfs.walk(&root).max_depth(0))?.is_empty());
+        assert_eq!(
+            vec!["a.txt", "b.csv", "d1", "skip"],
+            paths(vfs.walk(&root).sorted().max_depth(1))?
+        );
+        assert_eq!(
+            vec![
+                "a.txt",
+                "b.csv",
+                "d1",
+                "d1/c.txt",
+                "d1/d2",
+                "skip",
+                "skip/g.txt"
+            ],
+            paths(vfs.walk(&root).sorted().max_depth(2))?
+        );
+
+        assert_eq!(
+            vec!["a.txt", "d1/c.txt", "d1/d2/e.txt", "skip/g.txt"],
+            paths(vfs.walk(&root).sorted().include("*.txt")?)?
+        );
+        assert_eq!(
+            vec!["a.txt", "d1/c.txt", "d1/d2/e.txt"],
+            paths(vfs.walk(&root).sorted().include("*.txt")?.exclude("skip")?)?
+        );
+        assert_eq!(
+            vec!["d1/d2/e.txt", "d1/d2/f.csv"],
+            paths(vfs.walk(&root).sorted().include("d1/*/*")?)?
+        );
+        assert_eq!(
+            vec!["b.csv", "d1/d2/f.csv"],
+            paths(vfs.walk(&root).sorted().include("*.csv")?)?
+        );
+
+        assert!(matches!(
+            vfs.walk(&root).include("[").err(),
+            Some(Error::InvalidArgument(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,346 @@
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+
+use super::{VFSHandle, VFSMode, VFS};
+use crate::Result as TileDBResult;
+
+/// Number of bytes which a [VFSFile] reads ahead for [BufRead].
+const BUFFER_CAPACITY: usize = 64 * 1024;
+
+fn io_error(e: crate::error::Error) -> io::Error {
+    io::Error::other(e)
+}
+
+fn unsupported(mode: VFSMode, operation: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("Cannot {} a file opened in mode {:?}", operation, mode),
+    )
+}
+
+/// A file opened using a [VFS], which tracks a cursor position
+/// so that it can implement the [std::io] traits.
+///
+/// A file opened with [VFSMode::Read] implements [Read], [BufRead] and
+/// [Seek]. A file opened with [VFSMode::Write] or [VFSMode::Append]
+/// implements [Write]. The VFS only appends to files, so the file
+/// position can be queried using [Seek] but cannot be changed.
+///
+/// The file is closed when dropped. Use [close](Self::close) to observe
+/// any error from closing the file, which is where some backends
+/// finish writing.
+pub struct VFSFile<'vfs> {
+    vfs: &'vfs VFS,
+    uri: String,
+    mode: VFSMode,
+    handle: VFSHandle,
+    /// Offset of the next byte to read or write.
+    position: u64,
+    /// Size of the file when it was last checked.
+    size: u64,
+    /// Holds the bytes of the file at `position` in `buffer[consumed..filled]`.
+    buffer: Box<[u8]>,
+    consumed: usize,
+    filled: usize,
+}
+
+impl<'vfs> VFSFile<'vfs> {
+    pub(super) fn open(
+        vfs: &'vfs VFS,
+        uri: &str,
+        mode: VFSMode,
+    ) -> TileDBResult<Self> {
+        let size = match mode {
+            VFSMode::Read => vfs.file_size(uri)?,
+            VFSMode::Write => 0,
+            VFSMode::Append => {
+                if vfs.is_file(uri)? {
+                    vfs.file_size(uri)?
+                } else {
+                    0
+                }
+            }
+        };
+        let handle = vfs.open(uri, mode)?;
+        let buffer = match mode {
+            VFSMode::Read => vec![0u8; BUFFER_CAPACITY].into_boxed_slice(),
+            VFSMode::Write | VFSMode::Append => Box::new([]),
+        };
+
+        Ok(VFSFile {
+            vfs,
+            uri: uri.to_owned(),
+            mode,
+            handle,
+            position: if mode == VFSMode::Append { size } else { 0 },
+            size,
+            buffer,
+            consumed: 0,
+            filled: 0,
+        })
+    }
+
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    pub fn mode(&self) -> VFSMode {
+        self.mode
+    }
+
+    /// Closes the file.
+    pub fn close(self) -> TileDBResult<()> {
+        self.handle.close()
+    }
+
+    fn discard_buffer(&mut self) {
+        self.consumed = 0;
+        self.filled = 0;
+    }
+
+    /// Reads from the file at `position` into `buffer`, returning the
+    /// number of bytes read, which is zero at the end of the file.
+    fn read_at(&self, buffer: &mut [u8]) -> io::Result<usize> {
+        let available = self.size.saturating_sub(self.position);
+        let len = std::cmp::min(available, buffer.len() as u64) as usize;
+        if len > 0 {
+            self.handle
+                .read(self.position, &mut buffer[..len])
+                .map_err(io_error)?;
+        }
+        Ok(len)
+    }
+}
+
+impl Read for VFSFile<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.mode != VFSMode::Read {
+            return Err(unsupported(self.mode, "read"));
+        }
+        if self.consumed == self.filled && buf.len() >= self.buffer.len() {
+            // bypass the buffer for large reads
+            let n = self.read_at(buf)?;
+            self.position += n as u64;
+            return Ok(n);
+        }
+        let available = self.fill_buf()?;
+        let n = std::cmp::min(available.len(), buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for VFSFile<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.mode != VFSMode::Read {
+            return Err(unsupported(self.mode, "read"));
+        }
+        if self.consumed == self.filled {
+            let mut buffer = std::mem::take(&mut self.buffer);
+            let n = self.read_at(&mut buffer);
+            self.buffer = buffer;
+            self.consumed = 0;
+            self.filled = n?;
+        }
+        Ok(&self.buffer[self.consumed..self.filled])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = std::cmp::min(amt, self.filled - self.consumed);
+        self.consumed += amt;
+        self.position += amt as u64;
+    }
+}
+
+impl Seek for VFSFile<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(offset) => (0, offset as i128),
+            SeekFrom::Current(offset) => (self.position, offset as i128),
+            SeekFrom::End(offset) => {
+                if self.mode == VFSMode::Read {
+                    self.size =
+                        self.vfs.file_size(&self.uri).map_err(io_error)?;
+                }
+                (self.size, offset as i128)
+            }
+        };
+        let target = base as i128 + offset;
+        if target < 0 || target > u64::MAX as i128 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid seek to offset {}", target),
+            ));
+        }
+        let target = target as u64;
+
+        if target != self.position {
+            if self.mode != VFSMode::Read {
+                return Err(unsupported(self.mode, "seek within"));
+            }
+            self.position = target;
+            self.discard_buffer();
+        }
+        Ok(self.position)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.position)
+    }
+}
+
+impl Write for VFSFile<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.mode == VFSMode::Read {
+            return Err(unsupported(self.mode, "write"));
+        }
+        self.handle.write(buf).map_err(io_error)?;
+        self.position += buf.len() as u64;
+        self.size = std::cmp::max(self.size, self.position);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.mode == VFSMode::Read {
+            Ok(())
+        } else {
+            self.handle.sync().map_err(io_error)
+        }
+    }
+}
+
+impl Drop for VFSFile<'_> {
+    fn drop(&mut self) {
+        if let Ok(false) = self.handle.is_closed() {
+            let _ = self.handle.close();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+
+    use uri::TestDirectory;
+
+    use super::*;
+    use crate::config::Config;
+    use crate::context::Context;
+
+    #[test]
+    fn write_then_read() -> anyhow::Result<()> {
+        let ctx = Context::new()?;
+        let vfs = VFS::new(&ctx, &Config::new()?)?;
+        let test_dir = TestDirectory::new()?;
+        let uri = test_dir.with_path("lines.txt")?;
+
+        {
+            let mut f = vfs.open_file(&uri, VFSMode::Write)?;
+            for i in 0..1000 {
+                writeln!(f, "line {}", i)?;
+            }
+            assert_eq!(vfs.file_size(&uri)?, f.stream_position()?);
+            f.close()?;
+        }
+        {
+            let mut f = vfs.open_file(&uri, VFSMode::Append)?;
+            let size = f.stream_position()?;
+            assert_eq!(vfs.file_size(&uri)?, size);
+            write!(f, "last line")?;
+            assert_eq!(size + 9, f.stream_position()?);
+            assert!(f.seek(SeekFrom::Start(0)).is_err());
+        }
+
+        let expect = (0..1000)
+            .map(|i| format!("line {}", i))
+            .chain(std::iter::once("last line".to_owned()))
+            .collect::<Vec<String>>();
+
+        let f = vfs.open_file(&uri, VFSMode::Read)?;
+        let lines = f.lines().collect::<std::io::Result<Vec<String>>>()?;
+        assert_eq!(expect, lines);
+
+        let mut f = vfs.open_file(&uri, VFSMode::Read)?;
+        let mut contents = String::new();
+        f.read_to_string(&mut contents)?;
+        assert_eq!(expect.join("\n"), contents);
+        assert!(f.write_all(b"nope").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn seek() -> anyhow::Result<()> {
+        let ctx = Context::new()?;
+        let vfs = VFS::new(&ctx, &Config::new()?)?;
+        let test_dir = TestDirectory::new()?;
+        let uri = test_dir.with_path("bytes")?;
+
+        let data = (0..=255u8).cycle().take(200_000).collect::<Vec<u8>>();
+        {
+            let mut f = vfs.open_file(&uri, VFSMode::Write)?;
+            f.write_all(&data)?;
+            f.flush()?;
+        }
+
+        let mut f = vfs.open_file(&uri, VFSMode::Read)?;
+        let mut buf = [0u8; 4];
+
+        f.read_exact(&mut buf)?;
+        assert_eq!(data[0..4], buf);
+
+        assert_eq!(100_000, f.seek(SeekFrom::Start(100_000))?);
+        f.read_exact(&mut buf)?;
+        assert_eq!(data[100_000..100_004], buf);
+
+        assert_eq!(99_994, f.seek(SeekFrom::Current(-10))?);
+        f.read_exact(&mut buf)?;
+        assert_eq!(data[99_994..99_998], buf);
+
+        assert_eq!(199_996, f.seek(SeekFrom::End(-4))?);
+        f.read_exact(&mut buf)?;
+        assert_eq!(data[199_996..], buf);
+        assert_eq!(0, f.read(&mut buf)?);
+
+        assert!(f.seek(SeekFrom::Current(-200_001)).is_err());
+
+        // past the end there is nothing to read
+        assert_eq!(200_010, f.seek(SeekFrom::End(10))?);
+        assert_eq!(0, f.read(&mut buf)?);
+
+        // large reads bypass the buffer
+        f.rewind()?;
+        let mut contents = vec![0u8; data.len()];
+        f.read_exact(&mut contents)?;
+        assert_eq!(data, contents);
+
+        Ok(())
+    }
+
+    #[test]
+    fn copy() -> anyhow::Result<()> {
+        let ctx = Context::new()?;
+        let vfs = VFS::new(&ctx, &Config::new()?)?;
+        let test_dir = TestDirectory::new()?;
+        let src = test_dir.with_path("src")?;
+        let dst = test_dir.with_path("dst")?;
+
+        let data = (0..100_000u32)
+            .flat_map(u32::to_le_bytes)
+            .collect::<Vec<u8>>();
+        vfs.open_file(&src, VFSMode::Write)?.write_all(&data)?;
+
+        {
+            let mut from = vfs.open_file(&src, VFSMode::Read)?;
+            let mut to = vfs.open_file(&dst, VFSMode::Write)?;
+            assert_eq!(data.len() as u64, std::io::copy(&mut from, &mut to)?);
+        }
+
+        let mut contents = vec![];
+        vfs.open_file(&dst, VFSMode::Read)?
+            .read_to_end(&mut contents)?;
+        assert_eq!(data, contents);
+
+        Ok(())
+    }
+}
@@ -1,9 +1,14 @@
+mod dir;
+mod file;
+
 use std::ops::Deref;
 
 use crate::config::{Config, RawConfig};
 use crate::context::{CApiInterface, Context, ContextBound};
 use crate::Result as TileDBResult;
 
+pub use self::dir::{DirEntry, ReadDir, Walk};
+pub use self::file::VFSFile;
 pub use tiledb_common::vfs::VFSMode;
 
 pub enum VFSLsStatus {
@@ -32,6 +37,10 @@ impl Drop for RawVFS {
     }
 }
 
+// SAFETY: the libtiledb VFS is thread-safe.
+unsafe impl Send for RawVFS {}
+unsafe impl Sync for RawVFS {}
+
 pub struct VFS {
     context: Context,
     raw: RawVFS,
@@ -63,6 +72,10 @@ impl Drop for RawVFSHandle {
     }
 }
 
+// SAFETY: a file handle may be moved between threads, but is not `Sync`
+// since libtiledb does not order concurrent writes to the same handle.
+unsafe impl Send for RawVFSHandle {}
+
 pub struct VFSHandle {
     context: Context,
     raw: RawVFSHandle,
@@ -291,6 +304,17 @@ impl VFS {
         })
     }
 
+    /// Opens a file for use with [std::io].
+    ///
+    /// See [VFSFile].
+    pub fn open_file(
+        &self,
+        uri: &str,
+        mode: VFSMode,
+    ) -> TileDBResult<VFSFile<'_>> {
+        VFSFile::open(self, uri, mode)
+    }
+
     pub fn remove_file(&self, uri: &str) -> TileDBResult<()> {
         let c_vfs = *self.raw;
         let c_uri = cstring!(uri);
@@ -384,6 +408,18 @@ impl VFS {
 
         Ok(())
     }
+
+    /// Returns an iterator over the files and directories in the
+    /// directory `uri`. See [ReadDir].
+    pub fn read_dir(&self, uri: &str) -> TileDBResult<ReadDir<'_>> {
+        ReadDir::new(self, uri)
+    }
+
+    /// Returns an iterator over the files and directories in the
+    /// directory `uri` and its subdirectories. See [Walk].
+    pub fn walk(&self, uri: &str) -> Walk<'_> {
+        Walk::new(self, uri)
+    }
 }
 
 // This bit of complexity is based on the StackOverflow answer here:
@@ -516,6 +552,17 @@ mod tests {
     use crate::error::Error;
     use uri::TestDirectory;
 
+    #[test]
+    /// Ensure that VFS is Send and Sync, and VFSHandle and VFSFile are Send,
+    /// fails to compile if not
+    fn is_send_sync() {
+        fn is_send<T: Send>() {}
+        fn is_send_sync<T: Send + Sync>() {}
+        is_send_sync::<VFS>();
+        is_send::<VFSHandle>();
+        is_send::<VFSFile>();
+    }
+
     #[test]
     fn vfs_alloc() -> TileDBResult<()> {
         let ctx = Context::new()?;
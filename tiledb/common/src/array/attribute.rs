@@ -181,6 +181,18 @@ impl<'a> FromFillValue<'a> for String {
     }
 }
 
+impl IntoFillValue for &[bool] {
+    type PhysicalType = u8;
+
+    fn to_raw(&self) -> &[Self::PhysicalType] {
+        // SAFETY: `bool` has the same size and alignment as `u8`,
+        // and its values are `0` and `1`
+        unsafe {
+            std::slice::from_raw_parts(self.as_ptr() as *const u8, self.len())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::collection::vec;
@@ -225,6 +225,72 @@ impl Arbitrary for ArrayType {
     }
 }
 
+/// Describes how the values of a dimension label are ordered
+/// relative to the dimension which they label.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "option-subset", derive(OptionSubset))]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum DataOrder {
+    Unordered,
+    Increasing,
+    Decreasing,
+}
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum DataOrderError {
+    #[error("Invalid discriminant for {}: {0}", std::any::type_name::<DataOrder>())]
+    InvalidDiscriminant(u64),
+}
+
+impl From<DataOrder> for ffi::tiledb_data_order_t {
+    fn from(value: DataOrder) -> Self {
+        match value {
+            DataOrder::Unordered => {
+                ffi::tiledb_data_order_t_TILEDB_UNORDERED_DATA
+            }
+            DataOrder::Increasing => {
+                ffi::tiledb_data_order_t_TILEDB_INCREASING_DATA
+            }
+            DataOrder::Decreasing => {
+                ffi::tiledb_data_order_t_TILEDB_DECREASING_DATA
+            }
+        }
+    }
+}
+
+impl TryFrom<ffi::tiledb_data_order_t> for DataOrder {
+    type Error = DataOrderError;
+    fn try_from(value: ffi::tiledb_data_order_t) -> Result<Self, Self::Error> {
+        match value {
+            ffi::tiledb_data_order_t_TILEDB_UNORDERED_DATA => {
+                Ok(DataOrder::Unordered)
+            }
+            ffi::tiledb_data_order_t_TILEDB_INCREASING_DATA => {
+                Ok(DataOrder::Increasing)
+            }
+            ffi::tiledb_data_order_t_TILEDB_DECREASING_DATA => {
+                Ok(DataOrder::Decreasing)
+            }
+            _ => Err(DataOrderError::InvalidDiscriminant(value as u64)),
+        }
+    }
+}
+
+#[cfg(feature = "proptest-strategies")]
+impl Arbitrary for DataOrder {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(DataOrder::Unordered),
+            Just(DataOrder::Increasing),
+            Just(DataOrder::Decreasing)
+        ]
+        .boxed()
+    }
+}
+
 /// Represents the number of values carried within a single cell of an attribute or dimension.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "option-subset", derive(OptionSubset))]
@@ -375,6 +441,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ffi_data_order() {
+        for d in [
+            DataOrder::Unordered,
+            DataOrder::Increasing,
+            DataOrder::Decreasing,
+        ] {
+            assert_eq!(
+                d,
+                DataOrder::try_from(ffi::tiledb_data_order_t::from(d)).unwrap()
+            );
+        }
+    }
+
     #[test]
     fn ffi_array_type() {
         for a in [ArrayType::Dense, ArrayType::Sparse] {
@@ -23,10 +23,20 @@ pub enum Value {
     Int64Value(Vec<i64>),
     Float32Value(Vec<f32>),
     Float64Value(Vec<f64>),
+    /// Text for metadata of type [Datatype::StringAscii] or [Datatype::StringUtf8].
+    StringValue(String),
+    /// Opaque bytes for metadata of type [Datatype::Blob].
+    BlobValue(Vec<u8>),
+    /// Flags for metadata of type [Datatype::Boolean].
+    BoolValue(Vec<bool>),
 }
 
 /// Applies a generic expression to the interior of a `Value`.
 ///
+/// `StringValue` binds the `String` itself with `u8` as its type,
+/// and `BoolValue` binds a `Vec<bool>` with `bool` as its type.
+/// Both have the same memory layout as their `u8` counterparts.
+///
 /// # Examples
 /// ```
 /// use tiledb_common::metadata::Value;
@@ -85,6 +95,18 @@ macro_rules! metadata_value_go {
                 type $typename = f64;
                 $then
             }
+            Value::StringValue($vec) => {
+                type $typename = u8;
+                $then
+            }
+            Value::BlobValue($vec) => {
+                type $typename = u8;
+                $then
+            }
+            Value::BoolValue($vec) => {
+                type $typename = bool;
+                $then
+            }
         }
     }};
 }
@@ -138,6 +160,18 @@ macro_rules! value_cmp {
                 type $typename = f64;
                 $same_type
             }
+            (Value::StringValue($lpat), Value::StringValue($rpat)) => {
+                type $typename = u8;
+                $same_type
+            }
+            (Value::BlobValue($lpat), Value::BlobValue($rpat)) => {
+                type $typename = u8;
+                $same_type
+            }
+            (Value::BoolValue($lpat), Value::BoolValue($rpat)) => {
+                type $typename = bool;
+                $same_type
+            }
             _ => $else,
         }
     }};
@@ -151,6 +185,58 @@ impl Value {
     pub fn is_empty(&self) -> bool {
         metadata_value_go!(self, _DT, ref v, v.is_empty())
     }
+
+    /// Converts this value into the variant which represents
+    /// metadata of type `datatype`.
+    ///
+    /// Bytes become a `BlobValue`, `BoolValue`, or `StringValue`
+    /// if `datatype` calls for one. Bytes of a string type which are not
+    /// valid UTF-8 are left as a `UInt8Value`.
+    /// Returns an error if this value cannot represent `datatype`.
+    pub fn conform(self, datatype: Datatype) -> Result<Self, DatatypeError> {
+        match (datatype, self) {
+            (Datatype::Blob, Value::UInt8Value(bytes)) => {
+                Ok(Value::BlobValue(bytes))
+            }
+            (Datatype::Boolean, Value::UInt8Value(bytes)) => Ok(
+                Value::BoolValue(bytes.into_iter().map(|b| b != 0).collect()),
+            ),
+            (
+                Datatype::StringAscii | Datatype::StringUtf8,
+                Value::UInt8Value(bytes),
+            ) => Ok(match String::from_utf8(bytes) {
+                Ok(s) => Value::StringValue(s),
+                Err(e) => Value::UInt8Value(e.into_bytes()),
+            }),
+            (Datatype::Blob, value @ Value::BlobValue(_))
+            | (Datatype::Boolean, value @ Value::BoolValue(_))
+            | (
+                Datatype::StringAscii | Datatype::StringUtf8,
+                value @ Value::StringValue(_),
+            ) => Ok(value),
+            (datatype, Value::StringValue(_)) => Err(
+                DatatypeError::physical_type_incompatible::<String>(datatype),
+            ),
+            (datatype, Value::BlobValue(_)) => Err(
+                DatatypeError::physical_type_incompatible::<Vec<u8>>(datatype),
+            ),
+            (datatype, Value::BoolValue(_)) => {
+                Err(DatatypeError::physical_type_incompatible::<bool>(datatype))
+            }
+            (datatype, value) => {
+                metadata_value_go!(&value, DT, _, {
+                    if !datatype.is_compatible_type::<DT>() {
+                        return Err(
+                            DatatypeError::physical_type_incompatible::<DT>(
+                                datatype,
+                            ),
+                        );
+                    }
+                });
+                Ok(value)
+            }
+        }
+    }
 }
 
 macro_rules! metadata_value_impl {
@@ -173,8 +259,22 @@ metadata_value_impl!(u32, Value::UInt32Value);
 metadata_value_impl!(u64, Value::UInt64Value);
 metadata_value_impl!(f32, Value::Float32Value);
 metadata_value_impl!(f64, Value::Float64Value);
+metadata_value_impl!(bool, Value::BoolValue);
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::StringValue(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::StringValue(s.to_owned())
+    }
+}
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Metadata {
     pub key: String,
     pub datatype: Datatype,
@@ -182,24 +282,38 @@ pub struct Metadata {
 }
 
 impl Metadata {
-    pub fn new<T>(
+    /// Creates metadata of type `datatype`.
+    ///
+    /// `value` is converted into the [Value] variant which represents
+    /// `datatype`, so for example a `Vec<u8>` can be used
+    /// for any of the byte-sized types.
+    ///
+    /// ```
+    /// use tiledb_common::datatype::Datatype;
+    /// use tiledb_common::metadata::{Metadata, Value};
+    ///
+    /// let m = Metadata::new("key".to_owned(), Datatype::StringUtf8, "value")
+    ///     .unwrap();
+    /// assert_eq!(m.value, Value::StringValue("value".to_owned()));
+    ///
+    /// let m = Metadata::new("key".to_owned(), Datatype::Boolean, vec![0u8, 1])
+    ///     .unwrap();
+    /// assert_eq!(m.value, Value::BoolValue(vec![false, true]));
+    ///
+    /// assert!(Metadata::new("key".to_owned(), Datatype::Int32, "value").is_err());
+    /// ```
+    pub fn new<V>(
         key: String,
         datatype: Datatype,
-        vec: Vec<T>,
+        value: V,
     ) -> Result<Self, DatatypeError>
     where
-        Value: From<Vec<T>>,
-        T: 'static,
+        Value: From<V>,
     {
-        if !datatype.is_compatible_type::<T>() {
-            return Err(DatatypeError::physical_type_incompatible::<T>(
-                datatype,
-            ));
-        }
         Ok(Metadata {
             key,
             datatype,
-            value: Value::from(vec),
+            value: Value::from(value).conform(datatype)?,
         })
     }
 }
@@ -244,11 +358,30 @@ pub mod strategy {
             params
                 .datatype
                 .prop_flat_map(move |dt| {
-                    let value_strat = physical_type_go!(dt, DT, {
-                        vec(any::<DT>(), params.value_length.clone())
+                    let value_length = params.value_length.clone();
+                    let value_strat = match dt {
+                        Datatype::StringAscii => {
+                            vec(proptest::char::range(' ', '~'), value_length)
+                                .prop_map(|c| Value::from(String::from_iter(c)))
+                                .boxed()
+                        }
+                        Datatype::StringUtf8 => {
+                            vec(any::<char>(), value_length)
+                                .prop_map(|c| Value::from(String::from_iter(c)))
+                                .boxed()
+                        }
+                        Datatype::Blob => vec(any::<u8>(), value_length)
+                            .prop_map(Value::BlobValue)
+                            .boxed(),
+                        Datatype::Boolean => vec(any::<bool>(), value_length)
                             .prop_map(Value::from)
-                            .boxed()
-                    });
+                            .boxed(),
+                        _ => physical_type_go!(dt, DT, {
+                            vec(any::<DT>(), value_length)
+                                .prop_map(Value::from)
+                                .boxed()
+                        }),
+                    };
                     (params.key.clone(), Just(dt), value_strat)
                 })
                 .prop_map(|(key, datatype, value)| Metadata {
@@ -266,24 +399,82 @@ mod tests {
     use super::*;
     use proptest::prelude::*;
 
+    /// Returns the `Value` variant which is expected for `datatype`,
+    /// with the numeric variants identified by their physical type.
+    fn value_class(datatype: Datatype) -> Result<&'static str, Datatype> {
+        match datatype {
+            Datatype::StringAscii | Datatype::StringUtf8 => Ok("string"),
+            Datatype::Blob => Ok("blob"),
+            Datatype::Boolean => Ok("bool"),
+            dt => Err(dt),
+        }
+    }
+
     fn do_value_cmp(m1: Metadata, m2: Metadata) {
-        if m1.datatype.same_physical_type(&m2.datatype) {
+        let same_class =
+            match (value_class(m1.datatype), value_class(m2.datatype)) {
+                (Ok(c1), Ok(c2)) => c1 == c2,
+                (Err(dt1), Err(dt2)) => dt1.same_physical_type(&dt2),
+                _ => false,
+            };
+        if same_class {
             value_cmp!(&m1.value, &m2.value, _DT, _, _,
                 (),
-                unreachable!("Non-matching `Value` variants for same physical type: {:?} and {:?}",
+                unreachable!("Non-matching `Value` variants for same value class: {:?} and {:?}",
                     m1, m2));
         } else {
             value_cmp!(&m1.value, &m2.value, _DT, _, _,
-                unreachable!("Matching `Value` variants for different physical type: {:?} and {:?}",
+                unreachable!("Matching `Value` variants for different value class: {:?} and {:?}",
                     m1, m2),
                 ());
         }
     }
 
+    #[test]
+    fn conform() {
+        let bytes = || Value::UInt8Value(vec![0, 1, 2]);
+
+        assert_eq!(bytes(), bytes().conform(Datatype::UInt8).unwrap());
+        assert_eq!(
+            Value::BlobValue(vec![0, 1, 2]),
+            bytes().conform(Datatype::Blob).unwrap()
+        );
+        assert_eq!(
+            Value::BoolValue(vec![false, true, true]),
+            bytes().conform(Datatype::Boolean).unwrap()
+        );
+        assert_eq!(
+            Value::StringValue("\0\u{1}\u{2}".to_owned()),
+            bytes().conform(Datatype::StringAscii).unwrap()
+        );
+
+        // invalid UTF-8 is left as bytes
+        let invalid = Value::UInt8Value(vec![0xC3, 0x28]);
+        assert_eq!(
+            invalid.clone(),
+            invalid.conform(Datatype::StringUtf8).unwrap()
+        );
+
+        let s = Value::from("foo");
+        assert_eq!(s.clone(), s.clone().conform(Datatype::StringUtf8).unwrap());
+        assert!(s.conform(Datatype::UInt8).is_err());
+        assert!(Value::from(vec![true]).conform(Datatype::UInt8).is_err());
+        assert!(Value::BlobValue(vec![1]).conform(Datatype::UInt8).is_err());
+        assert!(Value::from(vec![1i32]).conform(Datatype::Int64).is_err());
+        assert!(Value::from(vec![1i64])
+            .conform(Datatype::DateTimeSecond)
+            .is_ok());
+    }
+
     proptest! {
         #[test]
         fn value_cmp((m1, m2) in (any::<Metadata>(), any::<Metadata>())) {
             do_value_cmp(m1, m2)
         }
+
+        #[test]
+        fn conform_arbitrary(m in any::<Metadata>()) {
+            assert_eq!(m.value.clone(), m.value.conform(m.datatype).unwrap())
+        }
     }
 }
@@ -0,0 +1,554 @@
+//! Evaluates a [QueryConditionExpr] against in-memory data.
+//!
+//! This applies the same filter which libtiledb applies to the cells
+//! of an array, without requiring libtiledb. Data is provided one
+//! row at a time using the [Row] trait.
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use tiledb_common::query::condition::eval::CellValue;
+//! use tiledb_common::query::condition::QueryConditionExpr as QC;
+//!
+//! let row = HashMap::from([
+//!     ("a".to_owned(), CellValue::Int32(7)),
+//!     ("b".to_owned(), CellValue::Null),
+//! ]);
+//!
+//! assert!(QC::field("a").gt(5).evaluate(&row).unwrap());
+//! assert!(QC::field("b").is_null().evaluate(&row).unwrap());
+//! assert!(!QC::field("b").lt(5).evaluate(&row).unwrap());
+//! assert!(!(!QC::field("b").lt(5)).evaluate(&row).unwrap());
+//! ```
+//!
+//! The result of evaluating a predicate follows libtiledb:
+//! * A null value satisfies `IS NULL`, `<>`, and `NOT IN`, and no other
+//!   predicate.
+//! * Floating-point values are compared using IEEE 754 semantics,
+//!   so a NaN value satisfies `<>` and no other comparison.
+//!   Set membership compares floating-point values by their bits.
+//! * Strings are compared lexicographically by their bytes.
+//! * `NOT` is applied by negating each predicate beneath it, and
+//!   exchanging `AND` with `OR`. As a result `NOT (a < 5)` is equivalent
+//!   to `a >= 5`, which is not satisfied by a null value or NaN.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use thiserror::Error;
+
+use super::*;
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum Error {
+    #[error("No such field: {0}")]
+    NoSuchField(String),
+    #[error("Field '{0}' does not contain single values or strings")]
+    UnsupportedField(String),
+    #[error("Field '{field}' has type {found} which cannot be compared with a value of type {expected}")]
+    TypeMismatch {
+        field: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+}
+
+/// The value of one field in a single row.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CellValue<'a> {
+    Null,
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    Float32(f32),
+    Float64(f64),
+    /// A variable-length string, which need not be valid UTF-8.
+    String(&'a [u8]),
+}
+
+impl CellValue<'_> {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::UInt8(_) => "u8",
+            Self::UInt16(_) => "u16",
+            Self::UInt32(_) => "u32",
+            Self::UInt64(_) => "u64",
+            Self::Int8(_) => "i8",
+            Self::Int16(_) => "i16",
+            Self::Int32(_) => "i32",
+            Self::Int64(_) => "i64",
+            Self::Float32(_) => "f32",
+            Self::Float64(_) => "f64",
+            Self::String(_) => "string",
+        }
+    }
+}
+
+macro_rules! cell_value_from_impl {
+    ($($V:ident : $U:ty),+) => {
+        $(
+            impl From<$U> for CellValue<'_> {
+                fn from(value: $U) -> Self {
+                    CellValue::$V(value)
+                }
+            }
+        )+
+    };
+}
+
+cell_value_from_impl!(UInt8: u8, UInt16: u16, UInt32: u32, UInt64: u64);
+cell_value_from_impl!(Int8: i8, Int16: i16, Int32: i32, Int64: i64);
+cell_value_from_impl!(Float32: f32, Float64: f64);
+
+impl<'a> From<&'a str> for CellValue<'a> {
+    fn from(value: &'a str) -> Self {
+        CellValue::String(value.as_bytes())
+    }
+}
+
+impl<'a, T> From<Option<T>> for CellValue<'a>
+where
+    T: Into<CellValue<'a>>,
+{
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(CellValue::Null)
+    }
+}
+
+/// Provides access to the fields of a single row.
+pub trait Row {
+    /// Returns the value of `field` in this row.
+    fn value(&self, field: &str) -> Result<CellValue<'_>, Error>;
+}
+
+impl<K> Row for HashMap<K, CellValue<'_>>
+where
+    K: Borrow<str> + Eq + Hash,
+{
+    fn value(&self, field: &str) -> Result<CellValue<'_>, Error> {
+        self.get(field)
+            .copied()
+            .ok_or_else(|| Error::NoSuchField(field.to_owned()))
+    }
+}
+
+impl<R> Row for &R
+where
+    R: Row + ?Sized,
+{
+    fn value(&self, field: &str) -> Result<CellValue<'_>, Error> {
+        (**self).value(field)
+    }
+}
+
+impl EqualityOp {
+    fn negate(self) -> Self {
+        match self {
+            Self::Less => Self::GreaterEqual,
+            Self::LessEqual => Self::Greater,
+            Self::Equal => Self::NotEqual,
+            Self::NotEqual => Self::Equal,
+            Self::GreaterEqual => Self::Less,
+            Self::Greater => Self::LessEqual,
+        }
+    }
+
+    fn accepts(&self, cmp: Option<Ordering>) -> bool {
+        match self {
+            Self::Less => cmp == Some(Ordering::Less),
+            Self::LessEqual => {
+                matches!(cmp, Some(Ordering::Less | Ordering::Equal))
+            }
+            Self::Equal => cmp == Some(Ordering::Equal),
+            Self::NotEqual => cmp != Some(Ordering::Equal),
+            Self::GreaterEqual => {
+                matches!(cmp, Some(Ordering::Greater | Ordering::Equal))
+            }
+            Self::Greater => cmp == Some(Ordering::Greater),
+        }
+    }
+}
+
+impl SetMembershipOp {
+    fn negate(self) -> Self {
+        match self {
+            Self::In => Self::NotIn,
+            Self::NotIn => Self::In,
+        }
+    }
+}
+
+impl NullnessOp {
+    fn negate(self) -> Self {
+        match self {
+            Self::IsNull => Self::NotNull,
+            Self::NotNull => Self::IsNull,
+        }
+    }
+}
+
+impl CombinationOp {
+    fn negate(self) -> Self {
+        match self {
+            Self::And => Self::Or,
+            Self::Or => Self::And,
+        }
+    }
+}
+
+impl Literal {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::UInt8(_) => "u8",
+            Self::UInt16(_) => "u16",
+            Self::UInt32(_) => "u32",
+            Self::UInt64(_) => "u64",
+            Self::Int8(_) => "i8",
+            Self::Int16(_) => "i16",
+            Self::Int32(_) => "i32",
+            Self::Int64(_) => "i64",
+            Self::Float32(_) => "f32",
+            Self::Float64(_) => "f64",
+            Self::String(_) => "string",
+        }
+    }
+}
+
+impl SetMembers {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::UInt8(_) => "u8",
+            Self::UInt16(_) => "u16",
+            Self::UInt32(_) => "u32",
+            Self::UInt64(_) => "u64",
+            Self::Int8(_) => "i8",
+            Self::Int16(_) => "i16",
+            Self::Int32(_) => "i32",
+            Self::Int64(_) => "i64",
+            Self::Float32(_) => "f32",
+            Self::Float64(_) => "f64",
+            Self::String(_) => "string",
+        }
+    }
+}
+
+/// Compares a non-null cell value with a literal.
+fn compare(
+    field: &str,
+    cell: CellValue,
+    value: &Literal,
+) -> Result<Option<Ordering>, Error> {
+    Ok(match (cell, value) {
+        (CellValue::UInt8(c), Literal::UInt8(v)) => c.partial_cmp(v),
+        (CellValue::UInt16(c), Literal::UInt16(v)) => c.partial_cmp(v),
+        (CellValue::UInt32(c), Literal::UInt32(v)) => c.partial_cmp(v),
+        (CellValue::UInt64(c), Literal::UInt64(v)) => c.partial_cmp(v),
+        (CellValue::Int8(c), Literal::Int8(v)) => c.partial_cmp(v),
+        (CellValue::Int16(c), Literal::Int16(v)) => c.partial_cmp(v),
+        (CellValue::Int32(c), Literal::Int32(v)) => c.partial_cmp(v),
+        (CellValue::Int64(c), Literal::Int64(v)) => c.partial_cmp(v),
+        (CellValue::Float32(c), Literal::Float32(v)) => c.partial_cmp(v),
+        (CellValue::Float64(c), Literal::Float64(v)) => c.partial_cmp(v),
+        (CellValue::String(c), Literal::String(v)) => Some(c.cmp(v.as_bytes())),
+        _ => {
+            return Err(Error::TypeMismatch {
+                field: field.to_owned(),
+                expected: value.type_name(),
+                found: cell.type_name(),
+            })
+        }
+    })
+}
+
+/// Returns whether a non-null cell value is a member of a set.
+fn contains(
+    field: &str,
+    cell: CellValue,
+    members: &SetMembers,
+) -> Result<bool, Error> {
+    Ok(match (cell, members) {
+        (CellValue::UInt8(c), SetMembers::UInt8(m)) => m.contains(&c),
+        (CellValue::UInt16(c), SetMembers::UInt16(m)) => m.contains(&c),
+        (CellValue::UInt32(c), SetMembers::UInt32(m)) => m.contains(&c),
+        (CellValue::UInt64(c), SetMembers::UInt64(m)) => m.contains(&c),
+        (CellValue::Int8(c), SetMembers::Int8(m)) => m.contains(&c),
+        (CellValue::Int16(c), SetMembers::Int16(m)) => m.contains(&c),
+        (CellValue::Int32(c), SetMembers::Int32(m)) => m.contains(&c),
+        (CellValue::Int64(c), SetMembers::Int64(m)) => m.contains(&c),
+        (CellValue::Float32(c), SetMembers::Float32(m)) => {
+            m.iter().any(|v| v.bits_eq(&c))
+        }
+        (CellValue::Float64(c), SetMembers::Float64(m)) => {
+            m.iter().any(|v| v.bits_eq(&c))
+        }
+        (CellValue::String(c), SetMembers::String(m)) => {
+            m.iter().any(|v| v.as_bytes() == c)
+        }
+        _ => {
+            return Err(Error::TypeMismatch {
+                field: field.to_owned(),
+                expected: members.type_name(),
+                found: cell.type_name(),
+            })
+        }
+    })
+}
+
+impl EqualityPredicate {
+    fn evaluate<R>(&self, row: &R, negate: bool) -> Result<bool, Error>
+    where
+        R: Row + ?Sized,
+    {
+        let op = if negate { self.op.negate() } else { self.op };
+        match row.value(&self.field)? {
+            CellValue::Null => Ok(op == EqualityOp::NotEqual),
+            cell => Ok(op.accepts(compare(&self.field, cell, &self.value)?)),
+        }
+    }
+}
+
+impl SetMembershipPredicate {
+    fn evaluate<R>(&self, row: &R, negate: bool) -> Result<bool, Error>
+    where
+        R: Row + ?Sized,
+    {
+        let op = if negate { self.op.negate() } else { self.op };
+        match row.value(&self.field)? {
+            CellValue::Null => Ok(op == SetMembershipOp::NotIn),
+            cell => {
+                let found = contains(&self.field, cell, &self.members)?;
+                Ok(found == (op == SetMembershipOp::In))
+            }
+        }
+    }
+}
+
+impl NullnessPredicate {
+    fn evaluate<R>(&self, row: &R, negate: bool) -> Result<bool, Error>
+    where
+        R: Row + ?Sized,
+    {
+        let op = if negate { self.op.negate() } else { self.op };
+        let is_null = matches!(row.value(&self.field)?, CellValue::Null);
+        Ok(is_null == (op == NullnessOp::IsNull))
+    }
+}
+
+impl Predicate {
+    fn evaluate<R>(&self, row: &R, negate: bool) -> Result<bool, Error>
+    where
+        R: Row + ?Sized,
+    {
+        match self {
+            Self::Equality(pred) => pred.evaluate(row, negate),
+            Self::SetMembership(pred) => pred.evaluate(row, negate),
+            Self::Nullness(pred) => pred.evaluate(row, negate),
+        }
+    }
+}
+
+impl QueryConditionExpr {
+    /// Returns whether `row` satisfies this condition.
+    ///
+    /// See the [module documentation](self) for how each predicate
+    /// is evaluated.
+    pub fn evaluate<R>(&self, row: &R) -> Result<bool, Error>
+    where
+        R: Row + ?Sized,
+    {
+        self.evaluate_impl(row, false)
+    }
+
+    fn evaluate_impl<R>(&self, row: &R, negate: bool) -> Result<bool, Error>
+    where
+        R: Row + ?Sized,
+    {
+        match self {
+            Self::Cond(pred) => pred.evaluate(row, negate),
+            Self::Comb { lhs, rhs, op } => {
+                let op = if negate { op.negate() } else { *op };
+                let lhs = lhs.evaluate_impl(row, negate)?;
+                let rhs = rhs.evaluate_impl(row, negate)?;
+                Ok(match op {
+                    CombinationOp::And => lhs && rhs,
+                    CombinationOp::Or => lhs || rhs,
+                })
+            }
+            Self::Negate(expr) => expr.evaluate_impl(row, !negate),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::QueryConditionExpr as QC;
+    use super::*;
+
+    fn row<'a>(
+        values: impl IntoIterator<Item = (&'a str, CellValue<'a>)>,
+    ) -> HashMap<&'a str, CellValue<'a>> {
+        values.into_iter().collect()
+    }
+
+    #[test]
+    fn comparisons() {
+        let r = row([("a", CellValue::Int32(5)), ("s", "foo".into())]);
+
+        let eval = |qc: QC| qc.evaluate(&r).unwrap();
+
+        assert!(!eval(QC::field("a").lt(5)));
+        assert!(eval(QC::field("a").le(5)));
+        assert!(eval(QC::field("a").eq(5)));
+        assert!(!eval(QC::field("a").ne(5)));
+        assert!(eval(QC::field("a").ge(5)));
+        assert!(!eval(QC::field("a").gt(5)));
+        assert!(eval(QC::field("a").is_in(&[1, 5][..])));
+        assert!(!eval(QC::field("a").not_in(&[1, 5][..])));
+        assert!(!eval(QC::field("a").is_null()));
+        assert!(eval(QC::field("a").not_null()));
+
+        assert!(eval(QC::field("s").lt("foobar")));
+        assert!(eval(QC::field("s").gt("fo")));
+        assert!(eval(QC::field("s").gt("Foo")));
+        assert!(eval(QC::field("s").is_in(&["bar", "foo"][..])));
+
+        assert!(eval(QC::field("a").gt(4) & QC::field("s").eq("foo")));
+        assert!(!eval(QC::field("a").gt(5) & QC::field("s").eq("foo")));
+        assert!(eval(QC::field("a").gt(5) | QC::field("s").eq("foo")));
+        assert!(eval(!QC::field("a").gt(5)));
+    }
+
+    #[test]
+    fn nulls() {
+        let r = row([("a", CellValue::Null)]);
+
+        let eval = |qc: QC| qc.evaluate(&r).unwrap();
+
+        assert!(!eval(QC::field("a").lt(5)));
+        assert!(!eval(QC::field("a").le(5)));
+        assert!(!eval(QC::field("a").eq(5)));
+        assert!(eval(QC::field("a").ne(5)));
+        assert!(!eval(QC::field("a").ge(5)));
+        assert!(!eval(QC::field("a").gt(5)));
+        assert!(!eval(QC::field("a").is_in(&[5][..])));
+        assert!(eval(QC::field("a").not_in(&[5][..])));
+        assert!(eval(QC::field("a").is_null()));
+        assert!(!eval(QC::field("a").not_null()));
+
+        // negation is not boolean negation
+        assert!(!eval(!QC::field("a").lt(5)));
+        assert!(eval(!QC::field("a").eq(5)));
+        assert!(!eval(!QC::field("a").ne(5)));
+        assert!(!eval(!QC::field("a").not_in(&[5][..])));
+        assert!(!eval(!(QC::field("a").lt(5) | QC::field("a").ge(5))));
+        assert!(eval(!!QC::field("a").ne(5)));
+    }
+
+    #[test]
+    fn nan() {
+        let r = row([("f", CellValue::Float64(f64::NAN))]);
+
+        let eval = |qc: QC| qc.evaluate(&r).unwrap();
+
+        assert!(!eval(QC::field("f").lt(1.0)));
+        assert!(!eval(QC::field("f").eq(f64::NAN)));
+        assert!(eval(QC::field("f").ne(f64::NAN)));
+        assert!(!eval(!QC::field("f").lt(1.0)));
+        assert!(eval(QC::field("f").is_in(&[f64::NAN][..])));
+
+        let r = row([("f", CellValue::Float64(-0.0))]);
+        assert!(QC::field("f").eq(0.0).evaluate(&r).unwrap());
+        assert!(!QC::field("f").is_in(&[0.0][..]).evaluate(&r).unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        let r = row([("a", CellValue::Int32(5))]);
+
+        assert_eq!(
+            Err(Error::NoSuchField("b".to_owned())),
+            QC::field("b").lt(5).evaluate(&r)
+        );
+        assert_eq!(
+            Err(Error::TypeMismatch {
+                field: "a".to_owned(),
+                expected: "i64",
+                found: "i32"
+            }),
+            QC::field("a").lt(5i64).evaluate(&r)
+        );
+        assert_eq!(
+            Err(Error::TypeMismatch {
+                field: "a".to_owned(),
+                expected: "string",
+                found: "i32"
+            }),
+            QC::field("a").is_in(&["5"][..]).evaluate(&r)
+        );
+    }
+
+    /// Produces conditions over `i32` fields "a" and "b".
+    fn prop_int_condition() -> impl Strategy<Value = QC> {
+        use proptest::collection::vec;
+
+        use crate::query::condition::strategy::{
+            prop_query_condition, QueryConditionParameters,
+        };
+
+        let field = prop_oneof![Just("a"), Just("b")];
+        let leaf = prop_oneof![
+            (field.clone(), any::<i32>(), 0..6).prop_map(|(f, v, op)| {
+                let f = QC::field(f);
+                match op {
+                    0 => f.lt(v),
+                    1 => f.le(v),
+                    2 => f.eq(v),
+                    3 => f.ne(v),
+                    4 => f.ge(v),
+                    _ => f.gt(v),
+                }
+            }),
+            (field.clone(), vec(any::<i32>(), 1..4), any::<bool>()).prop_map(
+                |(f, members, is_in)| {
+                    if is_in {
+                        QC::field(f).is_in(members.as_slice())
+                    } else {
+                        QC::field(f).not_in(members.as_slice())
+                    }
+                }
+            ),
+            (field, any::<bool>()).prop_map(|(f, is_null)| {
+                if is_null {
+                    QC::field(f).is_null()
+                } else {
+                    QC::field(f).not_null()
+                }
+            }),
+        ];
+        prop_query_condition(leaf, &QueryConditionParameters::default())
+    }
+
+    proptest! {
+        /// Without nulls or NaNs, `NOT` gives the boolean negation.
+        #[test]
+        fn negate_without_nulls(
+            a in any::<i32>(), b in any::<i32>(), qc in prop_int_condition()
+        ) {
+            let r = row([("a", a.into()), ("b", b.into())]);
+            prop_assert_eq!(
+                !qc.evaluate(&r).unwrap(),
+                (!qc).evaluate(&r).unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,883 @@
+//! Parses the textual representation of a [QueryConditionExpr].
+//!
+//! The syntax is a small subset of SQL `WHERE` clauses, and is the same
+//! syntax which the [Display] implementation of [QueryConditionExpr]
+//! writes:
+//!
+//! ```
+//! use tiledb_common::query::condition::QueryConditionExpr as QC;
+//!
+//! let qc = "a > 5 AND (b IN ('x', 'y') OR c IS NULL)"
+//!     .parse::<QC>()
+//!     .unwrap();
+//! assert_eq!(
+//!     QC::field("a").gt(5)
+//!         & (QC::field("b").is_in(&["x", "y"][..]) | QC::field("c").is_null()),
+//!     qc
+//! );
+//! ```
+//!
+//! * Predicates compare a field with a literal using one of
+//!   `<`, `<=`, `=`, `<>`, `>=`, `>` (or `==` and `!=`),
+//!   test set membership using `IN` and `NOT IN`,
+//!   or test nullness using `IS NULL` and `IS NOT NULL`.
+//! * Predicates are combined using `AND`, `OR`, `NOT`, and parentheses.
+//!   `NOT` binds most tightly, followed by `AND` and then `OR`.
+//! * Keywords are case-insensitive.
+//! * A field name is either an identifier, or is enclosed in double quotes.
+//! * String literals are enclosed in single quotes.
+//!   Both quoted field names and string literals use the same
+//!   backslash escape sequences as Rust string literals.
+//! * Numeric literals may have a type suffix as in Rust, such as `5u8`
+//!   or `1.5f32`. Without a suffix an integer is an `Int32`, or an `Int64`
+//!   or `UInt64` if it does not fit, and a floating-point number is
+//!   a `Float64`. The members of a set all have the same type.
+
+use std::ops::Range;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::*;
+
+const KEYWORDS: [&str; 6] = ["AND", "OR", "NOT", "IN", "IS", "NULL"];
+
+/// Returns whether `s` can be written as a field name without quotes.
+pub(crate) fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    let is_identifier = match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    };
+    is_identifier && !KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(s))
+}
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum ParseErrorKind {
+    #[error("Unexpected character '{0}'")]
+    UnexpectedCharacter(char),
+    #[error("Unterminated quoted text")]
+    UnterminatedQuote,
+    #[error("Invalid escape sequence")]
+    InvalidEscape,
+    #[error("Invalid numeric literal type suffix '{0}'")]
+    InvalidSuffix(String),
+    #[error("Invalid {datatype} literal '{literal}'")]
+    InvalidNumber {
+        literal: String,
+        datatype: &'static str,
+    },
+    #[error("Expected {expected}, found {found}")]
+    UnexpectedToken {
+        expected: &'static str,
+        found: String,
+    },
+    #[error("Expected {0}, found end of input")]
+    UnexpectedEnd(&'static str),
+    #[error("Set members must not be empty")]
+    EmptySet,
+    #[error("Set members must all have the same type")]
+    MixedSetMembers,
+}
+
+/// An error parsing a [QueryConditionExpr], and where in the input it was found.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[error("{kind} at {}..{}", span.start, span.end)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    /// Byte range of the input which caused the error.
+    pub span: Range<usize>,
+}
+
+impl ParseError {
+    fn new(kind: ParseErrorKind, span: Range<usize>) -> Self {
+        ParseError { kind, span }
+    }
+}
+
+impl FromStr for QueryConditionExpr {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            end: s.len(),
+        };
+        let expr = parser.expr()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(expr),
+            Some(t) => Err(ParseError::new(
+                ParseErrorKind::UnexpectedToken {
+                    expected: "end of input",
+                    found: t.token.to_string(),
+                },
+                t.span.clone(),
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum NumberType {
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+}
+
+impl NumberType {
+    fn from_suffix(suffix: &str) -> Option<Self> {
+        Some(match suffix {
+            "u8" => Self::UInt8,
+            "u16" => Self::UInt16,
+            "u32" => Self::UInt32,
+            "u64" => Self::UInt64,
+            "i8" => Self::Int8,
+            "i16" => Self::Int16,
+            "i32" => Self::Int32,
+            "i64" => Self::Int64,
+            "f32" => Self::Float32,
+            "f64" => Self::Float64,
+            _ => return None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::UInt8 => "UInt8",
+            Self::UInt16 => "UInt16",
+            Self::UInt32 => "UInt32",
+            Self::UInt64 => "UInt64",
+            Self::Int8 => "Int8",
+            Self::Int16 => "Int16",
+            Self::Int32 => "Int32",
+            Self::Int64 => "Int64",
+            Self::Float32 => "Float32",
+            Self::Float64 => "Float64",
+        }
+    }
+
+    fn literal(&self, text: &str) -> Option<Literal> {
+        match self {
+            Self::UInt8 => text.parse::<u8>().ok().map(Literal::UInt8),
+            Self::UInt16 => text.parse::<u16>().ok().map(Literal::UInt16),
+            Self::UInt32 => text.parse::<u32>().ok().map(Literal::UInt32),
+            Self::UInt64 => text.parse::<u64>().ok().map(Literal::UInt64),
+            Self::Int8 => text.parse::<i8>().ok().map(Literal::Int8),
+            Self::Int16 => text.parse::<i16>().ok().map(Literal::Int16),
+            Self::Int32 => text.parse::<i32>().ok().map(Literal::Int32),
+            Self::Int64 => text.parse::<i64>().ok().map(Literal::Int64),
+            Self::Float32 => text.parse::<f32>().ok().map(Literal::Float32),
+            Self::Float64 => text.parse::<f64>().ok().map(Literal::Float64),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Field(String),
+    String(String),
+    Number {
+        text: String,
+        suffix: Option<NumberType>,
+        is_float: bool,
+    },
+    Op(EqualityOp),
+    LParen,
+    RParen,
+    Comma,
+    And,
+    Or,
+    Not,
+    In,
+    Is,
+    Null,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Self::Field(name) => write!(f, "field {}", FieldName(name)),
+            Self::String(s) => write!(f, "'{}'", escape_string_literal(s)),
+            Self::Number { text, .. } => write!(f, "{}", text),
+            Self::Op(op) => write!(f, "'{}'", op),
+            Self::LParen => write!(f, "'('"),
+            Self::RParen => write!(f, "')'"),
+            Self::Comma => write!(f, "','"),
+            Self::And => write!(f, "AND"),
+            Self::Or => write!(f, "OR"),
+            Self::Not => write!(f, "NOT"),
+            Self::In => write!(f, "IN"),
+            Self::Is => write!(f, "IS"),
+            Self::Null => write!(f, "NULL"),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct SpannedToken {
+    token: Token,
+    span: Range<usize>,
+}
+
+fn tokenize(s: &str) -> Result<Vec<SpannedToken>, ParseError> {
+    let bytes = s.as_bytes();
+    let mut tokens = vec![];
+    let mut pos = 0;
+
+    while pos < s.len() {
+        let start = pos;
+        let c = s[pos..].chars().next().unwrap();
+
+        let token = if c.is_whitespace() {
+            pos += c.len_utf8();
+            continue;
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            while pos < s.len()
+                && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_')
+            {
+                pos += 1;
+            }
+            let word = &s[start..pos];
+            match word.to_ascii_uppercase().as_ref() {
+                "AND" => Token::And,
+                "OR" => Token::Or,
+                "NOT" => Token::Not,
+                "IN" => Token::In,
+                "IS" => Token::Is,
+                "NULL" => Token::Null,
+                _ => Token::Field(word.to_owned()),
+            }
+        } else if c == '"' || c == '\'' {
+            let (unquoted, end) = unquote(s, start)?;
+            pos = end;
+            if c == '"' {
+                Token::Field(unquoted)
+            } else {
+                Token::String(unquoted)
+            }
+        } else if c.is_ascii_digit()
+            || (c == '-' && bytes.get(pos + 1).is_some_and(u8::is_ascii_digit))
+        {
+            let (token, end) = number(s, start)?;
+            pos = end;
+            token
+        } else {
+            let (token, len) = match (c, bytes.get(pos + 1)) {
+                ('(', _) => (Token::LParen, 1),
+                (')', _) => (Token::RParen, 1),
+                (',', _) => (Token::Comma, 1),
+                ('<', Some(b'=')) => (Token::Op(EqualityOp::LessEqual), 2),
+                ('<', Some(b'>')) => (Token::Op(EqualityOp::NotEqual), 2),
+                ('<', _) => (Token::Op(EqualityOp::Less), 1),
+                ('>', Some(b'=')) => (Token::Op(EqualityOp::GreaterEqual), 2),
+                ('>', _) => (Token::Op(EqualityOp::Greater), 1),
+                ('=', Some(b'=')) => (Token::Op(EqualityOp::Equal), 2),
+                ('=', _) => (Token::Op(EqualityOp::Equal), 1),
+                ('!', Some(b'=')) => (Token::Op(EqualityOp::NotEqual), 2),
+                _ => {
+                    return Err(ParseError::new(
+                        ParseErrorKind::UnexpectedCharacter(c),
+                        start..start + c.len_utf8(),
+                    ))
+                }
+            };
+            pos += len;
+            token
+        };
+
+        tokens.push(SpannedToken {
+            token,
+            span: start..pos,
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// Reads quoted text beginning at `start` and undoes its escape sequences.
+/// Returns the text and the position following the closing quote.
+fn unquote(s: &str, start: usize) -> Result<(String, usize), ParseError> {
+    let quote = s[start..].chars().next().unwrap();
+    let mut unquoted = String::new();
+    let mut chars = s[start + 1..]
+        .char_indices()
+        .map(|(i, c)| (start + 1 + i, c));
+
+    while let Some((i, c)) = chars.next() {
+        if c == quote {
+            return Ok((unquoted, i + 1));
+        } else if c != '\\' {
+            unquoted.push(c);
+            continue;
+        }
+
+        let invalid_escape =
+            |end: usize| ParseError::new(ParseErrorKind::InvalidEscape, i..end);
+        let escaped = match chars.next() {
+            Some((_, 'n')) => '\n',
+            Some((_, 'r')) => '\r',
+            Some((_, 't')) => '\t',
+            Some((_, '0')) => '\0',
+            Some((_, '\\')) => '\\',
+            Some((_, '\'')) => '\'',
+            Some((_, '"')) => '"',
+            Some((j, 'u')) => {
+                let mut hex = String::new();
+                let mut end = j + 1;
+                if !matches!(chars.next(), Some((_, '{'))) {
+                    return Err(invalid_escape(end));
+                }
+                loop {
+                    match chars.next() {
+                        Some((k, '}')) => {
+                            end = k + 1;
+                            break;
+                        }
+                        Some((k, h)) if h.is_ascii_hexdigit() => {
+                            hex.push(h);
+                            end = k + 1;
+                        }
+                        Some((k, h)) => {
+                            return Err(invalid_escape(k + h.len_utf8()))
+                        }
+                        None => return Err(invalid_escape(end)),
+                    }
+                }
+                u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| invalid_escape(end))?
+            }
+            Some((j, e)) => return Err(invalid_escape(j + e.len_utf8())),
+            None => break,
+        };
+        unquoted.push(escaped);
+    }
+
+    Err(ParseError::new(
+        ParseErrorKind::UnterminatedQuote,
+        start..s.len(),
+    ))
+}
+
+/// Reads a numeric literal beginning at `start`.
+/// Returns the token and the position following the literal.
+fn number(s: &str, start: usize) -> Result<(Token, usize), ParseError> {
+    let bytes = s.as_bytes();
+    let digits = |mut pos: usize| {
+        while pos < s.len() && bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        pos
+    };
+
+    let mut pos = start;
+    if bytes[pos] == b'-' {
+        pos += 1;
+    }
+    pos = digits(pos);
+
+    let mut is_float = false;
+    if bytes.get(pos) == Some(&b'.') {
+        is_float = true;
+        pos = digits(pos + 1);
+    }
+    if matches!(bytes.get(pos), Some(b'e') | Some(b'E')) {
+        let exponent = match bytes.get(pos + 1) {
+            Some(b'+') | Some(b'-') => pos + 2,
+            _ => pos + 1,
+        };
+        if bytes.get(exponent).is_some_and(u8::is_ascii_digit) {
+            is_float = true;
+            pos = digits(exponent);
+        }
+    }
+    let text = s[start..pos].to_owned();
+
+    let suffix_start = pos;
+    while pos < s.len() && bytes[pos].is_ascii_alphanumeric() {
+        pos += 1;
+    }
+    let suffix = if suffix_start == pos {
+        None
+    } else {
+        let suffix = &s[suffix_start..pos];
+        Some(NumberType::from_suffix(suffix).ok_or_else(|| {
+            ParseError::new(
+                ParseErrorKind::InvalidSuffix(suffix.to_owned()),
+                suffix_start..pos,
+            )
+        })?)
+    };
+
+    Ok((
+        Token::Number {
+            text,
+            suffix,
+            is_float,
+        },
+        pos,
+    ))
+}
+
+struct Parser {
+    tokens: Vec<SpannedToken>,
+    pos: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|t| &t.token)
+    }
+
+    fn next(
+        &mut self,
+        expected: &'static str,
+    ) -> Result<&SpannedToken, ParseError> {
+        match self.tokens.get(self.pos) {
+            Some(t) => {
+                self.pos += 1;
+                Ok(t)
+            }
+            None => Err(ParseError::new(
+                ParseErrorKind::UnexpectedEnd(expected),
+                self.end..self.end,
+            )),
+        }
+    }
+
+    fn unexpected(t: &SpannedToken, expected: &'static str) -> ParseError {
+        ParseError::new(
+            ParseErrorKind::UnexpectedToken {
+                expected,
+                found: t.token.to_string(),
+            },
+            t.span.clone(),
+        )
+    }
+
+    fn expect(
+        &mut self,
+        token: Token,
+        expected: &'static str,
+    ) -> Result<(), ParseError> {
+        let t = self.next(expected)?;
+        if t.token == token {
+            Ok(())
+        } else {
+            Err(Self::unexpected(t, expected))
+        }
+    }
+
+    fn expr(&mut self) -> Result<QueryConditionExpr, ParseError> {
+        let mut lhs = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            lhs = lhs | self.and()?;
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<QueryConditionExpr, ParseError> {
+        let mut lhs = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            lhs = lhs & self.unary()?;
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<QueryConditionExpr, ParseError> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.pos += 1;
+                Ok(!self.unary()?)
+            }
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let expr = self.expr()?;
+                self.expect(Token::RParen, "')'")?;
+                Ok(expr)
+            }
+            _ => self.predicate(),
+        }
+    }
+
+    fn predicate(&mut self) -> Result<QueryConditionExpr, ParseError> {
+        const EXPECT_FIELD: &str = "field name";
+        const EXPECT_OP: &str = "comparison, IN, NOT IN, or IS";
+
+        let t = self.next(EXPECT_FIELD)?;
+        let Token::Field(ref name) = t.token else {
+            return Err(Self::unexpected(t, EXPECT_FIELD));
+        };
+        let field = QueryConditionExpr::field(name);
+
+        let t = self.next(EXPECT_OP)?;
+        match t.token {
+            Token::Op(op) => {
+                let value = self.literal()?;
+                Ok(QueryConditionExpr::Cond(Predicate::Equality(
+                    EqualityPredicate {
+                        field: field.field,
+                        op,
+                        value,
+                    },
+                )))
+            }
+            Token::In => Ok(field.is_in(self.set_members()?)),
+            Token::Not => {
+                self.expect(Token::In, "IN")?;
+                Ok(field.not_in(self.set_members()?))
+            }
+            Token::Is => {
+                if self.peek() == Some(&Token::Not) {
+                    self.pos += 1;
+                    self.expect(Token::Null, "NULL")?;
+                    Ok(field.not_null())
+                } else {
+                    self.expect(Token::Null, "NOT or NULL")?;
+                    Ok(field.is_null())
+                }
+            }
+            _ => Err(Self::unexpected(t, EXPECT_OP)),
+        }
+    }
+
+    fn literal(&mut self) -> Result<Literal, ParseError> {
+        let t = self.next("literal")?;
+        match t.token {
+            Token::String(ref s) => Ok(Literal::String(s.clone())),
+            Token::Number { .. } => {
+                Ok(resolve_numbers(std::slice::from_ref(t))?
+                    .into_iter()
+                    .next()
+                    .unwrap())
+            }
+            _ => Err(Self::unexpected(t, "literal")),
+        }
+    }
+
+    fn set_members(&mut self) -> Result<SetMembers, ParseError> {
+        let start = self.pos;
+        self.expect(Token::LParen, "'('")?;
+        if self.peek() == Some(&Token::RParen) {
+            let span =
+                self.tokens[start].span.start..self.tokens[self.pos].span.end;
+            return Err(ParseError::new(ParseErrorKind::EmptySet, span));
+        }
+
+        let mut members = vec![];
+        loop {
+            let t = self.next("literal")?;
+            match t.token {
+                Token::String(_) | Token::Number { .. } => {
+                    members.push(t.clone())
+                }
+                _ => return Err(Self::unexpected(t, "literal")),
+            }
+            let t = self.next("',' or ')'")?;
+            match t.token {
+                Token::Comma => continue,
+                Token::RParen => break,
+                _ => return Err(Self::unexpected(t, "',' or ')'")),
+            }
+        }
+
+        let mixed_span = |t: &SpannedToken| {
+            ParseError::new(ParseErrorKind::MixedSetMembers, t.span.clone())
+        };
+        if matches!(members[0].token, Token::String(_)) {
+            members
+                .iter()
+                .map(|t| match t.token {
+                    Token::String(ref s) => Ok(s.clone()),
+                    _ => Err(mixed_span(t)),
+                })
+                .collect::<Result<Vec<String>, _>>()
+                .map(SetMembers::String)
+        } else {
+            if let Some(t) =
+                members.iter().find(|t| matches!(t.token, Token::String(_)))
+            {
+                return Err(mixed_span(t));
+            }
+            Ok(set_members(resolve_numbers(&members)?))
+        }
+    }
+}
+
+/// Converts numeric literal tokens into [Literal]s of the same variant.
+fn resolve_numbers(
+    tokens: &[SpannedToken],
+) -> Result<Vec<Literal>, ParseError> {
+    let numbers = tokens
+        .iter()
+        .map(|t| match t.token {
+            Token::Number {
+                ref text,
+                suffix,
+                is_float,
+            } => (t, text.as_str(), suffix, is_float),
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+
+    let to_literals = |datatype: NumberType| {
+        numbers
+            .iter()
+            .map(|(t, text, _, _)| {
+                datatype.literal(text).ok_or_else(|| {
+                    ParseError::new(
+                        ParseErrorKind::InvalidNumber {
+                            literal: text.to_string(),
+                            datatype: datatype.name(),
+                        },
+                        t.span.clone(),
+                    )
+                })
+            })
+            .collect::<Result<Vec<Literal>, ParseError>>()
+    };
+
+    let mut suffixes =
+        numbers.iter().filter_map(|(t, _, s, _)| s.map(|s| (t, s)));
+    if let Some((_, datatype)) = suffixes.next() {
+        if let Some((t, _)) = suffixes.find(|(_, s)| *s != datatype) {
+            return Err(ParseError::new(
+                ParseErrorKind::MixedSetMembers,
+                t.span.clone(),
+            ));
+        }
+        to_literals(datatype)
+    } else if numbers.iter().any(|(_, _, _, is_float)| *is_float) {
+        to_literals(NumberType::Float64)
+    } else {
+        to_literals(NumberType::Int32)
+            .or_else(|_| to_literals(NumberType::Int64))
+            .or_else(|_| to_literals(NumberType::UInt64))
+    }
+}
+
+/// Collects literals which all have the same numeric variant.
+fn set_members(literals: Vec<Literal>) -> SetMembers {
+    macro_rules! collect {
+        ($variant:ident) => {
+            SetMembers::$variant(
+                literals
+                    .into_iter()
+                    .map(|l| match l {
+                        Literal::$variant(v) => v,
+                        _ => unreachable!(),
+                    })
+                    .collect(),
+            )
+        };
+    }
+
+    match literals[0] {
+        Literal::UInt8(_) => collect!(UInt8),
+        Literal::UInt16(_) => collect!(UInt16),
+        Literal::UInt32(_) => collect!(UInt32),
+        Literal::UInt64(_) => collect!(UInt64),
+        Literal::Int8(_) => collect!(Int8),
+        Literal::Int16(_) => collect!(Int16),
+        Literal::Int32(_) => collect!(Int32),
+        Literal::Int64(_) => collect!(Int64),
+        Literal::Float32(_) => collect!(Float32),
+        Literal::Float64(_) => collect!(Float64),
+        Literal::String(_) => collect!(String),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::QueryConditionExpr as QC;
+    use super::*;
+
+    fn parse(s: &str) -> Result<QC, ParseError> {
+        s.parse::<QC>()
+    }
+
+    fn error(s: &str) -> (ParseErrorKind, Range<usize>) {
+        let e = parse(s).unwrap_err();
+        (e.kind, e.span)
+    }
+
+    #[test]
+    fn predicates() {
+        assert_eq!(Ok(QC::field("a").lt(5)), parse("a < 5"));
+        assert_eq!(Ok(QC::field("a").le(-5)), parse("a<=-5"));
+        assert_eq!(Ok(QC::field("a").eq(5u8)), parse("a = 5u8"));
+        assert_eq!(Ok(QC::field("a").eq(5)), parse("a == 5"));
+        assert_eq!(Ok(QC::field("a").ne(5i64)), parse("a <> 5i64"));
+        assert_eq!(Ok(QC::field("a").ne(1.5f32)), parse("a != 1.5f32"));
+        assert_eq!(Ok(QC::field("a").ge(1e10)), parse("a >= 1e10"));
+        assert_eq!(
+            Ok(QC::field("a").gt(5_000_000_000i64)),
+            parse("a > 5000000000")
+        );
+        assert_eq!(
+            Ok(QC::field("a").gt(u64::MAX)),
+            parse("a > 18446744073709551615")
+        );
+        assert_eq!(Ok(QC::field("a").eq("it's")), parse(r"a = 'it\'s'"));
+        assert_eq!(Ok(QC::field("a").eq("\u{e9}\n")), parse(r"a = '\u{e9}\n'"));
+        assert_eq!(Ok(QC::field("a b").is_null()), parse(r#""a b" IS NULL"#));
+        assert_eq!(
+            Ok(QC::field("and").not_null()),
+            parse(r#""and" is not null"#)
+        );
+        assert_eq!(
+            Ok(QC::field("a").is_in(&[1, 2, 3][..])),
+            parse("a IN (1, 2, 3)")
+        );
+        assert_eq!(
+            Ok(QC::field("a").not_in(&[1.0, 2.5][..])),
+            parse("a NOT IN (1, 2.5)")
+        );
+        assert_eq!(
+            Ok(QC::field("a").is_in(&[1i64, 5_000_000_000][..])),
+            parse("a IN (1, 5000000000)")
+        );
+        assert_eq!(
+            Ok(QC::field("a").is_in(&[1u16, 2][..])),
+            parse("a IN (1, 2u16)")
+        );
+        assert_eq!(
+            Ok(QC::field("a").is_in(&["x", "y"][..])),
+            parse("a in ('x','y')")
+        );
+    }
+
+    #[test]
+    fn precedence() {
+        let a = || QC::field("a").lt(1);
+        let b = || QC::field("b").lt(2);
+        let c = || QC::field("c").lt(3);
+
+        assert_eq!(Ok((a() & b()) | c()), parse("a < 1 AND b < 2 OR c < 3"));
+        assert_eq!(Ok(a() | (b() & c())), parse("a < 1 OR b < 2 AND c < 3"));
+        assert_eq!(Ok(a() & (b() | c())), parse("a < 1 AND (b < 2 OR c < 3)"));
+        assert_eq!(Ok((a() & b()) & c()), parse("a < 1 AND b < 2 AND c < 3"));
+        assert_eq!(Ok(!a() & b()), parse("NOT a < 1 AND b < 2"));
+        assert_eq!(Ok(!(a() & b())), parse("NOT (a < 1 AND b < 2)"));
+        assert_eq!(Ok(!!a()), parse("NOT NOT ((a < 1))"));
+    }
+
+    #[test]
+    fn errors() {
+        use ParseErrorKind as K;
+
+        assert_eq!((K::UnexpectedCharacter('#'), 4..5), error("a < #"));
+        assert_eq!((K::UnterminatedQuote, 4..8), error("a < 'foo"));
+        assert_eq!((K::InvalidEscape, 5..7), error(r"a < '\q'"));
+        assert_eq!((K::InvalidSuffix("u7".to_owned()), 5..7), error("a < 5u7"));
+        assert_eq!(
+            (
+                K::InvalidNumber {
+                    literal: "300".to_owned(),
+                    datatype: "UInt8"
+                },
+                4..9
+            ),
+            error("a < 300u8")
+        );
+        assert_eq!(
+            (
+                K::InvalidNumber {
+                    literal: "1.5".to_owned(),
+                    datatype: "Int32"
+                },
+                4..10
+            ),
+            error("a < 1.5i32")
+        );
+        assert_eq!((K::UnexpectedEnd("literal"), 3..3), error("a <"));
+        assert_eq!(
+            (
+                K::UnexpectedToken {
+                    expected: "field name",
+                    found: "5".to_owned()
+                },
+                0..1
+            ),
+            error("5 < a")
+        );
+        assert_eq!(
+            (
+                K::UnexpectedToken {
+                    expected: "end of input",
+                    found: "')'".to_owned()
+                },
+                5..6
+            ),
+            error("a < 5)")
+        );
+        assert_eq!((K::UnexpectedEnd("')'"), 6..6), error("(a < 5"));
+        assert_eq!((K::EmptySet, 5..7), error("a IN ()"));
+        assert_eq!((K::MixedSetMembers, 9..12), error("a IN (1, 'x')"));
+        assert_eq!((K::MixedSetMembers, 11..15), error("a IN (1u8, 2u16)"));
+        assert_eq!(
+            (
+                K::UnexpectedToken {
+                    expected: "NULL",
+                    found: "field x".to_owned()
+                },
+                9..10
+            ),
+            error("a IS NOT x")
+        );
+    }
+
+    #[test]
+    fn display_identifiers() {
+        assert!(is_identifier("a"));
+        assert!(is_identifier("_a1"));
+        assert!(!is_identifier(""));
+        assert!(!is_identifier("1a"));
+        assert!(!is_identifier("a b"));
+        assert!(!is_identifier("Null"));
+
+        assert_eq!(r#""a b" IS NULL"#, QC::field("a b").is_null().to_string());
+        assert_eq!(r#""not" IS NULL"#, QC::field("not").is_null().to_string());
+        assert_eq!(
+            r#""a\"b" IS NULL"#,
+            QC::field("a\"b").is_null().to_string()
+        );
+    }
+
+    #[test]
+    fn display_numbers() {
+        assert_eq!("a < 5u8", QC::field("a").lt(5u8).to_string());
+        assert_eq!("a < -5i64", QC::field("a").lt(-5i64).to_string());
+        assert_eq!("a < 1.0", QC::field("a").lt(1.0).to_string());
+        assert_eq!("a < 1.5f32", QC::field("a").lt(1.5f32).to_string());
+        assert_eq!("a < 1e-7", QC::field("a").lt(1e-7).to_string());
+        assert_eq!(
+            "a IN (1u32, 2u32)",
+            QC::field("a").is_in(&[1u32, 2][..]).to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trip(qc in any::<QC>()) {
+            assert_eq!(Ok(qc.clone()), parse(&qc.to_string()));
+        }
+    }
+}
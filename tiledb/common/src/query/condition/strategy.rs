@@ -1,7 +1,192 @@
-use proptest::strategy::ValueTree;
+use proptest::prelude::*;
+use proptest::strategy::{NewTree, ValueTree};
+use proptest::test_runner::TestRunner;
 
 use super::*;
 
+#[derive(Clone, Debug)]
+pub struct QueryConditionParameters {
+    /// Maximum depth of combinations and negations.
+    pub max_depth: u32,
+    /// Maximum number of predicates.
+    pub max_predicates: u32,
+    /// Maximum number of set members of a set membership predicate.
+    pub max_set_members: usize,
+}
+
+impl Default for QueryConditionParameters {
+    fn default() -> Self {
+        const MAX_DEPTH: u32 = 4;
+        const MAX_PREDICATES: u32 = 16;
+        const MAX_SET_MEMBERS: usize = 8;
+
+        QueryConditionParameters {
+            max_depth: MAX_DEPTH,
+            max_predicates: MAX_PREDICATES,
+            max_set_members: MAX_SET_MEMBERS,
+        }
+    }
+}
+
+/// Produces field names which are mostly identifiers,
+/// and occasionally names which must be quoted.
+fn prop_field_name() -> impl Strategy<Value = String> {
+    prop_oneof![
+        8 => "[a-z_][a-z0-9_]{0,8}",
+        1 => prop_oneof![Just("and"), Just("Not"), Just("NULL")]
+            .prop_map(String::from),
+        1 => any::<String>(),
+    ]
+}
+
+/// Produces finite floating-point numbers of any sign and magnitude.
+macro_rules! prop_finite_float {
+    ($float:ident) => {
+        proptest::num::$float::POSITIVE
+            | proptest::num::$float::NEGATIVE
+            | proptest::num::$float::NORMAL
+            | proptest::num::$float::SUBNORMAL
+            | proptest::num::$float::ZERO
+    };
+}
+
+fn prop_literal() -> impl Strategy<Value = Literal> {
+    prop_oneof![
+        any::<u8>().prop_map(Literal::UInt8),
+        any::<u16>().prop_map(Literal::UInt16),
+        any::<u32>().prop_map(Literal::UInt32),
+        any::<u64>().prop_map(Literal::UInt64),
+        any::<i8>().prop_map(Literal::Int8),
+        any::<i16>().prop_map(Literal::Int16),
+        any::<i32>().prop_map(Literal::Int32),
+        any::<i64>().prop_map(Literal::Int64),
+        prop_finite_float!(f32).prop_map(Literal::Float32),
+        prop_finite_float!(f64).prop_map(Literal::Float64),
+        any::<String>().prop_map(Literal::String),
+    ]
+}
+
+fn prop_set_members(max_members: usize) -> impl Strategy<Value = SetMembers> {
+    use proptest::collection::vec;
+
+    let members = 1..=max_members.max(1);
+    prop_oneof![
+        vec(any::<u8>(), members.clone()).prop_map(SetMembers::UInt8),
+        vec(any::<u16>(), members.clone()).prop_map(SetMembers::UInt16),
+        vec(any::<u32>(), members.clone()).prop_map(SetMembers::UInt32),
+        vec(any::<u64>(), members.clone()).prop_map(SetMembers::UInt64),
+        vec(any::<i8>(), members.clone()).prop_map(SetMembers::Int8),
+        vec(any::<i16>(), members.clone()).prop_map(SetMembers::Int16),
+        vec(any::<i32>(), members.clone()).prop_map(SetMembers::Int32),
+        vec(any::<i64>(), members.clone()).prop_map(SetMembers::Int64),
+        vec(prop_finite_float!(f32), members.clone())
+            .prop_map(SetMembers::Float32),
+        vec(prop_finite_float!(f64), members.clone())
+            .prop_map(SetMembers::Float64),
+        vec(any::<String>(), members).prop_map(SetMembers::String),
+    ]
+}
+
+fn prop_predicate(max_set_members: usize) -> impl Strategy<Value = Predicate> {
+    let equality_op = prop_oneof![
+        Just(EqualityOp::Less),
+        Just(EqualityOp::LessEqual),
+        Just(EqualityOp::Equal),
+        Just(EqualityOp::NotEqual),
+        Just(EqualityOp::GreaterEqual),
+        Just(EqualityOp::Greater),
+    ];
+    let set_membership_op =
+        prop_oneof![Just(SetMembershipOp::In), Just(SetMembershipOp::NotIn)];
+    let nullness_op =
+        prop_oneof![Just(NullnessOp::IsNull), Just(NullnessOp::NotNull)];
+
+    prop_oneof![
+        (prop_field_name(), equality_op, prop_literal()).prop_map(
+            |(field, op, value)| {
+                Predicate::Equality(EqualityPredicate { field, op, value })
+            }
+        ),
+        (
+            prop_field_name(),
+            set_membership_op,
+            prop_set_members(max_set_members)
+        )
+            .prop_map(|(field, op, members)| {
+                Predicate::SetMembership(SetMembershipPredicate {
+                    field,
+                    op,
+                    members,
+                })
+            }),
+        (prop_field_name(), nullness_op).prop_map(|(field, op)| {
+            Predicate::Nullness(NullnessPredicate { field, op })
+        }),
+    ]
+}
+
+/// Produces arbitrary query conditions, which shrink using
+/// [QueryConditionValueTree]. The conditions are not correlated with
+/// any schema, so they are suitable for testing the representation of
+/// a query condition but not necessarily for running queries.
+#[derive(Debug)]
+pub struct QueryConditionStrategy(BoxedStrategy<QueryConditionExpr>);
+
+impl Strategy for QueryConditionStrategy {
+    type Tree = QueryConditionValueTree;
+    type Value = QueryConditionExpr;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let qc = self.0.new_tree(runner)?.current();
+        Ok(QueryConditionValueTree::new(qc))
+    }
+}
+
+/// Produces query conditions which combine the conditions produced by
+/// `leaf` using `AND`, `OR`, and `NOT`.
+///
+/// This can be used to produce conditions over the fields of a particular
+/// schema, whereas [Arbitrary] produces conditions over any fields.
+pub fn prop_query_condition<S>(
+    leaf: S,
+    params: &QueryConditionParameters,
+) -> QueryConditionStrategy
+where
+    S: Strategy<Value = QueryConditionExpr> + 'static,
+{
+    let strategy = leaf.prop_recursive(
+        params.max_depth,
+        params.max_predicates,
+        2,
+        |inner| {
+            let combination_op =
+                prop_oneof![Just(CombinationOp::And), Just(CombinationOp::Or)];
+            prop_oneof![
+                (inner.clone(), inner.clone(), combination_op).prop_map(
+                    |(lhs, rhs, op)| QueryConditionExpr::Comb {
+                        lhs: Box::new(lhs),
+                        rhs: Box::new(rhs),
+                        op,
+                    }
+                ),
+                inner.prop_map(|qc| QueryConditionExpr::Negate(Box::new(qc))),
+            ]
+        },
+    );
+    QueryConditionStrategy(strategy.boxed())
+}
+
+impl Arbitrary for QueryConditionExpr {
+    type Parameters = QueryConditionParameters;
+    type Strategy = QueryConditionStrategy;
+
+    fn arbitrary_with(p: Self::Parameters) -> Self::Strategy {
+        let leaf = prop_predicate(p.max_set_members)
+            .prop_map(QueryConditionExpr::Cond);
+        prop_query_condition(leaf, &p)
+    }
+}
+
 #[derive(Debug)]
 enum CombinationOpState {
     /// No values have been produced yet. Try the full combination op.
@@ -0,0 +1,44 @@
+#[cfg(any(test, feature = "proptest-strategies"))]
+pub mod strategy;
+
+#[cfg(feature = "option-subset")]
+use tiledb_utils::option::OptionSubset;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use tiledb_common::range::Range;
+
+/// Encapsulation of data needed to construct an NDRectangle.
+///
+/// `ranges` holds one range for each dimension of the domain,
+/// in the same order as the dimensions.
+#[derive(Clone, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "option-subset", derive(OptionSubset))]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct NDRectangleData {
+    pub ranges: Vec<Range>,
+}
+
+/// Encapsulation of data needed to construct a CurrentDomain
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "option-subset", derive(OptionSubset))]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum CurrentDomainData {
+    NDRectangle(NDRectangleData),
+}
+
+impl CurrentDomainData {
+    /// Returns the ranges of each dimension spanned by this current domain.
+    pub fn ranges(&self) -> &[Range] {
+        match self {
+            Self::NDRectangle(ndr) => &ndr.ranges,
+        }
+    }
+}
+
+impl From<NDRectangleData> for CurrentDomainData {
+    fn from(ndr: NDRectangleData) -> Self {
+        Self::NDRectangle(ndr)
+    }
+}
@@ -0,0 +1,74 @@
+use proptest::prelude::*;
+use tiledb_common::array::{ArrayType, CellValNum};
+
+use crate::array::current_domain::{CurrentDomainData, NDRectangleData};
+use crate::array::domain::DomainData;
+
+/// Returns whether libtiledb accepts a current domain
+/// for an array of type `array_type` with `domain`.
+///
+/// A current domain is supported for sparse arrays
+/// whose dimensions all have a fixed size.
+pub fn supports_current_domain(
+    array_type: ArrayType,
+    domain: &DomainData,
+) -> bool {
+    array_type == ArrayType::Sparse
+        && !domain.dimension.is_empty()
+        && domain
+            .dimension
+            .iter()
+            .all(|d| d.cell_val_num() == CellValNum::single())
+}
+
+/// Returns a strategy which produces an NDRectangle within `domain`.
+pub fn prop_ndrectangle(
+    domain: &DomainData,
+) -> impl Strategy<Value = NDRectangleData> {
+    domain
+        .subarray_strategy()
+        .prop_map(|ranges| NDRectangleData { ranges })
+}
+
+/// Returns a strategy which produces a current domain within `domain`.
+pub fn prop_current_domain(
+    domain: &DomainData,
+) -> impl Strategy<Value = CurrentDomainData> {
+    prop_ndrectangle(domain).prop_map(CurrentDomainData::NDRectangle)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::array::domain::strategy::Requirements as DomainRequirements;
+
+    fn strat_domain() -> impl Strategy<Value = DomainData> {
+        any_with::<DomainData>(Rc::new(DomainRequirements {
+            array_type: Some(ArrayType::Sparse),
+            ..Default::default()
+        }))
+        .prop_filter("Current domain not supported", |d| {
+            supports_current_domain(ArrayType::Sparse, d)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn current_domain_within_domain(
+            (domain, current_domain) in strat_domain()
+                .prop_flat_map(|d| {
+                    let cd = prop_current_domain(&d);
+                    (Just(d), cd)
+                })
+        ) {
+            let domains = domain.domains().unwrap();
+            let ranges = current_domain.ranges();
+            assert_eq!(domains.len(), ranges.len());
+            for (d, r) in domains.iter().zip(ranges.iter()) {
+                assert_eq!(*d, d.union(r));
+            }
+        }
+    }
+}
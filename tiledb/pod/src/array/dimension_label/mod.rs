@@ -0,0 +1,23 @@
+#[cfg(any(test, feature = "proptest-strategies"))]
+pub mod strategy;
+
+#[cfg(feature = "option-subset")]
+use tiledb_utils::option::OptionSubset;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use tiledb_common::array::DataOrder;
+use tiledb_common::datatype::Datatype;
+
+/// Encapsulation of data needed to add a dimension label to a schema.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "option-subset", derive(OptionSubset))]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct DimensionLabelData {
+    pub name: String,
+    /// Index of the labelled dimension within the schema domain.
+    pub dimension_index: usize,
+    pub label_order: DataOrder,
+    pub label_type: Datatype,
+}
@@ -0,0 +1,61 @@
+use proptest::prelude::*;
+use proptest::sample::select;
+use tiledb_common::array::{CellValNum, DataOrder};
+use tiledb_common::datatype::Datatype;
+
+use crate::array::dimension::strategy::prop_dimension_name;
+use crate::array::dimension_label::DimensionLabelData;
+use crate::array::domain::DomainData;
+
+/// Returns the indices of the dimensions of `domain` which may be labelled.
+///
+/// libtiledb only supports labels on fixed-size dimensions.
+pub fn labelled_dimension_candidates(domain: &DomainData) -> Vec<usize> {
+    domain
+        .dimension
+        .iter()
+        .enumerate()
+        .filter(|(_, d)| d.cell_val_num() == CellValNum::single())
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Returns a strategy which produces a label for some dimension of `domain`,
+/// or `None` if no dimension of `domain` can be labelled.
+pub fn prop_dimension_label(
+    domain: &DomainData,
+) -> Option<impl Strategy<Value = DimensionLabelData>> {
+    let candidates = labelled_dimension_candidates(domain);
+    if candidates.is_empty() {
+        return None;
+    }
+
+    // the label values are the dimension of the label array,
+    // and unordered labels are not supported
+    let label_type = select(
+        Datatype::iter()
+            .filter(|dt| dt.is_allowed_dimension_type_sparse())
+            .collect::<Vec<_>>(),
+    );
+    let label_order =
+        prop_oneof![Just(DataOrder::Increasing), Just(DataOrder::Decreasing)];
+
+    Some(
+        (
+            prop_dimension_name(),
+            select(candidates),
+            label_order,
+            label_type,
+        )
+            .prop_map(
+                |(name, dimension_index, label_order, label_type)| {
+                    DimensionLabelData {
+                        name,
+                        dimension_index,
+                        label_order,
+                        label_type,
+                    }
+                },
+            ),
+    )
+}
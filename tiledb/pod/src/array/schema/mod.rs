@@ -12,7 +12,10 @@ use tiledb_common::datatype::Datatype;
 use tiledb_common::filter::FilterData;
 use tiledb_common::key::LookupKey;
 
-use crate::array::{AttributeData, DimensionData, DomainData, EnumerationData};
+use crate::array::{
+    AttributeData, CurrentDomainData, DimensionData, DimensionLabelData,
+    DomainData, EnumerationData,
+};
 
 /// Encapsulation of data needed to construct a Schema
 #[derive(Clone, Default, Debug, PartialEq)]
@@ -30,6 +33,8 @@ pub struct SchemaData {
     pub coordinate_filters: Vec<FilterData>,
     pub offsets_filters: Vec<FilterData>,
     pub nullity_filters: Vec<FilterData>,
+    pub current_domain: Option<CurrentDomainData>,
+    pub dimension_labels: Vec<DimensionLabelData>,
 }
 
 impl SchemaData {
@@ -10,17 +10,25 @@ use strategy_ext::records::RecordsValueTree;
 use strategy_ext::StrategyExt;
 use tiledb_common::array::{ArrayType, CellOrder, TileOrder};
 use tiledb_common::filter::FilterData;
+use tiledb_common::range::Range;
 
 use crate::array::attribute::strategy::{
     prop_attribute, AttributeValueTree, Requirements as AttributeRequirements,
     StrategyContext as AttributeContext,
 };
+use crate::array::current_domain::strategy::{
+    prop_current_domain, supports_current_domain,
+};
+use crate::array::dimension_label::strategy::prop_dimension_label;
 use crate::array::domain::strategy::{
     DomainValueTree, Requirements as DomainRequirements,
 };
 use crate::array::enumeration::strategy::EnumerationValueTree;
 use crate::array::schema::{FieldData, SchemaData};
-use crate::array::{AttributeData, DimensionData, DomainData, EnumerationData};
+use crate::array::{
+    AttributeData, CurrentDomainData, DimensionData, DimensionLabelData,
+    DomainData, EnumerationData, NDRectangleData,
+};
 use crate::filter::strategy::{
     FilterPipelineStrategy, FilterPipelineValueTree,
     Requirements as FilterRequirements, StrategyContext as FilterContext,
@@ -36,6 +44,10 @@ pub struct Requirements {
     pub validity_filters: Option<Rc<FilterRequirements>>,
     pub sparse_tile_capacity: std::ops::RangeInclusive<u64>,
     pub attribute_enumeration_likelihood: f64,
+    /// Number of dimension labels to add, if the domain
+    /// has any dimension which can be labelled.
+    /// None by default, since each label is stored as a separate array.
+    pub num_dimension_labels: std::ops::RangeInclusive<usize>,
 }
 
 impl Requirements {
@@ -74,6 +86,7 @@ impl Default for Requirements {
                 ..=Self::max_sparse_tile_capacity_default(),
             attribute_enumeration_likelihood:
                 Self::attribute_enumeration_likelihood_default(),
+            num_dimension_labels: 0..=0,
         }
     }
 }
@@ -198,6 +211,23 @@ fn prop_schema_for_domain(
         )
     });
 
+    let current_domain = if supports_current_domain(array_type, &domain) {
+        proptest::option::of(prop_current_domain(&domain)).boxed()
+    } else {
+        Just(None).boxed()
+    };
+
+    let dimension_labels = match prop_dimension_label(&domain) {
+        Some(strat) if *params.num_dimension_labels.end() > 0 => {
+            proptest::collection::vec(
+                strat,
+                params.num_dimension_labels.clone(),
+            )
+            .boxed()
+        }
+        _ => Just(vec![]).boxed(),
+    };
+
     (
         capacity,
         any_with::<CellOrder>(Some(array_type)),
@@ -206,7 +236,9 @@ fn prop_schema_for_domain(
         strat_attributes_enumerations,
         prop_coordinate_filters(&domain, params.as_ref()),
         FilterPipelineStrategy::new(offsets_filters_requirements),
-        FilterPipelineStrategy::new(validity_filters_requirements)
+        FilterPipelineStrategy::new(validity_filters_requirements),
+        current_domain,
+        dimension_labels,
     )
         .prop_map(
             move |(
@@ -218,6 +250,8 @@ fn prop_schema_for_domain(
                 coordinate_filters,
                 offsets_filters,
                 nullity_filters,
+                current_domain,
+                dimension_labels,
             )| {
                 /*
                  * Update the set of dimension/attribute names to be unique.
@@ -227,6 +261,7 @@ fn prop_schema_for_domain(
                  */
                 let mut domain = (*domain).clone();
                 let mut attributes = attributes;
+                let mut dimension_labels = dimension_labels;
 
                 {
                     let mut runner =
@@ -255,6 +290,17 @@ fn prop_schema_for_domain(
                             }
                         }
                     }
+                    {
+                        let labelgen = crate::array::dimension::strategy::prop_dimension_name();
+                        for label in dimension_labels.iter_mut() {
+                            while !names.insert(label.name.clone()) {
+                                label.name = labelgen
+                                    .new_tree(&mut runner)
+                                    .unwrap()
+                                    .current();
+                            }
+                        }
+                    }
                 }
 
                 SchemaData {
@@ -269,6 +315,8 @@ fn prop_schema_for_domain(
                     coordinate_filters,
                     offsets_filters,
                     nullity_filters,
+                    current_domain,
+                    dimension_labels,
                 }
             },
         )
@@ -369,6 +417,8 @@ pub struct SchemaValueTree {
     coordinate_filters: FilterPipelineValueTree,
     offsets_filters: FilterPipelineValueTree,
     nullity_filters: FilterPipelineValueTree,
+    current_domain: Option<HashMap<String, Range>>, // TODO: make shrinkable
+    dimension_labels: Vec<(String, DimensionLabelData)>, // TODO: make shrinkable
 }
 
 impl SchemaValueTree {
@@ -377,7 +427,7 @@ impl SchemaValueTree {
 
         Self {
             array_type: schema.array_type,
-            domain: DomainValueTree::new(schema.domain),
+            domain: DomainValueTree::new(schema.domain.clone()),
             capacity: Just(schema.capacity),
             cell_order: Just(schema.cell_order),
             tile_order: Just(schema.tile_order),
@@ -410,6 +460,24 @@ impl SchemaValueTree {
             nullity_filters: FilterPipelineValueTree::new(
                 schema.nullity_filters,
             ),
+            current_domain: schema.current_domain.map(|cd| {
+                // key by name so that the ranges follow dimension shrinking
+                schema
+                    .domain
+                    .dimension
+                    .iter()
+                    .map(|d| d.name.clone())
+                    .zip(cd.ranges().iter().cloned())
+                    .collect::<HashMap<String, Range>>()
+            }),
+            dimension_labels: schema
+                .dimension_labels
+                .into_iter()
+                .map(|l| {
+                    // key by name so that labels follow dimension shrinking
+                    (schema.domain.dimension[l.dimension_index].name.clone(), l)
+                })
+                .collect::<Vec<_>>(),
         }
     }
 }
@@ -432,9 +500,35 @@ impl ValueTree for SchemaValueTree {
             .map(|e| self.all_enumerations.get(e).unwrap().current())
             .collect::<Vec<_>>();
 
+        let domain = self.domain.current();
+        let current_domain = self.current_domain.as_ref().map(|ranges| {
+            CurrentDomainData::NDRectangle(NDRectangleData {
+                ranges: domain
+                    .dimension
+                    .iter()
+                    .map(|d| ranges[&d.name].clone())
+                    .collect::<Vec<Range>>(),
+            })
+        });
+
+        let dimension_labels = self
+            .dimension_labels
+            .iter()
+            .filter_map(|(dimension_name, label)| {
+                domain
+                    .dimension
+                    .iter()
+                    .position(|d| d.name == *dimension_name)
+                    .map(|dimension_index| DimensionLabelData {
+                        dimension_index,
+                        ..label.clone()
+                    })
+            })
+            .collect::<Vec<_>>();
+
         SchemaData {
             array_type: self.array_type,
-            domain: self.domain.current(),
+            domain,
             capacity: self.capacity.current(),
             cell_order: self.cell_order.current(),
             tile_order: self.tile_order.current(),
@@ -444,6 +538,8 @@ impl ValueTree for SchemaValueTree {
             coordinate_filters: self.coordinate_filters.current(),
             offsets_filters: self.offsets_filters.current(),
             nullity_filters: self.nullity_filters.current(),
+            current_domain,
+            dimension_labels,
         }
     }
 
@@ -523,5 +619,28 @@ mod tests {
             let vt = SchemaValueTree::new(schema);
             test_schema_value_tree(vt)
         }
+
+        /// Test that dimension labels refer to a fixed-size dimension
+        /// and follow that dimension as the schema shrinks
+        #[test]
+        fn schema_dimension_labels(schema in any_with::<SchemaData>(Rc::new(Requirements {
+            num_dimension_labels: 1..=3,
+            ..Default::default()
+        }))) {
+            let check = |schema: &SchemaData| {
+                let mut names = schema.fields().map(|f| f.name().to_owned()).collect::<HashSet<_>>();
+                for label in schema.dimension_labels.iter() {
+                    assert!(names.insert(label.name.clone()));
+                    let dim = &schema.domain.dimension[label.dimension_index];
+                    assert!(dim.cell_val_num().is_single_valued());
+                }
+            };
+            check(&schema);
+
+            let mut vt = SchemaValueTree::new(schema);
+            while vt.simplify() {
+                check(&vt.current());
+            }
+        }
     }
 }
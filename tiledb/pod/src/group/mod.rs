@@ -0,0 +1,119 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use tiledb_common::metadata::Metadata;
+
+use crate::array::SchemaData;
+
+/// Encapsulation of data needed to construct a group together with
+/// its metadata and all of its members, recursively.
+///
+/// Each member is located at the URI of its group joined with the name
+/// of the member.
+#[derive(Clone, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DatasetData {
+    pub metadata: Vec<Metadata>,
+    pub members: Vec<MemberData>,
+}
+
+impl DatasetData {
+    /// Returns the member with the given `name`, if any.
+    pub fn member(&self, name: &str) -> Option<&MemberData> {
+        self.members.iter().find(|m| m.name == name)
+    }
+}
+
+/// Encapsulation of data needed to construct a member of a group.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct MemberData {
+    /// Name of the member within its group.
+    pub name: String,
+    /// Whether the group stores the URI of the member relative to its own.
+    pub relative: bool,
+    pub object: ObjectData,
+}
+
+/// The object which a [MemberData] describes.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum ObjectData {
+    Group(DatasetData),
+    Array(ArrayData),
+}
+
+/// Encapsulation of data needed to construct an array as a member of
+/// a [DatasetData].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ArrayData {
+    pub schema: SchemaData,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: Vec<Metadata>,
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "serde")]
+    #[test]
+    fn dataset_serde() {
+        use tiledb_common::datatype::Datatype;
+
+        use super::*;
+        use crate::test::serde::roundtrip;
+
+        let dataset = serde_json::from_str::<DatasetData>(
+            r#"{
+                "metadata": [
+                    { "key": "version", "datatype": "Int32", "value": { "Int32Value": [1] } }
+                ],
+                "members": [
+                    {
+                        "name": "raw",
+                        "relative": true,
+                        "object": { "type": "group" }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            DatasetData {
+                metadata: vec![Metadata::new(
+                    "version".to_owned(),
+                    Datatype::Int32,
+                    vec![1i32]
+                )
+                .unwrap()],
+                members: vec![MemberData {
+                    name: "raw".to_owned(),
+                    relative: true,
+                    object: ObjectData::Group(DatasetData::default()),
+                }],
+            },
+            dataset
+        );
+        assert!(dataset.member("raw").is_some());
+        assert!(dataset.member("cooked").is_none());
+
+        let dataset = DatasetData {
+            members: vec![
+                dataset.members[0].clone(),
+                MemberData {
+                    name: "table".to_owned(),
+                    relative: false,
+                    object: ObjectData::Array(ArrayData {
+                        schema: SchemaData::default(),
+                        metadata: dataset.metadata.clone(),
+                    }),
+                },
+            ],
+            ..dataset
+        };
+        assert_eq!(dataset, roundtrip(&dataset).unwrap());
+    }
+}
@@ -33,6 +33,7 @@ extern crate tiledb_sys_defs as ffi;
 
 pub mod array;
 pub mod filter;
+pub mod group;
 pub mod query;
 
 #[cfg(test)]
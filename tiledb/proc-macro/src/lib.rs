@@ -9,9 +9,25 @@ use proc_macro::TokenStream;
 use syn::DeriveInput;
 
 mod option_subset;
+mod record;
 
 #[proc_macro_derive(OptionSubset)]
 pub fn derive_option_subset(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     option_subset::expand(&input)
 }
+
+/// Derives `TileDBRecord` for a struct whose fields correspond
+/// to the dimensions and attributes of an array.
+///
+/// Each struct field maps to the array field of the same name,
+/// which can be changed using `#[tiledb(name = "...")]`.
+///
+/// The generated code refers to the `tiledb_api` crate by that name.
+/// If it is imported under another name, set the path to it
+/// using `#[tiledb(crate = "...")]` on the struct.
+#[proc_macro_derive(TileDBRecord, attributes(tiledb))]
+pub fn derive_tiledb_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    record::expand(&input)
+}
@@ -0,0 +1,157 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span};
+
+pub fn expand(input: &syn::DeriveInput) -> TokenStream {
+    match expand_record(input) {
+        Ok(expanded) => expanded.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// A struct field and the name of the array field which it corresponds to.
+struct RecordField<'a> {
+    ident: &'a Ident,
+    ty: &'a syn::Type,
+    name: String,
+}
+
+/// Returns the path to the `tiledb_api` crate, which is `::tiledb_api`
+/// unless set using `#[tiledb(crate = "...")]` on the struct.
+fn crate_path(input: &syn::DeriveInput) -> syn::Result<syn::Path> {
+    let mut path = parse_quote!(::tiledb_api);
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("tiledb")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                path = value.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported tiledb attribute"))
+            }
+        })?;
+    }
+    Ok(path)
+}
+
+fn record_fields(
+    input: &syn::DeriveInput,
+) -> syn::Result<Vec<RecordField<'_>>> {
+    let fields = match input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(ref fields),
+            ..
+        }) => &fields.named,
+        _ => return Err(syn::Error::new_spanned(
+            input,
+            "TileDBRecord can only be derived for structs with named fields",
+        )),
+    };
+    if fields.is_empty() {
+        return Err(syn::Error::new_spanned(
+            input,
+            "TileDBRecord requires at least one field",
+        ));
+    }
+
+    fields
+        .iter()
+        .map(|f| {
+            let ident = f.ident.as_ref().unwrap();
+            let mut name =
+                ident.to_string().trim_start_matches("r#").to_owned();
+            for attr in f.attrs.iter().filter(|a| a.path().is_ident("tiledb")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        let value: syn::LitStr = meta.value()?.parse()?;
+                        name = value.value();
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported tiledb attribute"))
+                    }
+                })?;
+            }
+            Ok(RecordField {
+                ident,
+                ty: &f.ty,
+                name,
+            })
+        })
+        .collect()
+}
+
+fn expand_record(
+    input: &syn::DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    let krate = crate_path(input)?;
+    let fields = record_fields(input)?;
+    let idents = fields.iter().map(|f| f.ident).collect::<Vec<_>>();
+    let tys = fields.iter().map(|f| f.ty).collect::<Vec<_>>();
+    let names = fields.iter().map(|f| &f.name).collect::<Vec<_>>();
+    let cells = (0..fields.len())
+        .map(|idx| Ident::new(&format!("cells_{}", idx), Span::call_site()))
+        .collect::<Vec<_>>();
+    let first_cells = &cells[0];
+
+    Ok(quote! {
+        impl #impl_generics #krate::query::record::TileDBRecord for #name #ty_generics #where_clause {
+            fn field_names() -> ::std::vec::Vec<&'static str> {
+                vec![#(#names),*]
+            }
+
+            fn check_schema(
+                schema: &#krate::array::Schema,
+            ) -> #krate::Result<()> {
+                #(
+                    <#tys as #krate::query::record::RecordField>::check_field(
+                        &schema.field(#names)?,
+                    )?;
+                )*
+                Ok(())
+            }
+
+            fn from_read_output(
+                output: ::std::vec::Vec<#krate::query::read::output::TypedRawReadOutput>,
+            ) -> #krate::Result<::std::vec::Vec<Self>> {
+                let mut output = output.into_iter();
+                #(
+                    let mut #cells = #krate::query::record::read_field::<#tys>(
+                        #names,
+                        output.next(),
+                    )?
+                    .into_iter();
+                )*
+                let ncells = #first_cells.len();
+                (0..ncells)
+                    .map(|_| {
+                        Ok(Self {
+                            #(
+                                #idents: #krate::query::record::next_cell(
+                                    #names,
+                                    &mut #cells,
+                                )?,
+                            )*
+                        })
+                    })
+                    .collect()
+            }
+
+            fn query_buffers(
+                records: &[Self],
+            ) -> #krate::Result<
+                ::std::vec::Vec<#krate::query::buffer::TypedQueryBuffers<'static>>,
+            > {
+                Ok(vec![
+                    #(
+                        <#tys as #krate::query::record::RecordField>::query_buffers(
+                            records.iter().map(|r| &r.#idents),
+                        )?,
+                    )*
+                ])
+            }
+        }
+    })
+}
@@ -197,3 +197,8 @@ pub type tiledb_walk_order_t = ::std::os::raw::c_uint;
 pub const tiledb_current_domain_type_t_TILEDB_NDRECTANGLE:
     tiledb_current_domain_type_t = 0;
 pub type tiledb_current_domain_type_t = ::std::os::raw::c_uint;
+
+pub const tiledb_data_order_t_TILEDB_UNORDERED_DATA: tiledb_data_order_t = 0;
+pub const tiledb_data_order_t_TILEDB_INCREASING_DATA: tiledb_data_order_t = 1;
+pub const tiledb_data_order_t_TILEDB_DECREASING_DATA: tiledb_data_order_t = 2;
+pub type tiledb_data_order_t = ::std::os::raw::c_uint;
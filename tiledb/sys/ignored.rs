@@ -77,6 +77,18 @@ extern "C" {
     pub fn tiledb_stats_dump(out: *mut FILE) -> i32;
     pub fn tiledb_stats_raw_dump(out: *mut FILE) -> i32;
 
+    // ConsolidationPlan reads each node from the plan, and is serialized
+    // to JSON using serde rather than the format which libtiledb writes.
+    pub fn tiledb_consolidation_plan_dump_json_str(
+        ctx: *mut tiledb_ctx_t,
+        consolidation_plan: *const tiledb_consolidation_plan_t,
+        str_: *mut *mut ::std::os::raw::c_char,
+    ) -> capi_return_t;
+
+    pub fn tiledb_consolidation_plan_free_json_str(
+        str_: *mut *mut ::std::os::raw::c_char,
+    );
+
     // This is an exact duplicate of tiledb_stats_dump_str
     pub fn tiledb_stats_raw_dump_str(
         out: *mut *mut ::std::os::raw::c_char,
@@ -219,11 +231,6 @@ extern "C" {
         error: *mut *mut tiledb_error_t,
     ) -> capi_return_t;
 
-    // Ignoring the async tasks as those likely won't be useful in Rust land given
-    // they don't at all map to async Rust.
-
-    pub fn tiledb_ctx_cancel_tasks(ctx: *mut tiledb_ctx_t) -> capi_return_t;
-
     // Filter types are not part of the public Rust API and the filter API's types
     // already have their Debug traits implemented.
 
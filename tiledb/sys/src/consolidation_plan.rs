@@ -0,0 +1,37 @@
+use crate::types::{
+    capi_return_t, tiledb_array_t, tiledb_consolidation_plan_t, tiledb_ctx_t,
+};
+
+extern "C" {
+    pub fn tiledb_consolidation_plan_create_with_mbr(
+        ctx: *mut tiledb_ctx_t,
+        array: *mut tiledb_array_t,
+        fragment_size: u64,
+        consolidation_plan: *mut *mut tiledb_consolidation_plan_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_consolidation_plan_free(
+        consolidation_plan: *mut *mut tiledb_consolidation_plan_t,
+    );
+
+    pub fn tiledb_consolidation_plan_get_num_nodes(
+        ctx: *mut tiledb_ctx_t,
+        consolidation_plan: *mut tiledb_consolidation_plan_t,
+        num_nodes: *mut u64,
+    ) -> capi_return_t;
+
+    pub fn tiledb_consolidation_plan_get_num_fragments(
+        ctx: *mut tiledb_ctx_t,
+        consolidation_plan: *mut tiledb_consolidation_plan_t,
+        node_index: u64,
+        num_fragments: *mut u64,
+    ) -> capi_return_t;
+
+    pub fn tiledb_consolidation_plan_get_fragment_uri(
+        ctx: *mut tiledb_ctx_t,
+        consolidation_plan: *mut tiledb_consolidation_plan_t,
+        node_index: u64,
+        fragment_index: u64,
+        frag_uri: *mut *const ::std::os::raw::c_char,
+    ) -> capi_return_t;
+}
@@ -0,0 +1,50 @@
+use crate::capi_enum::{tiledb_data_order_t, tiledb_datatype_t};
+use crate::types::{capi_return_t, tiledb_ctx_t, tiledb_dimension_label_t};
+
+extern "C" {
+    pub fn tiledb_dimension_label_free(
+        dim_label: *mut *mut tiledb_dimension_label_t,
+    );
+
+    pub fn tiledb_dimension_label_get_dimension_index(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        dim_index: *mut u32,
+    ) -> capi_return_t;
+
+    pub fn tiledb_dimension_label_get_label_attr_name(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        label_attr_name: *mut *const ::std::os::raw::c_char,
+    ) -> capi_return_t;
+
+    pub fn tiledb_dimension_label_get_label_cell_val_num(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        label_cell_val_num: *mut u32,
+    ) -> capi_return_t;
+
+    pub fn tiledb_dimension_label_get_label_order(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        label_order: *mut tiledb_data_order_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_dimension_label_get_label_type(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        label_type: *mut tiledb_datatype_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_dimension_label_get_name(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        name: *mut *const ::std::os::raw::c_char,
+    ) -> capi_return_t;
+
+    pub fn tiledb_dimension_label_get_uri(
+        ctx: *mut tiledb_ctx_t,
+        dim_label: *mut tiledb_dimension_label_t,
+        uri: *mut *const ::std::os::raw::c_char,
+    ) -> capi_return_t;
+}
@@ -0,0 +1,65 @@
+use crate::capi_enum::tiledb_datatype_t;
+use crate::types::{
+    capi_return_t, tiledb_ctx_t, tiledb_domain_t, tiledb_ndrectangle_t,
+    tiledb_range_t,
+};
+
+extern "C" {
+    pub fn tiledb_ndrectangle_alloc(
+        ctx: *mut tiledb_ctx_t,
+        domain: *mut tiledb_domain_t,
+        ndr: *mut *mut tiledb_ndrectangle_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_ndrectangle_free(
+        ndr: *mut *mut tiledb_ndrectangle_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_ndrectangle_get_range_from_name(
+        ctx: *mut tiledb_ctx_t,
+        ndr: *mut tiledb_ndrectangle_t,
+        name: *const ::std::os::raw::c_char,
+        range: *mut tiledb_range_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_ndrectangle_get_range(
+        ctx: *mut tiledb_ctx_t,
+        ndr: *mut tiledb_ndrectangle_t,
+        idx: u32,
+        range: *mut tiledb_range_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_ndrectangle_set_range_for_name(
+        ctx: *mut tiledb_ctx_t,
+        ndr: *mut tiledb_ndrectangle_t,
+        name: *const ::std::os::raw::c_char,
+        range: *mut tiledb_range_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_ndrectangle_set_range(
+        ctx: *mut tiledb_ctx_t,
+        ndr: *mut tiledb_ndrectangle_t,
+        idx: u32,
+        range: *mut tiledb_range_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_ndrectangle_get_dtype(
+        ctx: *mut tiledb_ctx_t,
+        ndr: *mut tiledb_ndrectangle_t,
+        idx: u32,
+        type_: *mut tiledb_datatype_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_ndrectangle_get_dtype_from_name(
+        ctx: *mut tiledb_ctx_t,
+        ndr: *mut tiledb_ndrectangle_t,
+        name: *const ::std::os::raw::c_char,
+        type_: *mut tiledb_datatype_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_ndrectangle_get_dim_num(
+        ctx: *mut tiledb_ctx_t,
+        ndr: *mut tiledb_ndrectangle_t,
+        ndim: *mut u32,
+    ) -> capi_return_t;
+}
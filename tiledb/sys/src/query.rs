@@ -2,8 +2,9 @@ use crate::capi_enum::{
     tiledb_layout_t, tiledb_query_status_t, tiledb_query_type_t,
 };
 use crate::types::{
-    tiledb_array_t, tiledb_config_t, tiledb_ctx_t, tiledb_query_condition_t,
-    tiledb_query_t, tiledb_subarray_t,
+    capi_return_t, tiledb_array_t, tiledb_config_t, tiledb_ctx_t,
+    tiledb_query_condition_t, tiledb_query_t, tiledb_string_t,
+    tiledb_subarray_t,
 };
 
 extern "C" {
@@ -22,6 +23,12 @@ extern "C" {
         stats_json: *mut *mut ::std::os::raw::c_char,
     ) -> i32;
 
+    pub fn tiledb_query_get_plan(
+        ctx: *mut tiledb_ctx_t,
+        query: *mut tiledb_query_t,
+        plan: *mut *mut tiledb_string_t,
+    ) -> capi_return_t;
+
     pub fn tiledb_query_set_config(
         ctx: *mut tiledb_ctx_t,
         query: *mut tiledb_query_t,
@@ -106,6 +113,14 @@ extern "C" {
         cond: *const tiledb_query_condition_t,
     ) -> i32;
 
+    pub fn tiledb_query_add_update_value(
+        ctx: *mut tiledb_ctx_t,
+        query: *mut tiledb_query_t,
+        field_name: *const ::std::os::raw::c_char,
+        update_value: *const ::std::os::raw::c_void,
+        update_value_size: u64,
+    ) -> i32;
+
     pub fn tiledb_query_finalize(
         ctx: *mut tiledb_ctx_t,
         query: *mut tiledb_query_t,
@@ -1,9 +1,11 @@
 use crate::capi_enum::{
-    tiledb_array_type_t, tiledb_encryption_type_t, tiledb_layout_t,
+    tiledb_array_type_t, tiledb_data_order_t, tiledb_datatype_t,
+    tiledb_encryption_type_t, tiledb_layout_t,
 };
 use crate::types::{
     capi_return_t, tiledb_array_schema_t, tiledb_attribute_t, tiledb_ctx_t,
-    tiledb_domain_t, tiledb_enumeration_t, tiledb_filter_list_t,
+    tiledb_current_domain_t, tiledb_dimension_label_t, tiledb_domain_t,
+    tiledb_enumeration_t, tiledb_filter_list_t,
 };
 
 extern "C" {
@@ -47,6 +49,12 @@ extern "C" {
         domain: *mut tiledb_domain_t,
     ) -> capi_return_t;
 
+    pub fn tiledb_array_schema_set_current_domain(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        current_domain: *mut tiledb_current_domain_t,
+    ) -> capi_return_t;
+
     pub fn tiledb_array_schema_set_capacity(
         ctx: *mut tiledb_ctx_t,
         array_schema: *mut tiledb_array_schema_t,
@@ -165,6 +173,12 @@ extern "C" {
         domain: *mut *mut tiledb_domain_t,
     ) -> capi_return_t;
 
+    pub fn tiledb_array_schema_get_current_domain(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        current_domain: *mut *mut tiledb_current_domain_t,
+    ) -> capi_return_t;
+
     pub fn tiledb_array_schema_get_tile_order(
         ctx: *mut tiledb_ctx_t,
         array_schema: *const tiledb_array_schema_t,
@@ -197,4 +211,55 @@ extern "C" {
         name: *const ::std::os::raw::c_char,
         has_attr: *mut i32,
     ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_add_dimension_label(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        dim_index: u32,
+        name: *const ::std::os::raw::c_char,
+        label_order: tiledb_data_order_t,
+        label_type: tiledb_datatype_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_get_dimension_label_from_name(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        label_name: *const ::std::os::raw::c_char,
+        dim_label: *mut *mut tiledb_dimension_label_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_has_dimension_label(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *const tiledb_array_schema_t,
+        name: *const ::std::os::raw::c_char,
+        has_dim_label: *mut i32,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_set_dimension_label_filter_list(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        label_name: *const ::std::os::raw::c_char,
+        filter_list: *mut tiledb_filter_list_t,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_set_dimension_label_tile_extent(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        label_name: *const ::std::os::raw::c_char,
+        datatype: tiledb_datatype_t,
+        tile_extent: *const ::std::os::raw::c_void,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_get_dimension_label_num(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        dim_label_num: *mut u64,
+    ) -> capi_return_t;
+
+    pub fn tiledb_array_schema_get_dimension_label_from_index(
+        ctx: *mut tiledb_ctx_t,
+        array_schema: *mut tiledb_array_schema_t,
+        dim_label_index: u64,
+        dim_label: *mut *mut tiledb_dimension_label_t,
+    ) -> capi_return_t;
 }
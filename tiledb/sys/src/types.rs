@@ -152,3 +152,30 @@ pub struct tiledb_array_schema_evolution_t {
 pub struct tiledb_current_domain_t {
     _unused: [u8; 0],
 }
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct tiledb_ndrectangle_t {
+    _unused: [u8; 0],
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct tiledb_dimension_label_t {
+    _unused: [u8; 0],
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct tiledb_range_t {
+    pub min: *const ::std::os::raw::c_void,
+    pub min_size: u64,
+    pub max: *const ::std::os::raw::c_void,
+    pub max_size: u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct tiledb_consolidation_plan_t {
+    _unused: [u8; 0],
+}